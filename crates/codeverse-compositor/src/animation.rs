@@ -0,0 +1,125 @@
+use codeverse_window::NodeId;
+use std::time::{Duration, Instant};
+
+/// Upper bound for animations, whatever `general.animation_duration` says
+pub const MAX_ANIMATION_DURATION: Duration = Duration::from_millis(1000);
+
+/// Cubic ease-out: fast start, gentle stop
+fn ease_out_cubic(t: f64) -> f64 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// Horizontal offset of the incoming workspace `elapsed` into a slide.
+///
+/// Starts a full `width` away on the side given by `direction` (1: comes
+/// in from the right, -1: from the left) and eases to 0.
+pub fn slide_offset(elapsed: Duration, duration: Duration, width: i32, direction: i32) -> i32 {
+    if duration.is_zero() || elapsed >= duration {
+        return 0;
+    }
+
+    let progress = elapsed.as_secs_f64() / duration.as_secs_f64();
+    let remaining = 1.0 - ease_out_cubic(progress);
+    (direction as f64 * width as f64 * remaining).round() as i32
+}
+
+/// What to draw for one frame of a workspace slide
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideFrame {
+    /// Tiled windows of the workspace sliding out
+    pub outgoing: Vec<NodeId>,
+    /// Floating windows of the workspace sliding out, bottom to top
+    pub outgoing_floating: Vec<NodeId>,
+    /// Horizontal offset of the outgoing workspace's windows
+    pub outgoing_offset: i32,
+    /// Horizontal offset of the active workspace's windows
+    pub incoming_offset: i32,
+}
+
+/// A workspace switch being animated as a horizontal slide
+#[derive(Debug, Clone, Copy)]
+pub struct WorkspaceSlide {
+    /// Workspace sliding out
+    pub from: usize,
+    /// Workspace sliding in (the active one)
+    pub to: usize,
+    start: Instant,
+    duration: Duration,
+}
+
+impl WorkspaceSlide {
+    /// Start a slide from workspace `from` to `to`
+    pub fn new(from: usize, to: usize, start: Instant, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            start,
+            duration: duration.min(MAX_ANIMATION_DURATION),
+        }
+    }
+
+    /// Higher workspace numbers come in from the right
+    fn direction(&self) -> i32 {
+        if self.to > self.from {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Offsets of the outgoing and incoming workspace on a `width` wide output
+    pub fn offsets(&self, now: Instant, width: i32) -> (i32, i32) {
+        let incoming = slide_offset(
+            now.saturating_duration_since(self.start),
+            self.duration,
+            width,
+            self.direction(),
+        );
+        (incoming - self.direction() * width, incoming)
+    }
+
+    /// Whether the slide has reached its end
+    pub fn is_finished(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) >= self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slide_offset_over_time() {
+        let duration = Duration::from_millis(200);
+        let at = |ms| slide_offset(Duration::from_millis(ms), duration, 1000, 1);
+
+        assert_eq!(at(0), 1000);
+        assert_eq!(at(100), 125);
+        assert_eq!(at(200), 0);
+        assert_eq!(at(500), 0);
+
+        // Eases monotonically towards 0
+        let offsets: Vec<i32> = (0..=200).step_by(20).map(at).collect();
+        assert!(offsets.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        // Sliding back to a lower workspace comes in from the left
+        assert_eq!(slide_offset(Duration::ZERO, duration, 1000, -1), -1000);
+        assert_eq!(slide_offset(Duration::ZERO, Duration::ZERO, 1000, 1), 0);
+    }
+
+    #[test]
+    fn test_workspace_slide() {
+        let start = Instant::now();
+        let slide = WorkspaceSlide::new(2, 1, start, Duration::from_secs(10));
+
+        // The duration is capped
+        assert!(slide.is_finished(start + MAX_ANIMATION_DURATION));
+        assert!(!slide.is_finished(start + Duration::from_millis(500)));
+
+        // The outgoing workspace stays a full width ahead of the incoming one
+        assert_eq!(slide.offsets(start, 1920), (0, -1920));
+        let (outgoing, incoming) = slide.offsets(start + Duration::from_millis(300), 1920);
+        assert_eq!(outgoing - incoming, 1920);
+        assert_eq!(slide.offsets(start + MAX_ANIMATION_DURATION, 1920), (1920, 0));
+    }
+}
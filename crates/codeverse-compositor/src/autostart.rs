@@ -0,0 +1,105 @@
+use crate::hooks::reap_in_background;
+use smithay::reexports::calloop::{
+    timer::{TimeoutAction, Timer},
+    LoopHandle,
+};
+use std::process::Command;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Build the command for a `general.autostart` entry: run through `sh -c`
+/// and connected to our Wayland socket
+pub fn autostart_command(command: &str, socket_name: Option<&str>) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    if let Some(socket) = socket_name {
+        cmd.env("WAYLAND_DISPLAY", socket);
+    }
+    cmd
+}
+
+/// Spawn an autostart command without waiting for it
+pub fn spawn_autostart(command: &str, socket_name: Option<&str>) {
+    match autostart_command(command, socket_name).spawn() {
+        Ok(child) => {
+            info!("Autostarted '{}' (PID: {})", command, child.id());
+            reap_in_background(child, "Autostart command");
+        }
+        Err(e) => warn!("Failed to autostart '{}': {}", command, e),
+    }
+}
+
+/// Run `spawn` for each of `commands` once `delay` has passed, from a
+/// one-shot timer on the event loop. Call after the Wayland socket source
+/// is inserted so the commands find it listening.
+pub fn schedule_autostart<Data: 'static>(
+    handle: &LoopHandle<'static, Data>,
+    delay: Duration,
+    commands: Vec<String>,
+    mut spawn: impl FnMut(&str) + 'static,
+) {
+    if commands.is_empty() {
+        return;
+    }
+
+    info!("Starting {} autostart command(s) in {:?}", commands.len(), delay);
+    let scheduled = handle.insert_source(Timer::from_duration(delay), move |_, _, _| {
+        for command in &commands {
+            spawn(command);
+        }
+        TimeoutAction::Drop
+    });
+
+    if let Err(e) = scheduled {
+        warn!("Failed to schedule autostart: {}", e.error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use smithay::reexports::calloop::EventLoop;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Instant;
+
+    #[test]
+    fn test_autostart_runs_after_delay() {
+        let mut event_loop: EventLoop<'static, ()> = EventLoop::try_new().unwrap();
+        let started = Rc::new(RefCell::new(Vec::new()));
+        let delay = Duration::from_millis(50);
+
+        let scheduled_at = Instant::now();
+        let record = started.clone();
+        schedule_autostart(
+            &event_loop.handle(),
+            delay,
+            vec!["waybar".to_string(), "mako".to_string()],
+            move |command| record.borrow_mut().push((command.to_string(), Instant::now())),
+        );
+
+        // Nothing runs before the delay
+        event_loop.dispatch(Some(Duration::ZERO), &mut ()).unwrap();
+        assert!(started.borrow().is_empty());
+
+        while started.borrow().is_empty() && scheduled_at.elapsed() < Duration::from_secs(5) {
+            event_loop.dispatch(Some(delay), &mut ()).unwrap();
+        }
+
+        let started = started.borrow();
+        let commands: Vec<&str> = started.iter().map(|(command, _)| command.as_str()).collect();
+        assert_eq!(commands, ["waybar", "mako"]);
+        assert!(started.iter().all(|(_, at)| at.duration_since(scheduled_at) >= delay));
+    }
+
+    #[test]
+    fn test_autostart_command() {
+        let cmd = autostart_command("waybar -c ~/.config/waybar", Some("wayland-1"));
+        assert_eq!(cmd.get_program(), "sh");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["-c", "waybar -c ~/.config/waybar"]);
+        assert!(cmd
+            .get_envs()
+            .any(|(key, value)| key == "WAYLAND_DISPLAY" && value == Some("wayland-1".as_ref())));
+    }
+}
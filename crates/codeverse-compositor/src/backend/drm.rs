@@ -1,5 +1,9 @@
 use crate::compositor::{ClientState, CodeVerseCompositor};
-use crate::render::{create_border_elements, load_cached_wallpaper, make_wallpaper_key, OutputRenderElements};
+use crate::render::{create_border_elements, create_shadow_elements, crop_to_output, load_cached_wallpaper, make_wallpaper_key, overview_rects, OutputRenderElements, Panel, SHADOW_RADIUS};
+use super::mirror::MirrorTransform;
+use super::output_info::OutputIdentity;
+use super::tablet_pad::{pad_button, TabletPads};
+use codeverse_window::TabbedLayout;
 use smithay::{
     backend::{
         allocator::{
@@ -32,7 +36,10 @@ use smithay::{
     },
     output::{Mode as WlMode, Output, PhysicalProperties, Subpixel},
     reexports::{
-        calloop::{EventLoop, RegistrationToken},
+        calloop::{
+            generic::Generic,
+            EventLoop, Interest, Mode, PostAction, RegistrationToken,
+        },
         drm::control::{connector, crtc, ModeTypeFlags},
         input::Libinput,
         rustix::fs::OFlags,
@@ -41,6 +48,7 @@ use smithay::{
     utils::{DeviceFd, IsAlive, Physical, Point, Rectangle},
     wayland::socket::ListeningSocketSource,
 };
+use smithay_drm_extras::display_info;
 use smithay_drm_extras::drm_scanner::{DrmScanEvent, DrmScanner};
 use std::{
     collections::HashMap,
@@ -64,6 +72,7 @@ pub struct DrmBackendData {
     primary_gpu: DrmNode,
     gpus: GpuManager<GbmGlesBackend<GlesRenderer, DrmDeviceFd>>,
     backends: HashMap<DrmNode, BackendData>,
+    tablet_pads: TabletPads,
 }
 
 // User data type for DrmOutput (passed to queue_frame, returned on vblank)
@@ -88,14 +97,6 @@ struct SurfaceData {
 }
 
 pub fn init_drm() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize logging (same as winit backend)
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
-        )
-        .init();
-
     let mut event_loop = EventLoop::try_new()?;
     let mut display = Display::new()?;
     let display_handle = display.handle();
@@ -125,6 +126,7 @@ pub fn init_drm() -> Result<(), Box<dyn std::error::Error>> {
 
     let data = DrmBackendData {
         dh: display_handle.clone(),
+        tablet_pads: TabletPads::new(&session),
         session,
         primary_gpu,
         gpus,
@@ -174,12 +176,31 @@ pub fn init_drm() -> Result<(), Box<dyn std::error::Error>> {
             compositor.handle_input_event(event);
         })?;
 
+    // Tablet pad buttons come from a libinput context of their own
+    let mut pad_context = compositor.backend_data.tablet_pads.context();
+    event_loop
+        .handle()
+        .insert_source(
+            Generic::new(compositor.backend_data.tablet_pads.context(), Interest::READ, Mode::Level),
+            move |_, _, compositor| {
+                pad_context.dispatch()?;
+                for event in &mut pad_context {
+                    if let Some((button, state)) = pad_button(&event) {
+                        crate::input::handle_tablet_pad_button(compositor, button, state);
+                    }
+                }
+                Ok(PostAction::Continue)
+            },
+        )
+        .map_err(|e| e.error)?;
+
     // Insert session event source (from anvil)
     event_loop
         .handle()
         .insert_source(notifier, move |event, &mut (), compositor| match event {
             SessionEvent::PauseSession => {
                 libinput_context.suspend();
+                compositor.backend_data.tablet_pads.suspend();
                 info!("Session paused");
                 for backend in compositor.backend_data.backends.values_mut() {
                     backend.drm_output_manager.pause();
@@ -190,6 +211,7 @@ pub fn init_drm() -> Result<(), Box<dyn std::error::Error>> {
                 if let Err(err) = libinput_context.resume() {
                     error!("Failed to resume libinput: {:?}", err);
                 }
+                compositor.backend_data.tablet_pads.resume();
                 for backend in compositor.backend_data.backends.values_mut() {
                     backend
                         .drm_output_manager
@@ -197,6 +219,7 @@ pub fn init_drm() -> Result<(), Box<dyn std::error::Error>> {
                         .activate(false)
                         .expect("Failed to activate DRM backend");
                 }
+                compositor.session_activated();
             }
         })?;
 
@@ -209,6 +232,11 @@ pub fn init_drm() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Starting without any connected output is the same as losing them all
+    if !compositor.has_outputs() {
+        compositor.outputs_lost();
+    }
+
     // Insert udev event source (from anvil)
     event_loop
         .handle()
@@ -251,6 +279,15 @@ pub fn init_drm() -> Result<(), Box<dyn std::error::Error>> {
             }
         })?;
 
+    // Start autostart commands once clients can connect
+    let socket_name = compositor.socket_name.clone();
+    crate::autostart::schedule_autostart(
+        &event_loop.handle(),
+        Duration::from_millis(compositor.config.general.autostart_delay_ms),
+        compositor.config.general.autostart.clone(),
+        move |command| crate::autostart::spawn_autostart(command, socket_name.as_deref()),
+    );
+
     // Main event loop
     info!("Starting CodeVerse Compositor with DRM backend");
     loop {
@@ -260,9 +297,29 @@ pub fn init_drm() -> Result<(), Box<dyn std::error::Error>> {
         // Process IPC commands
         compositor.process_ipc();
 
+        // Close the launcher if it has been idle too long
+        compositor.check_launcher_timeout();
+
+        // Cancel an unconfirmed quit prompt
+        compositor.check_quit_timeout();
+
+        // Take down error toasts shown long enough
+        compositor.check_error_toasts();
+
+        // Run the workspace-change hook once switching settles
+        compositor.run_workspace_hook();
+
+        // Drop windows whose client never drew anything
+        compositor.reap_unmapped_windows();
+
         // Render all outputs
         compositor.render_all_outputs();
 
+        // With every output unplugged, keep clients ticking unless paused
+        if compositor.offscreen && !compositor.rendering_paused() {
+            compositor.send_offscreen_frames();
+        }
+
         // Flush Wayland clients
         compositor.display_handle.flush_clients().ok();
     }
@@ -420,19 +477,36 @@ impl CodeVerseCompositor<DrmBackendData> {
     info!("Connector {} connected on crtc {:?}", output_name, crtc);
 
     // Get the preferred mode (from anvil)
-    let mode = connector
+    let Some(mode) = connector
         .modes()
         .iter()
         .find(|mode| mode.mode_type().contains(ModeTypeFlags::PREFERRED))
         .or_else(|| connector.modes().first())
         .copied()
-        .unwrap_or_else(|| {
-            panic!("No mode available for connector {}", output_name);
-        });
+    else {
+        warn!("No mode available for connector {}, skipping", output_name);
+        return;
+    };
+
+    // Some adapters report a bogus 0x0 mode; configuring it would leave a zero-size output
+    let (mode_width, mode_height) = mode.size();
+    if mode_width == 0 || mode_height == 0 {
+        warn!("Connector {} reports an invalid {}x{} mode, skipping", output_name, mode_width, mode_height);
+        return;
+    }
 
     let drm_mode = mode;
     let wl_mode = WlMode::from(mode);
 
+    // Read make/model from EDID so clients can tell monitors apart
+    let display_info = display_info::for_connector(backend.drm_output_manager.device(), connector.handle());
+    let identity = OutputIdentity::from_edid(
+        display_info.as_ref().and_then(|info| info.make()),
+        display_info.as_ref().and_then(|info| info.model()),
+        display_info.as_ref().and_then(|info| info.serial()),
+    );
+    info!("Output {} is {}", output_name, identity.description(&output_name));
+
     // Create Smithay output (from anvil)
     let output = Output::new(
         output_name.clone(),
@@ -443,9 +517,9 @@ impl CodeVerseCompositor<DrmBackendData> {
             )
                 .into(),
             subpixel: Subpixel::Unknown,
-            make: "Unknown".into(),
-            model: output_name.clone(),
-            serial_number: "0".into(),
+            make: identity.make,
+            model: identity.model,
+            serial_number: identity.serial,
         },
     );
 
@@ -506,6 +580,11 @@ impl CodeVerseCompositor<DrmBackendData> {
     );
 
         info!("Output {} configured with mode {:?}", output_name, wl_mode);
+
+        // Bring windows kept offscreen back onto the new output
+        drop(renderer);
+        let (width, height) = drm_mode.size();
+        self.output_restored(codeverse_window::Rectangle::new(0, 0, width as u32, height as u32));
     }
 
     // Adapted from anvil's connector_disconnected
@@ -517,6 +596,40 @@ impl CodeVerseCompositor<DrmBackendData> {
         if let Some(_surface_data) = backend.surfaces.remove(&crtc) {
             info!("Connector on crtc {:?} disconnected", crtc);
         }
+
+        if !self.has_outputs() {
+            self.outputs_lost();
+        }
+    }
+
+    /// Whether any DRM device still drives an output
+    fn has_outputs(&self) -> bool {
+        self.backend_data.backends.values().any(|backend| !backend.surfaces.is_empty())
+    }
+
+    /// Mode size of the output `name` mirrors (`[[outputs]] mirror_of`), if
+    /// that output is connected
+    fn mirror_source_size(&self, name: &str) -> Option<(u32, u32)> {
+        let source = self.config.outputs.iter()
+            .find(|output| output.name == name)?
+            .mirror_of.as_deref()
+            .filter(|&source| source != name)?;
+
+        self.backend_data.backends.values()
+            .flat_map(|backend| backend.surfaces.values())
+            .find(|surface| surface.output.name() == source)
+            .and_then(|surface| surface.output.current_mode())
+            .map(|mode| (mode.size.w as u32, mode.size.h as u32))
+    }
+
+    /// Keep clients ticking while every output is gone (pause_without_outputs = false)
+    fn send_offscreen_frames(&mut self) {
+        let time = self.clock.now().as_millis() as u32;
+        for window_id in self.window_tree.find_windows() {
+            if let Some(toplevel) = self.window_tree.get(window_id).and_then(|c| c.window.as_ref()) {
+                send_frames_surface_tree_drm(toplevel.wl_surface(), time);
+            }
+        }
     }
 
     // Adapted from anvil's device_removed
@@ -524,11 +637,15 @@ impl CodeVerseCompositor<DrmBackendData> {
         if let Some(_backend) = self.backend_data.backends.remove(&node) {
             info!("DRM device {:?} removed", node);
         }
+
+        if !self.offscreen && !self.has_outputs() {
+            self.outputs_lost();
+        }
     }
 
     /// Handle input events from libinput
     fn handle_input_event(&mut self, event: InputEvent<LibinputInputBackend>) {
-        use crate::input::{handle_keyboard_shortcut, handle_pointer_button, handle_pointer_motion, handle_pointer_axis};
+        use crate::input::{handle_keyboard_shortcut, handle_pointer_button, handle_pointer_motion, handle_pointer_axis, handle_super_tap};
         use smithay::backend::input::{
             AbsolutePositionEvent, Axis, Event, KeyState, KeyboardKeyEvent,
             PointerAxisEvent, PointerButtonEvent, PointerMotionEvent,
@@ -546,7 +663,10 @@ impl CodeVerseCompositor<DrmBackendData> {
                 debug!("Keyboard event: key={:?} state={:?}", key_code, state);
 
                 // Process through the seat keyboard
-                let keyboard = self.seat.get_keyboard().unwrap();
+                let Some(keyboard) = self.seat.get_keyboard() else {
+                    warn!("Seat has no keyboard, dropping key event");
+                    return;
+                };
                 keyboard.input::<(), _>(
                     self,
                     key_code,
@@ -554,13 +674,16 @@ impl CodeVerseCompositor<DrmBackendData> {
                     serial,
                     time,
                     |compositor, modifiers, keysym_handle| {
+                        let keysym = keysym_handle.modified_sym();
+
+                        // Tap-Super detection needs both presses and releases
+                        handle_super_tap(compositor, keysym, state == KeyState::Pressed);
+
                         // Only handle key press events for shortcuts
                         if state != KeyState::Pressed {
                             return FilterResult::Forward;
                         }
 
-                        let keysym = keysym_handle.modified_sym();
-
                         // Try to handle as compositor shortcut
                         if handle_keyboard_shortcut(compositor, keysym, *modifiers) {
                             info!("Shortcut handled: {:?}", keysym);
@@ -607,7 +730,20 @@ impl CodeVerseCompositor<DrmBackendData> {
                 let time = Event::time_msec(&event);
                 let location = self.pointer_location;
 
-                handle_pointer_button(self, button, button_state, serial, time, location);
+                // Buttons taken by a binding or the compositor's UI don't reach clients
+                let consumed = handle_pointer_button(self, button, button_state, serial, time, location);
+                if let (false, Some(pointer)) = (consumed, self.seat.get_pointer()) {
+                    pointer.button(
+                        self,
+                        &smithay::input::pointer::ButtonEvent {
+                            serial,
+                            time,
+                            button,
+                            state: button_state,
+                        },
+                    );
+                    pointer.frame(self);
+                }
             }
             InputEvent::PointerAxis { event } => {
                 let horizontal = event.amount(Axis::Horizontal).unwrap_or(0.0);
@@ -621,8 +757,14 @@ impl CodeVerseCompositor<DrmBackendData> {
 
                 handle_pointer_axis(self, frame);
             }
+            InputEvent::DeviceAdded { device } => {
+                self.backend_data.tablet_pads.device_added(&device);
+            }
+            InputEvent::DeviceRemoved { device } => {
+                self.backend_data.tablet_pads.device_removed(&device);
+            }
             _ => {
-                // Other events (touch, tablet, etc.)
+                // Other events (touch, tablet tools, etc.)
             }
         }
     }
@@ -651,6 +793,9 @@ impl CodeVerseCompositor<DrmBackendData> {
         for node in nodes {
             self.render_node_outputs(node);
         }
+
+        // Committed damage is now on screen
+        self.surface_damage.clear_damage();
     }
 
     /// Render all outputs for a specific DRM node
@@ -686,16 +831,22 @@ impl CodeVerseCompositor<DrmBackendData> {
         type RenderElement<'a> = OutputRenderElements<Renderer<'a>>;
 
         // Get screen geometry from the output for layout calculation
-        // Do this before getting the renderer to avoid borrow conflicts
-        let screen_geometry = {
+        // Do this before getting the renderer to avoid borrow conflicts.
+        // A mirroring output lays out its source's scene and scales it to fit.
+        let (screen_geometry, mirror) = {
             let backend = self.backend_data.backends.get(&node).ok_or("Backend not found for geometry")?;
             let surface_data = backend.surfaces.get(&crtc).ok_or("Surface not found for geometry")?;
             let mode = surface_data.output.current_mode().ok_or("No output mode")?;
-            codeverse_window::Rectangle {
-                x: 0,
-                y: 0,
-                width: mode.size.w as u32,
-                height: mode.size.h as u32,
+            let own_size = (mode.size.w as u32, mode.size.h as u32);
+            match self.mirror_source_size(&surface_data.output.name()) {
+                Some(source_size) => (
+                    codeverse_window::Rectangle::new(0, 0, source_size.0, source_size.1),
+                    MirrorTransform::fit(source_size, own_size),
+                ),
+                None => (
+                    codeverse_window::Rectangle::new(0, 0, own_size.0, own_size.1),
+                    MirrorTransform::IDENTITY,
+                ),
             }
         };
 
@@ -707,24 +858,44 @@ impl CodeVerseCompositor<DrmBackendData> {
         let theme_bg_color = Color32F::new(theme_bg_array[0], theme_bg_array[1], theme_bg_array[2], theme_bg_array[3]);
 
         // Cache the screen geometry for the commit handler
-        self.last_screen_geometry = Some(screen_geometry);
+        if !screen_geometry.is_empty() {
+            self.last_screen_geometry = Some(screen_geometry);
+        }
 
         // Calculate layout before rendering to ensure windows have proper geometries
+        let work_area = self.work_area(screen_geometry);
+        let gap_width = self.effective_gap_width();
         if let Some(ref mut manager) = self.workspace_manager {
-            let gap_width = self.config.general.gap_width as i32;
-            manager.layout_active_workspace(&mut self.window_tree, screen_geometry, gap_width);
+            manager.layout_active_workspace(&mut self.window_tree, work_area, gap_width);
         }
+        // Keep transient dialogs over their parents as the layout moves them
+        self.floating_manager.follow_parents(&mut self.window_tree);
+
+        // Lay out the workspace sliding out during a workspace switch
+        let slide = self.workspace_slide_frame(std::time::Instant::now());
+        let fullscreen = self.fullscreen_window();
 
         // Send configure events to windows whose layout size changed
         // (must be done before obtaining the renderer to avoid borrow conflicts)
         self.send_pending_configures();
 
-        // Get renderer for this GPU
-        let mut renderer = self.backend_data.gpus.single_renderer(&render_node)?;
+        // Collect workspace overview rectangles (also needs &self)
+        let overview = if self.overview_active {
+            overview_rects(self)
+        } else {
+            Vec::new()
+        };
+        let quit_prompt = self.quit_prompt(screen_geometry);
+        let error_toasts = self.error_toast_panels(screen_geometry);
+        let taskbar = self.taskbar_panels(screen_geometry);
 
-        // Collect visible windows and their surfaces
+        // Collect visible windows and their surfaces; a fullscreen window
+        // hides the rest of the workspace
         let visible_windows = if let Some(ref manager) = self.workspace_manager {
-            let windows = manager.visible_windows(&self.window_tree);
+            let mut windows = manager.visible_windows(&self.window_tree);
+            if let Some(fullscreen_id) = fullscreen {
+                windows.retain(|&id| id == fullscreen_id);
+            }
             debug!("WorkspaceManager found {} visible windows", windows.len());
             windows
         } else {
@@ -732,19 +903,47 @@ impl CodeVerseCompositor<DrmBackendData> {
             vec![]
         };
 
+        // Bars above the visible tabbed groups
+        let group_bars = if fullscreen.is_none() {
+            TabbedLayout::new().group_bars(&self.window_tree, &visible_windows, self.config.group_background())
+        } else {
+            Vec::new()
+        };
+
+        // Pair each window with its horizontal slide offset; the outgoing
+        // workspace (if sliding), floating windows included, is drawn below
+        // the active one
+        let incoming_offset = slide.as_ref().map_or(0, |slide| slide.incoming_offset);
+        let slide_windows: Vec<_> = slide
+            .iter()
+            .flat_map(|slide| {
+                slide
+                    .outgoing
+                    .iter()
+                    .chain(&slide.outgoing_floating)
+                    .map(|&id| (id, slide.outgoing_offset))
+            })
+            .chain(visible_windows.iter().map(|&id| (id, incoming_offset)))
+            .collect();
+
         // Collect window surfaces with their locations and border data
         let mut window_surfaces = Vec::new();
         let mut border_data: Vec<(codeverse_window::Rectangle, u32, codeverse_config::NordColor, String)> = Vec::new();
 
-        for window_id in &visible_windows {
+        for (window_id, x_offset) in &slide_windows {
             if let Some(container) = self.window_tree.get(*window_id) {
                 debug!("Window {:?} found in tree, has_window={}", window_id, container.window.is_some());
                 if let Some(ref window_handle) = container.window {
-                    let geom = container.geometry;
-                    let location = Point::from((geom.x, geom.y));
+                    let mut geom = container.geometry;
+                    geom.x += x_offset;
+                    let location = geom.loc();
                     let surface = window_handle.wl_surface().clone();
                     debug!("Window {:?} at location {:?}, geom: {:?}", window_id, location, geom);
-                    window_surfaces.push((surface, location));
+                    let opacity = self.window_opacity(*window_id);
+                    // Windows entirely off the output (e.g. slid out) still
+                    // get frame callbacks but nothing is drawn for them
+                    let on_output = geom.intersection(screen_geometry).is_some();
+                    window_surfaces.push((surface, location, opacity, on_output));
 
                     // Collect border data
                     border_data.push((
@@ -761,6 +960,9 @@ impl CodeVerseCompositor<DrmBackendData> {
 
         debug!("Collected {} window surfaces for rendering", window_surfaces.len());
 
+        // Get renderer for this GPU (holds self.backend_data from here on)
+        let mut renderer = self.backend_data.gpus.single_renderer(&render_node)?;
+
         // Create combined render elements list
         let mut render_elements: Vec<RenderElement<'_>> = Vec::new();
 
@@ -791,12 +993,13 @@ impl CodeVerseCompositor<DrmBackendData> {
                                 Transform::Normal,
                                 None,
                             );
+                            let scaled = mirror.rect(screen_geometry);
                             let texture_element = TextureRenderElement::from_texture_buffer(
-                                (0.0, 0.0),
+                                (scaled.x as f64, scaled.y as f64),
                                 &texture_buffer,
                                 None,
                                 None,
-                                None,
+                                Some((scaled.width as i32, scaled.height as i32).into()),
                                 Kind::Unspecified,
                             );
                             render_elements.push(RenderElement::Texture(texture_element));
@@ -810,15 +1013,34 @@ impl CodeVerseCompositor<DrmBackendData> {
             }
         }
 
-        // Add border render elements (behind windows) - only if borders are enabled
+        // Tabbed group bars (behind windows)
+        for bar in &group_bars {
+            let mut rect = bar.rect;
+            rect.x += incoming_offset;
+            render_elements.push(RenderElement::Solid(Panel::new(mirror.rect(rect), bar.background).render_element()));
+        }
+
+        // Soft shadows behind the borders and windows
         let borders_enabled = self.config.general.borders_enabled;
+        if self.config.general.shadows_enabled {
+            for (geom, border_width, _, _) in &border_data {
+                let rect: Rectangle<i32, Physical> = mirror.rect(*geom).into();
+                let border_width = if borders_enabled { mirror.length(*border_width) } else { 0 };
+                for shadow in create_shadow_elements(rect, border_width, mirror.length(SHADOW_RADIUS)) {
+                    render_elements.push(RenderElement::Solid(shadow));
+                }
+            }
+        }
+
+        // Add border render elements (behind windows) - only if borders are enabled
         if borders_enabled {
+            let inner_border = self
+                .config
+                .inner_border()
+                .map(|(width, color)| (mirror.length(width), color));
             for (geom, border_width, color, id) in &border_data {
-                let rect: Rectangle<i32, Physical> = Rectangle::from_loc_and_size(
-                    (geom.x, geom.y),
-                    (geom.width as i32, geom.height as i32),
-                );
-                let borders = create_border_elements(rect, *border_width, *color, id);
+                let rect: Rectangle<i32, Physical> = mirror.rect(*geom).into();
+                let borders = create_border_elements(rect, mirror.length(*border_width), *color, inner_border, id);
                 // Wrap border elements in OutputRenderElements::Solid
                 for border in borders {
                     render_elements.push(RenderElement::Solid(border));
@@ -828,19 +1050,25 @@ impl CodeVerseCompositor<DrmBackendData> {
         let border_count = render_elements.len();
         debug!("Created {} border elements (borders_enabled={})", border_count, borders_enabled);
 
+        // Surfaces are cropped to the output, skipping off-screen parts
+        let output_rect: Rectangle<i32, Physical> = mirror.rect(screen_geometry).into();
+
         // Add window surface elements (on top of borders)
-        for (surface, location) in &window_surfaces {
+        for (surface, location, opacity, on_output) in &window_surfaces {
+            if !on_output {
+                continue;
+            }
             let elements = render_elements_from_surface_tree(
                 &mut renderer,
                 surface,
-                *location,
-                1.0,
-                1.0,
+                Point::from(mirror.point((location.x, location.y))),
+                mirror.scale,
+                *opacity,
                 Kind::Unspecified,
             );
             debug!("Created {} render elements for surface at {:?}", elements.len(), location);
-            for element in elements {
-                render_elements.push(RenderElement::Surface(element));
+            for element in crop_to_output(elements, mirror.scale, output_rect) {
+                render_elements.push(RenderElement::CroppedSurface(element));
             }
         }
 
@@ -851,8 +1079,8 @@ impl CodeVerseCompositor<DrmBackendData> {
             let geo = popup.with_pending_state(|state| state.geometry);
             let parent = popup.get_parent_surface()?;
             let parent_loc = window_surfaces.iter()
-                .find(|(s, _)| *s == parent)
-                .map(|(_, loc)| *loc)
+                .find(|(s, _, _, _)| *s == parent)
+                .map(|(_, loc, _, _)| *loc)
                 .unwrap_or_else(|| Point::from((0, 0)));
             let popup_loc = Point::from((
                 parent_loc.x + geo.loc.x,
@@ -865,25 +1093,27 @@ impl CodeVerseCompositor<DrmBackendData> {
             let elements = render_elements_from_surface_tree(
                 &mut renderer,
                 surface,
-                *location,
-                1.0,
+                Point::from(mirror.point((location.x, location.y))),
+                mirror.scale,
                 1.0,
                 Kind::Unspecified,
             );
-            for element in elements {
-                render_elements.push(RenderElement::Surface(element));
+            for element in crop_to_output(elements, mirror.scale, output_rect) {
+                render_elements.push(RenderElement::CroppedSurface(element));
             }
         }
 
-        // Render layer surfaces (panels, bars, etc.) on top
-        for layer_surface in &self.layer_surfaces {
+        // Render layer surfaces (panels, bars, etc.) on top, unless a
+        // fullscreen window owns the screen
+        let layer_surfaces = if fullscreen.is_some() { &[][..] } else { &self.layer_surfaces[..] };
+        for layer_surface in layer_surfaces {
             if layer_surface.alive() {
-                let layer_loc = Point::from((0i32, 0i32));
+                let layer_loc = Point::from(mirror.point((0, 0)));
                 let elements = render_elements_from_surface_tree(
                     &mut renderer,
                     layer_surface.wl_surface(),
                     layer_loc,
-                    1.0,
+                    mirror.scale,
                     1.0,
                     Kind::Unspecified,
                 );
@@ -893,16 +1123,39 @@ impl CodeVerseCompositor<DrmBackendData> {
             }
         }
 
-        // Render cursor on DRM backend
-        {
+        // Taskbar above windows and layer surfaces
+        for panel in taskbar {
+            let panel = Panel { rect: mirror.rect(panel.rect), ..panel };
+            render_elements.push(RenderElement::Solid(panel.render_element()));
+        }
+
+        // Render workspace overview above everything but the cursor
+        for (rect, color) in overview {
+            render_elements.push(RenderElement::Solid(Panel::new(mirror.rect(rect), color).render_element()));
+        }
+
+        // "Press again to quit" prompt above that
+        if let Some(panel) = quit_prompt {
+            let panel = Panel { rect: mirror.rect(panel.rect), ..panel };
+            render_elements.push(RenderElement::Solid(panel.render_element()));
+        }
+
+        // Error toasts
+        for panel in error_toasts {
+            let panel = Panel { rect: mirror.rect(panel.rect), ..panel };
+            render_elements.push(RenderElement::Solid(panel.render_element()));
+        }
+
+        // Render cursor on DRM backend (unless hidden while typing)
+        if !self.cursor_hidden_by_typing {
             use smithay::input::pointer::CursorImageStatus;
             use smithay::backend::renderer::element::solid::SolidColorRenderElement;
 
-            let cursor_pos = self.pointer_location;
+            let cursor_pos = mirror.point((self.pointer_location.x as i32, self.pointer_location.y as i32));
             match &self.cursor_status {
                 CursorImageStatus::Surface(surface) => {
                     if surface.alive() {
-                        let cursor_loc = Point::from((cursor_pos.x as i32, cursor_pos.y as i32));
+                        let cursor_loc = Point::from(cursor_pos);
                         let elements = render_elements_from_surface_tree(
                             &mut renderer,
                             surface,
@@ -918,10 +1171,7 @@ impl CodeVerseCompositor<DrmBackendData> {
                 }
                 _ => {
                     let cursor_size = smithay::utils::Size::from((8, 12));
-                    let cursor_rect = Rectangle::from_loc_and_size(
-                        (cursor_pos.x as i32, cursor_pos.y as i32),
-                        cursor_size,
-                    );
+                    let cursor_rect = Rectangle::from_loc_and_size(cursor_pos, cursor_size);
                     let cursor_element = SolidColorRenderElement::new(
                         smithay::backend::renderer::element::Id::new(),
                         cursor_rect,
@@ -960,7 +1210,7 @@ impl CodeVerseCompositor<DrmBackendData> {
 
         // Send frame callbacks to windows
         let time = self.clock.now().as_millis() as u32;
-        for (surface, _) in &window_surfaces {
+        for (surface, _, _, _) in &window_surfaces {
             send_frames_surface_tree_drm(surface, time);
         }
 
@@ -0,0 +1,82 @@
+use codeverse_window::Rectangle;
+
+/// Maps a source output's scene onto a mirroring output (`[[outputs]] mirror_of`)
+///
+/// The scene is scaled uniformly to fit the mirror's mode and centered, so
+/// outputs with a different aspect ratio get bars instead of stretching.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MirrorTransform {
+    pub scale: f64,
+    pub offset: (i32, i32),
+}
+
+impl MirrorTransform {
+    /// No mirroring: the output shows its own scene as is
+    pub const IDENTITY: Self = Self { scale: 1.0, offset: (0, 0) };
+
+    /// Fit a `source` sized scene into a `mirror` sized mode
+    pub fn fit(source: (u32, u32), mirror: (u32, u32)) -> Self {
+        if source.0 == 0 || source.1 == 0 {
+            return Self::IDENTITY;
+        }
+
+        let scale = (mirror.0 as f64 / source.0 as f64).min(mirror.1 as f64 / source.1 as f64);
+        let offset = (
+            ((mirror.0 as f64 - source.0 as f64 * scale) / 2.0).round() as i32,
+            ((mirror.1 as f64 - source.1 as f64 * scale) / 2.0).round() as i32,
+        );
+
+        Self { scale, offset }
+    }
+
+    /// Map a point of the source scene
+    pub fn point(&self, (x, y): (i32, i32)) -> (i32, i32) {
+        (
+            (x as f64 * self.scale).round() as i32 + self.offset.0,
+            (y as f64 * self.scale).round() as i32 + self.offset.1,
+        )
+    }
+
+    /// Map a length (e.g. a border width), keeping non-zero lengths visible
+    pub fn length(&self, length: u32) -> u32 {
+        if length == 0 {
+            return 0;
+        }
+        ((length as f64 * self.scale).round() as u32).max(1)
+    }
+
+    /// Map a rectangle of the source scene
+    pub fn rect(&self, rect: Rectangle) -> Rectangle {
+        let (x, y) = self.point((rect.x, rect.y));
+        Rectangle::new(
+            x,
+            y,
+            (rect.width as f64 * self.scale).round() as u32,
+            (rect.height as f64 * self.scale).round() as u32,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirror_fit_scale() {
+        // Same mode: nothing changes
+        let same = MirrorTransform::fit((1920, 1080), (1920, 1080));
+        assert_eq!(same, MirrorTransform::IDENTITY);
+
+        // 1080p laptop onto a 4K projector doubles everything
+        let up = MirrorTransform::fit((1920, 1080), (3840, 2160));
+        assert_eq!(up.scale, 2.0);
+        assert_eq!(up.rect(Rectangle::new(10, 20, 100, 50)), Rectangle::new(20, 40, 200, 100));
+
+        // 16:10 onto 4:3 fits the width and centers vertically
+        let letterbox = MirrorTransform::fit((1920, 1200), (1024, 768));
+        assert!((letterbox.scale - 1024.0 / 1920.0).abs() < f64::EPSILON);
+        assert_eq!(letterbox.offset, (0, 64));
+        assert_eq!(letterbox.point((1920, 1200)), (1024, 704));
+        assert_eq!(letterbox.length(1), 1);
+    }
+}
@@ -1,5 +1,8 @@
 pub mod winit;
 pub mod drm;
+pub mod mirror;
+pub mod output_info;
+pub mod tablet_pad;
 
 pub use winit::init_winit;
 pub use drm::init_drm;
@@ -0,0 +1,51 @@
+/// Make, model and serial number advertised for an output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputIdentity {
+    pub make: String,
+    pub model: String,
+    pub serial: String,
+}
+
+impl OutputIdentity {
+    /// Build from EDID fields, falling back to "Unknown" for anything missing
+    pub fn from_edid(make: Option<String>, model: Option<String>, serial: Option<String>) -> Self {
+        let known = |value: Option<String>| {
+            value
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| "Unknown".to_string())
+        };
+
+        Self {
+            make: known(make),
+            model: known(model),
+            serial: known(serial),
+        }
+    }
+
+    /// Human-friendly description as advertised through xdg-output
+    /// (e.g. "Dell Inc. - DELL U2720Q - DP-1")
+    pub fn description(&self, name: &str) -> String {
+        format!("{} - {} - {}", self.make, self.model, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_description_from_edid() {
+        let identity = OutputIdentity::from_edid(
+            Some("Dell Inc.".to_string()),
+            Some("DELL U2720Q ".to_string()),
+            Some("ABC123".to_string()),
+        );
+        assert_eq!(identity.description("DP-1"), "Dell Inc. - DELL U2720Q - DP-1");
+        assert_eq!(identity.serial, "ABC123");
+
+        // No EDID (or empty fields) falls back to placeholders
+        let identity = OutputIdentity::from_edid(None, Some(String::new()), None);
+        assert_eq!(identity.description("HDMI-A-1"), "Unknown - Unknown - HDMI-A-1");
+    }
+}
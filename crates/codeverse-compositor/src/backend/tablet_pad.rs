@@ -0,0 +1,86 @@
+use smithay::{
+    backend::{
+        input::ButtonState,
+        libinput::LibinputSessionInterface,
+        session::libseat::LibSeatSession,
+    },
+    reexports::input::{
+        event::{pointer::ButtonState as PadButtonState, tablet_pad::TabletPadEvent, Event},
+        Device, DeviceCapability, Libinput,
+    },
+};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+/// Tablet pads, opened a second time in a path-based libinput context
+///
+/// Smithay's libinput backend drops tablet pad events, so pad buttons are
+/// read from this context instead. Pads are added and removed as the main
+/// context reports them, and the context is suspended and resumed with the
+/// session so it doesn't hold device fds across VT switches.
+pub struct TabletPads {
+    context: Libinput,
+    devices: HashMap<String, Device>,
+}
+
+impl TabletPads {
+    pub fn new(session: &LibSeatSession) -> Self {
+        Self {
+            context: Libinput::new_from_path::<LibinputSessionInterface<LibSeatSession>>(session.clone().into()),
+            devices: HashMap::new(),
+        }
+    }
+
+    /// Handle to the pad context, to watch for and read its events
+    pub fn context(&self) -> Libinput {
+        self.context.clone()
+    }
+
+    /// Close the pads' devices while the session is paused
+    pub fn suspend(&self) {
+        self.context.suspend();
+    }
+
+    /// Reopen the pads' devices once the session is active again
+    pub fn resume(&mut self) {
+        if self.context.resume().is_err() {
+            warn!("Failed to resume the tablet pad context");
+        }
+    }
+
+    /// Open `device` here too if it is a tablet pad
+    pub fn device_added(&mut self, device: &Device) {
+        if !device.has_capability(DeviceCapability::TabletPad) || self.devices.contains_key(device.sysname()) {
+            return;
+        }
+
+        let path = format!("/dev/input/{}", device.sysname());
+        match self.context.path_add_device(&path) {
+            Some(pad) => {
+                info!("Tablet pad '{}' at {} available for bindings", device.name(), path);
+                self.devices.insert(device.sysname().to_string(), pad);
+            }
+            None => warn!("Failed to open tablet pad {}", path),
+        }
+    }
+
+    /// Close a pad the main context lost (unplugged or session paused)
+    pub fn device_removed(&mut self, device: &Device) {
+        if let Some(pad) = self.devices.remove(device.sysname()) {
+            self.context.path_remove_device(pad);
+        }
+    }
+}
+
+/// The zero-based button number and state of a pad button event
+pub fn pad_button(event: &Event) -> Option<(u32, ButtonState)> {
+    let Event::TabletPad(TabletPadEvent::Button(event)) = event else {
+        return None;
+    };
+
+    let state = match event.button_state() {
+        PadButtonState::Pressed => ButtonState::Pressed,
+        PadButtonState::Released => ButtonState::Released,
+    };
+    Some((event.button_number(), state))
+}
@@ -1,6 +1,7 @@
 use crate::compositor::{ClientState, CodeVerseCompositor};
-use crate::input::{handle_keyboard_shortcut, handle_pointer_button, handle_pointer_motion};
-use crate::render::{create_border_elements, BorderRenderElement, load_cached_wallpaper, make_wallpaper_key};
+use crate::input::{handle_keyboard_shortcut, handle_pointer_button, handle_pointer_motion, handle_super_tap};
+use crate::render::{create_border_elements, create_shadow_elements, crop_to_output, overview_rects, BorderRenderElement, load_cached_wallpaper, make_wallpaper_key, IconCache, Panel, ICON_PADDING, SHADOW_RADIUS};
+use codeverse_window::TabbedLayout;
 use smithay::{
     backend::{
         input::{
@@ -10,9 +11,11 @@ use smithay::{
         renderer::{
             element::{
                 surface::{render_elements_from_surface_tree, WaylandSurfaceRenderElement},
+                texture::TextureBuffer,
+                utils::CropRenderElement,
                 Kind,
             },
-            gles::GlesRenderer,
+            gles::{GlesRenderer, GlesTexture},
             utils::draw_render_elements,
             Color32F, Frame, Renderer,
         },
@@ -27,22 +30,37 @@ use smithay::{
     utils::{Rectangle, Transform, Logical, Physical, Point, Size, SERIAL_COUNTER},
     wayland::compositor::SurfaceAttributes,
 };
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use tracing::{error, info};
 
 pub struct WinitData {
     pub output: Output,
+    /// Imported app icon textures keyed by (app_id, size)
+    pub icon_textures: HashMap<(String, u32), TextureBuffer<GlesTexture>>,
+    /// `IconCache::generation` the textures were imported from
+    pub icon_generation: u64,
+    /// What the previous frame drew besides client surfaces
+    pub last_scene: Option<FrameScene>,
 }
 
-pub fn init_winit() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize logging
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
-        )
-        .init();
+/// Everything a frame draws besides the contents of client surfaces. When
+/// it matches the previous frame, only committed window damage changed.
+#[derive(Debug, PartialEq)]
+pub struct FrameScene {
+    size: (i32, i32),
+    workspace: Option<usize>,
+    windows: Vec<(codeverse_window::NodeId, i32, i32)>,
+    opacities: Vec<f32>,
+    borders: Vec<(codeverse_window::Rectangle, u32, codeverse_config::NordColor, String)>,
+    app_ids: Vec<Option<String>>,
+    group_bars: Vec<codeverse_window::GroupBar>,
+    panels: Vec<Panel>,
+    overview: Vec<(codeverse_window::Rectangle, codeverse_config::NordColor)>,
+    popups: Vec<(i32, i32)>,
+    layers: usize,
+}
 
+pub fn init_winit() -> Result<(), Box<dyn std::error::Error>> {
     info!("Starting CodeVerse Compositor with Winit backend");
 
     // Create event loop
@@ -79,6 +97,9 @@ pub fn init_winit() -> Result<(), Box<dyn std::error::Error>> {
     // Backend data
     let backend_data = WinitData {
         output: output.clone(),
+        icon_textures: HashMap::new(),
+        icon_generation: 0,
+        last_scene: None,
     };
 
     // Create compositor
@@ -145,6 +166,14 @@ pub fn init_winit() -> Result<(), Box<dyn std::error::Error>> {
         })
         .expect("Failed to insert listening socket into event loop");
 
+    // Start autostart commands once clients can connect
+    crate::autostart::schedule_autostart(
+        &loop_handle,
+        Duration::from_millis(compositor.config.general.autostart_delay_ms),
+        compositor.config.general.autostart.clone(),
+        move |command| crate::autostart::spawn_autostart(command, Some(&socket_name)),
+    );
+
     // Add display to event loop
     loop_handle
         .insert_source(
@@ -177,6 +206,21 @@ pub fn init_winit() -> Result<(), Box<dyn std::error::Error>> {
         // Process IPC commands
         compositor.process_ipc();
 
+        // Close the launcher if it has been idle too long
+        compositor.check_launcher_timeout();
+
+        // Cancel an unconfirmed quit prompt
+        compositor.check_quit_timeout();
+
+        // Take down error toasts shown long enough
+        compositor.check_error_toasts();
+
+        // Run the workspace-change hook once switching settles
+        compositor.run_workspace_hook();
+
+        // Drop windows whose client never drew anything
+        compositor.reap_unmapped_windows();
+
         // Process winit events
         winit_event_loop.dispatch_new_events(|event| match event {
             WinitEvent::Resized { size, .. } => {
@@ -204,10 +248,11 @@ pub fn init_winit() -> Result<(), Box<dyn std::error::Error>> {
                                 0.into(),
                                 0,
                                 |compositor_state, modifiers, keysym_handle| {
+                                    let keysym = keysym_handle.modified_sym();
+                                    handle_super_tap(compositor_state, keysym, event.state() == KeyState::Pressed);
                                     if event.state() != KeyState::Pressed {
                                         return FilterResult::Forward;
                                     }
-                                    let keysym = keysym_handle.modified_sym();
                                     if handle_keyboard_shortcut(compositor_state, keysym, *modifiers) {
                                         FilterResult::Intercept(())
                                     } else {
@@ -226,39 +271,27 @@ pub fn init_winit() -> Result<(), Box<dyn std::error::Error>> {
                         // On press: set focus to window under cursor
                         if button_state == smithay::backend::input::ButtonState::Pressed {
                             if let Some(window_id) = compositor.window_under(location) {
-                                compositor.window_tree.set_focused(Some(window_id));
-                                compositor.update_window_border_colors();
-
-                                // Set seat keyboard focus so key events reach the client
-                                let kb_surface = compositor.window_tree.get(window_id)
-                                    .and_then(|c| c.window.as_ref())
-                                    .map(|t| t.wl_surface().clone());
-                                if let Some(surface) = kb_surface {
-                                    let keyboard = compositor.seat.get_keyboard().unwrap();
-                                    keyboard.set_focus(
-                                        &mut compositor,
-                                        Some(crate::focus::KeyboardFocusTarget::Surface(surface)),
-                                        serial,
-                                    );
-                                }
+                                compositor.focus_window(window_id);
                             }
                         }
 
-                        // Handle floating window operations (move, resize, title bar drag)
-                        handle_pointer_button(&mut compositor, button, button_state, serial, 0, location);
+                        // Handle mouse-button bindings and floating window operations
+                        // (move, resize, title bar drag)
+                        let consumed = handle_pointer_button(&mut compositor, button, button_state, serial, 0, location);
 
                         // Forward button event to seat pointer for client delivery
-                        let pointer = compositor.seat.get_pointer().unwrap();
-                        pointer.button(
-                            &mut compositor,
-                            &smithay::input::pointer::ButtonEvent {
-                                serial,
-                                time: 0,
-                                button,
-                                state: button_state,
-                            },
-                        );
-                        pointer.frame(&mut compositor);
+                        if let (false, Some(pointer)) = (consumed, compositor.seat.get_pointer()) {
+                            pointer.button(
+                                &mut compositor,
+                                &smithay::input::pointer::ButtonEvent {
+                                    serial,
+                                    time: 0,
+                                    button,
+                                    state: button_state,
+                                },
+                            );
+                            pointer.frame(&mut compositor);
+                        }
                     }
                     InputEvent::PointerMotionAbsolute { event } => {
                         let output_size = backend.window_size();
@@ -273,17 +306,18 @@ pub fn init_winit() -> Result<(), Box<dyn std::error::Error>> {
 
                         // Forward to seat pointer with surface focus for client delivery
                         let under = compositor.surface_under(location);
-                        let pointer = compositor.seat.get_pointer().unwrap();
-                        pointer.motion(
-                            &mut compositor,
-                            under,
-                            &smithay::input::pointer::MotionEvent {
-                                location,
-                                serial: SERIAL_COUNTER.next_serial(),
-                                time: 0,
-                            },
-                        );
-                        pointer.frame(&mut compositor);
+                        if let Some(pointer) = compositor.seat.get_pointer() {
+                            pointer.motion(
+                                &mut compositor,
+                                under,
+                                &smithay::input::pointer::MotionEvent {
+                                    location,
+                                    serial: SERIAL_COUNTER.next_serial(),
+                                    time: 0,
+                                },
+                            );
+                            pointer.frame(&mut compositor);
+                        }
                     }
                     InputEvent::PointerMotion { .. } => {
                         // Winit backend only generates absolute motion events, not relative.
@@ -299,9 +333,10 @@ pub fn init_winit() -> Result<(), Box<dyn std::error::Error>> {
                             .value(Axis::Vertical, vertical);
 
                         // Forward to seat pointer for client delivery
-                        let pointer = compositor.seat.get_pointer().unwrap();
-                        pointer.axis(&mut compositor, frame);
-                        pointer.frame(&mut compositor);
+                        if let Some(pointer) = compositor.seat.get_pointer() {
+                            pointer.axis(&mut compositor, frame);
+                            pointer.frame(&mut compositor);
+                        }
                     }
                     _ => {}
                 }
@@ -322,24 +357,31 @@ pub fn init_winit() -> Result<(), Box<dyn std::error::Error>> {
             window_size.h as u32,
         );
 
-        // Cache the screen geometry for the commit handler
-        compositor.last_screen_geometry = Some(screen_rect);
+        // Cache the screen geometry for the commit handler (a minimized
+        // window reports 0x0, keep the last usable size in that case)
+        if !screen_rect.is_empty() {
+            compositor.last_screen_geometry = Some(screen_rect);
+        }
 
+        let work_area = compositor.work_area(screen_rect);
+        let gap_width = compositor.effective_gap_width();
         if let Some(ref mut manager) = compositor.workspace_manager {
-            let gap_width = compositor.config.general.gap_width as i32;
-            manager.layout_active_workspace(&mut compositor.window_tree, screen_rect, gap_width);
+            manager.layout_active_workspace(&mut compositor.window_tree, work_area, gap_width);
         }
+        // Keep transient dialogs over their parents as the layout moves them
+        compositor.floating_manager.follow_parents(&mut compositor.window_tree);
 
         // Send configure events to windows whose layout size changed
         compositor.send_pending_configures();
 
         // Render windows
-        if let Err(err) = render_output(&mut backend, &mut compositor) {
+        let frame_damage = render_output(&mut backend, &mut compositor).unwrap_or_else(|err| {
             error!("Rendering error: {}", err);
-        }
+            None
+        });
 
-        // Present
-        backend.submit(None).expect("Failed to submit frame");
+        // Present, passing on what changed
+        backend.submit(frame_damage.as_deref()).expect("Failed to submit frame");
 
         // Flush protocol messages to clients (critical: without this, clients never receive
         // configure events and can't render)
@@ -350,39 +392,71 @@ pub fn init_winit() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Draw a frame. The whole output is redrawn; the returned damage (`None`
+/// for the whole output) tells the host which parts actually changed.
 fn render_output(
     backend: &mut winit::WinitGraphicsBackend<GlesRenderer>,
     compositor: &mut CodeVerseCompositor<WinitData>,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<Option<Vec<Rectangle<i32, Physical>>>, Box<dyn std::error::Error>> {
     // Get window size for the render area
     let size = backend.window_size();
     let damage = Rectangle::from_loc_and_size((0, 0), size);
 
-    // Get visible windows from workspace manager
-    let visible_windows = if let Some(ref manager) = compositor.workspace_manager {
+    // Lay out the workspace sliding out during a workspace switch
+    let slide = compositor.workspace_slide_frame(std::time::Instant::now());
+    let incoming_offset = slide.as_ref().map_or(0, |slide| slide.incoming_offset);
+    if slide.is_some() {
+        compositor.send_pending_configures();
+    }
+
+    // Get visible windows from workspace manager (a fullscreen window hides
+    // the rest), paired with their horizontal slide offset (the outgoing
+    // workspace first)
+    let fullscreen = compositor.fullscreen_window();
+    let mut visible_windows = if let Some(ref manager) = compositor.workspace_manager {
         manager.visible_windows(&compositor.window_tree)
     } else {
         vec![]
     };
+    if let Some(fullscreen_id) = fullscreen {
+        visible_windows.retain(|&id| id == fullscreen_id);
+    }
+
+    // Bars above the visible tabbed groups
+    let group_bars = if fullscreen.is_none() {
+        TabbedLayout::new().group_bars(&compositor.window_tree, &visible_windows, compositor.config.group_background())
+    } else {
+        Vec::new()
+    };
+
+    let slide_windows: Vec<_> = slide
+        .iter()
+        .flat_map(|slide| slide.outgoing.iter().map(|&id| (id, slide.outgoing_offset)))
+        .chain(visible_windows.into_iter().map(|id| (id, incoming_offset)))
+        .collect();
 
     // Separate tiled and floating windows
     let mut tiled_windows = Vec::new();
     let mut floating_windows_data = Vec::new();
+    // Where each window's surface is drawn, to place its committed damage
+    let mut window_locations: Vec<(codeverse_window::NodeId, i32, i32)> = Vec::new();
 
     // Collect border data for rendering
     let mut tiled_border_data: Vec<(codeverse_window::Rectangle, u32, codeverse_config::NordColor, String)> = Vec::new();
     let mut floating_border_data: Vec<(codeverse_window::Rectangle, u32, codeverse_config::NordColor, String)> = Vec::new();
 
     // First, collect tiled windows
-    for window_id in visible_windows {
+    for (window_id, x_offset) in slide_windows {
         if let Some(container) = compositor.window_tree.get(window_id) {
             if let Some(ref window_handle) = container.window {
-                let geom = container.geometry;
-                let location = Point::from((geom.x, geom.y));
+                let mut geom = container.geometry;
+                geom.x += x_offset;
+                let location = geom.loc();
                 let surface = window_handle.wl_surface().clone();
 
                 if !container.is_floating {
-                    tiled_windows.push((surface, location));
+                    tiled_windows.push((surface, location, compositor.window_opacity(window_id)));
+                    window_locations.push((window_id, location.x, location.y));
                     // Collect border data for tiled windows
                     tiled_border_data.push((
                         geom,
@@ -395,18 +469,32 @@ fn render_output(
         }
     }
 
-    // Then, collect floating windows in stacking order
-    for &window_id in compositor.floating_manager.get_stack() {
+    // Then, collect the floating windows in stacking order: the outgoing
+    // workspace's below the active one's
+    let floating_windows: Vec<_> = slide
+        .iter()
+        .flat_map(|slide| slide.outgoing_floating.iter().map(|&id| (id, slide.outgoing_offset)))
+        .chain(compositor.active_floating_stack().into_iter().map(|id| (id, incoming_offset)))
+        .collect();
+    for (window_id, x_offset) in floating_windows {
         if let Some(container) = compositor.window_tree.get(window_id) {
             if let Some(ref window_handle) = container.window {
-                let geom = container.geometry;
+                let mut geom = container.geometry;
+                geom.x += x_offset;
                 let title_bar_height = compositor.floating_manager.title_bar_height();
 
                 // Adjust window position to account for title bar
                 let window_location = Point::from((geom.x, geom.y + title_bar_height as i32));
                 let surface = window_handle.wl_surface().clone();
-
-                floating_windows_data.push((surface, window_location, geom, container.title.clone()));
+                window_locations.push((window_id, window_location.x, window_location.y));
+
+                floating_windows_data.push((
+                    surface,
+                    window_location,
+                    geom,
+                    container.app_id.clone(),
+                    compositor.window_opacity(window_id),
+                ));
 
                 // Collect border data for floating windows (include title bar in border area)
                 let bordered_geom = codeverse_window::Rectangle::new(
@@ -439,51 +527,60 @@ fn render_output(
     // Collect all render elements BEFORE starting the frame
     // Create border elements for tiled windows (only if borders are enabled)
     let borders_enabled = compositor.config.general.borders_enabled;
+    let inner_border = compositor.config.inner_border();
     let mut tiled_border_elements: Vec<BorderRenderElement> = Vec::new();
     if borders_enabled {
         for (geom, border_width, color, id) in &tiled_border_data {
-            let rect: Rectangle<i32, Physical> = Rectangle::from_loc_and_size(
-                (geom.x, geom.y),
-                (geom.width as i32, geom.height as i32),
-            );
-            let borders = create_border_elements(rect, *border_width, *color, id);
+            let rect: Rectangle<i32, Physical> = (*geom).into();
+            let borders = create_border_elements(rect, *border_width, *color, inner_border, id);
             tiled_border_elements.extend(borders);
         }
     }
 
+    // Soft shadows, drawn behind each window's border
+    let shadows = |border_data: &[(codeverse_window::Rectangle, u32, codeverse_config::NordColor, String)]| {
+        let mut elements: Vec<BorderRenderElement> = Vec::new();
+        if compositor.config.general.shadows_enabled {
+            for (geom, border_width, _, _) in border_data {
+                let border_width = if borders_enabled { *border_width } else { 0 };
+                elements.extend(create_shadow_elements((*geom).into(), border_width, SHADOW_RADIUS));
+            }
+        }
+        elements
+    };
+    let tiled_shadow_elements = shadows(&tiled_border_data);
+    let floating_shadow_elements = shadows(&floating_border_data);
+
     // Create border elements for floating windows (only if borders are enabled)
     let mut floating_border_elements: Vec<BorderRenderElement> = Vec::new();
     if borders_enabled {
         for (geom, border_width, color, id) in &floating_border_data {
-            let rect: Rectangle<i32, Physical> = Rectangle::from_loc_and_size(
-                (geom.x, geom.y),
-                (geom.width as i32, geom.height as i32),
-            );
-            let borders = create_border_elements(rect, *border_width, *color, id);
+            let rect: Rectangle<i32, Physical> = (*geom).into();
+            let borders = create_border_elements(rect, *border_width, *color, inner_border, id);
             floating_border_elements.extend(borders);
         }
     }
 
-    // Tiled windows first
-    let mut tiled_elements: Vec<WaylandSurfaceRenderElement<GlesRenderer>> = Vec::new();
-    for (surface, location) in &tiled_windows {
+    // Tiled windows first, cropped to the output like on DRM
+    let mut tiled_elements: Vec<CropRenderElement<WaylandSurfaceRenderElement<GlesRenderer>>> = Vec::new();
+    for (surface, location, opacity) in &tiled_windows {
         let elements = render_elements_from_surface_tree(
             renderer,
             surface,
             *location,
             1.0,
-            1.0,
+            *opacity,
             Kind::Unspecified,
         );
-        tiled_elements.extend(elements);
+        tiled_elements.extend(crop_to_output(elements, 1.0, damage));
     }
 
     // Floating windows with title bars
     let title_bar_height = compositor.floating_manager.title_bar_height();
     let mut floating_title_bars = Vec::new();
-    let mut floating_elements: Vec<WaylandSurfaceRenderElement<GlesRenderer>> = Vec::new();
+    let mut floating_elements: Vec<CropRenderElement<WaylandSurfaceRenderElement<GlesRenderer>>> = Vec::new();
 
-    for (surface, window_location, geom, _title) in &floating_windows_data {
+    for (surface, window_location, geom, _app_id, opacity) in &floating_windows_data {
         // Store title bar rect for later drawing
         let title_bar_rect = Rectangle::from_loc_and_size(
             (geom.x, geom.y),
@@ -497,10 +594,10 @@ fn render_output(
             surface,
             *window_location,
             1.0,
-            1.0,
+            *opacity,
             Kind::Unspecified,
         );
-        floating_elements.extend(elements);
+        floating_elements.extend(crop_to_output(elements, 1.0, damage));
     }
 
     // Get active workspace index for per-workspace wallpapers
@@ -530,7 +627,7 @@ fn render_output(
 
     // Import wallpaper texture BEFORE starting the frame to avoid borrow conflicts
     use smithay::backend::renderer::ImportMem;
-    use smithay::backend::renderer::element::texture::{TextureBuffer, TextureRenderElement};
+    use smithay::backend::renderer::element::texture::TextureRenderElement;
 
     let wallpaper_texture_buffer = if let Some((data, wp_width, wp_height)) = wallpaper_data {
         match renderer.import_memory(
@@ -555,9 +652,44 @@ fn render_output(
         None
     };
 
-    // Collect layer surface elements before starting the frame (renderer borrow)
+    // Look up title bar and taskbar icons BEFORE starting the frame (renderer borrow);
+    // the textures are imported once and reused until the icon cache is cleared
+    if compositor.backend_data.icon_generation != compositor.icon_cache.generation() {
+        compositor.backend_data.icon_textures.clear();
+        compositor.backend_data.icon_generation = compositor.icon_cache.generation();
+    }
+    let screen = codeverse_window::Rectangle::new(0, 0, size.w as u32, size.h as u32);
+    let icon_size = compositor.title_bar_icon_size();
+    let mut title_bar_icons = Vec::new();
+    if icon_size > 0 {
+        for (_, _, geom, app_id, _) in &floating_windows_data {
+            let Some(app_id) = app_id else {
+                continue;
+            };
+            let textures = &mut compositor.backend_data.icon_textures;
+            if let Some(buffer) = icon_texture(renderer, textures, &compositor.icon_cache, app_id, icon_size) {
+                let location = (
+                    (geom.x + ICON_PADDING as i32) as f64,
+                    (geom.y + ICON_PADDING as i32) as f64,
+                );
+                title_bar_icons.push((location, buffer));
+            }
+        }
+    }
+    let mut taskbar_icons = Vec::new();
+    for (app_id, rect) in compositor.taskbar_icons(screen) {
+        let textures = &mut compositor.backend_data.icon_textures;
+        if let Some(buffer) = icon_texture(renderer, textures, &compositor.icon_cache, &app_id, rect.width) {
+            taskbar_icons.push(((rect.x as f64, rect.y as f64), buffer));
+        }
+    }
+
+    // Collect layer surface elements before starting the frame (renderer borrow);
+    // panels and bars stay hidden while a window is fullscreen
     let mut layer_elements: Vec<WaylandSurfaceRenderElement<GlesRenderer>> = Vec::new();
-    for layer_surface in &compositor.layer_surfaces {
+    let layer_surfaces = if fullscreen.is_some() { &[][..] } else { &compositor.layer_surfaces[..] };
+    let layer_count = layer_surfaces.iter().filter(|layer_surface| layer_surface.alive()).count();
+    for layer_surface in layer_surfaces {
         if layer_surface.alive() {
             let layer_loc = Point::from((0i32, 0i32));
             let elements = render_elements_from_surface_tree(
@@ -575,20 +707,22 @@ fn render_output(
     // Collect popup elements before starting the frame
     compositor.popups.retain(|p| p.alive());
     let mut popup_elements: Vec<WaylandSurfaceRenderElement<GlesRenderer>> = Vec::new();
+    let mut popup_locations = Vec::new();
     for popup in &compositor.popups {
         let geo = popup.with_pending_state(|state| state.geometry);
         if let Some(parent) = popup.get_parent_surface() {
             let parent_loc = tiled_windows.iter()
-                .find(|(s, _)| *s == parent)
-                .map(|(_, loc)| *loc)
+                .find(|(s, _, _)| *s == parent)
+                .map(|(_, loc, _)| *loc)
                 .or_else(|| floating_windows_data.iter()
-                    .find(|(s, _, _, _)| *s == parent)
-                    .map(|(_, loc, _, _)| *loc))
+                    .find(|(s, _, _, _, _)| *s == parent)
+                    .map(|(_, loc, _, _, _)| *loc))
                 .unwrap_or_else(|| Point::from((0, 0)));
             let popup_loc = Point::from((
                 parent_loc.x + geo.loc.x,
                 parent_loc.y + geo.loc.y,
             ));
+            popup_locations.push((popup_loc.x, popup_loc.y));
             let elements = render_elements_from_surface_tree(
                 renderer,
                 popup.wl_surface(),
@@ -601,6 +735,48 @@ fn render_output(
         }
     }
 
+    // Collect workspace overview rectangles before starting the frame
+    let overview = if compositor.overview_active {
+        overview_rects(compositor)
+    } else {
+        Vec::new()
+    };
+    let quit_prompt = compositor.quit_prompt(screen);
+    let error_toasts = compositor.error_toast_panels(screen);
+    let taskbar = compositor.taskbar_panels(screen);
+
+    // Repaint only the committed window damage when nothing else on the
+    // output changed since the last frame
+    let scene = FrameScene {
+        size: (size.w, size.h),
+        workspace: workspace_index,
+        windows: window_locations.clone(),
+        opacities: tiled_windows
+            .iter()
+            .map(|(_, _, opacity)| *opacity)
+            .chain(floating_windows_data.iter().map(|(_, _, _, _, opacity)| *opacity))
+            .collect(),
+        borders: tiled_border_data.iter().chain(&floating_border_data).cloned().collect(),
+        app_ids: floating_windows_data.iter().map(|(_, _, _, app_id, _)| app_id.clone()).collect(),
+        group_bars: group_bars.clone(),
+        panels: taskbar.iter().chain(&error_toasts).chain(&quit_prompt).copied().collect(),
+        overview: overview.clone(),
+        popups: popup_locations,
+        layers: layer_count,
+    };
+    let frame_damage = if compositor.backend_data.last_scene.as_ref() == Some(&scene) {
+        compositor.surface_damage.frame_damage(&window_locations).map(|rects| {
+            rects
+                .into_iter()
+                .filter_map(|rect| rect.intersection(screen))
+                .map(Rectangle::from)
+                .collect()
+        })
+    } else {
+        None
+    };
+    compositor.backend_data.last_scene = Some(scene);
+
     // Start a render frame
     let mut frame = renderer.render(&mut framebuffer, size, Transform::Flipped180)?;
 
@@ -623,7 +799,20 @@ fn render_output(
         }
     }
 
-    // Draw tiled window borders
+    // Draw tabbed group bars
+    for bar in &group_bars {
+        let mut rect = bar.rect;
+        rect.x += incoming_offset;
+        let panel = Panel::new(rect, bar.background);
+        if let Err(e) = frame.clear(panel.color(), &[panel.physical_rect()]) {
+            tracing::warn!("Failed to draw group bar: {:?}", e);
+        }
+    }
+
+    // Draw tiled window shadows and borders
+    if let Err(e) = draw_render_elements::<GlesRenderer, _, _>(&mut frame, 1.0, &tiled_shadow_elements, &[damage]) {
+        tracing::warn!("Failed to draw tiled shadow elements: {:?}", e);
+    }
     // Note: Explicitly specify element type since SolidColorRenderElement is generic over renderer
     if let Err(e) = draw_render_elements::<GlesRenderer, _, _>(&mut frame, 1.0, &tiled_border_elements, &[damage]) {
         tracing::warn!("Failed to draw tiled border elements: {:?}", e);
@@ -634,6 +823,11 @@ fn render_output(
         tracing::warn!("Failed to draw tiled window elements: {:?}", e);
     }
 
+    // Draw floating window shadows (behind the title bars)
+    if let Err(e) = draw_render_elements::<GlesRenderer, _, _>(&mut frame, 1.0, &floating_shadow_elements, &[damage]) {
+        tracing::warn!("Failed to draw floating shadow elements: {:?}", e);
+    }
+
     // Draw floating window title bars
     for title_bar_rect in &floating_title_bars {
         if let Err(e) = frame.clear(title_bar_color, &[*title_bar_rect]) {
@@ -641,6 +835,17 @@ fn render_output(
         }
     }
 
+    // Draw app icons in the title bars
+    let icon_elements: Vec<_> = title_bar_icons
+        .iter()
+        .map(|(location, buffer)| {
+            TextureRenderElement::from_texture_buffer(*location, buffer, None, None, None, Kind::Unspecified)
+        })
+        .collect();
+    if let Err(e) = draw_render_elements::<GlesRenderer, _, _>(&mut frame, 1.0, &icon_elements, &[damage]) {
+        tracing::warn!("Failed to draw title bar icons: {:?}", e);
+    }
+
     // Draw floating window borders
     if let Err(e) = draw_render_elements::<GlesRenderer, _, _>(&mut frame, 1.0, &floating_border_elements, &[damage]) {
         tracing::warn!("Failed to draw floating border elements: {:?}", e);
@@ -665,19 +870,60 @@ fn render_output(
         }
     }
 
+    // Draw the taskbar above windows and popups
+    for panel in &taskbar {
+        if let Err(e) = frame.clear(panel.color(), &[panel.physical_rect()]) {
+            tracing::warn!("Failed to draw taskbar: {:?}", e);
+        }
+    }
+    let taskbar_icon_elements: Vec<_> = taskbar_icons
+        .iter()
+        .map(|(location, buffer)| {
+            TextureRenderElement::from_texture_buffer(*location, buffer, None, None, None, Kind::Unspecified)
+        })
+        .collect();
+    if let Err(e) = draw_render_elements::<GlesRenderer, _, _>(&mut frame, 1.0, &taskbar_icon_elements, &[damage]) {
+        tracing::warn!("Failed to draw taskbar icons: {:?}", e);
+    }
+
+    // Draw workspace overview on top of everything
+    for &(rect, color) in &overview {
+        let panel = Panel::new(rect, color);
+        if let Err(e) = frame.clear(panel.color(), &[panel.physical_rect()]) {
+            tracing::warn!("Failed to draw overview: {:?}", e);
+        }
+    }
+
+    // "Press again to quit" prompt
+    if let Some(panel) = quit_prompt {
+        if let Err(e) = frame.clear(panel.color(), &[panel.physical_rect()]) {
+            tracing::warn!("Failed to draw quit prompt: {:?}", e);
+        }
+    }
+
+    // Error toasts
+    for panel in &error_toasts {
+        if let Err(e) = frame.clear(panel.color(), &[panel.physical_rect()]) {
+            tracing::warn!("Failed to draw error toast: {:?}", e);
+        }
+    }
+
     // Finish the frame
     let _ = frame.finish()?;
 
+    // All committed damage is now on screen
+    compositor.surface_damage.clear_damage();
+
     // Send frame callbacks to all windows
     let time = compositor.clock.now().as_millis() as u32;
-    for (surface, _) in &tiled_windows {
+    for (surface, _, _) in &tiled_windows {
         send_frames_surface_tree(surface, time);
     }
-    for (surface, _, _, _) in &floating_windows_data {
+    for (surface, _, _, _, _) in &floating_windows_data {
         send_frames_surface_tree(surface, time);
     }
 
-    Ok(())
+    Ok(frame_damage)
 }
 
 /// Send frame callbacks to a surface tree
@@ -702,3 +948,37 @@ fn send_frames_surface_tree(surface: &smithay::reexports::wayland_server::protoc
         |_, _, &()| true,
     );
 }
+
+/// Texture for an already loaded icon, imported on first use
+fn icon_texture(
+    renderer: &mut GlesRenderer,
+    textures: &mut HashMap<(String, u32), TextureBuffer<GlesTexture>>,
+    icons: &IconCache,
+    app_id: &str,
+    size: u32,
+) -> Option<TextureBuffer<GlesTexture>> {
+    use smithay::backend::renderer::ImportMem;
+
+    let key = (app_id.to_string(), size);
+    if let Some(buffer) = textures.get(&key) {
+        return Some(buffer.clone());
+    }
+
+    let icon = icons.get(app_id, size)?;
+    match renderer.import_memory(
+        &icon.data,
+        smithay::backend::allocator::Fourcc::Abgr8888,
+        (icon.width as i32, icon.height as i32).into(),
+        false,
+    ) {
+        Ok(texture) => {
+            let buffer = TextureBuffer::from_texture(renderer, texture, 1, Transform::Normal, None);
+            textures.insert(key, buffer.clone());
+            Some(buffer)
+        }
+        Err(e) => {
+            tracing::warn!("Failed to import icon for {}: {:?}", app_id, e);
+            None
+        }
+    }
+}
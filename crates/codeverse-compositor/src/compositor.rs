@@ -1,5 +1,10 @@
-use crate::render::{ScaleMode, WallpaperCache};
-use codeverse_config::{Config, NordTheme};
+use crate::animation::{SlideFrame, WorkspaceSlide};
+use crate::hooks::{spawn_hook, workspace_change_command, WorkspaceHook};
+use crate::placement::{PendingPlacement, Placements};
+use crate::input::{ChordState, QuitConfirm, RepeatGuard, SuperTapDetector};
+use crate::render::{centered_rect, IconCache, Panel, ICON_PADDING, ScaleMode, SurfaceDamage, WallpaperCache};
+use crate::toast::{toast_rects, ErrorToasts};
+use codeverse_config::{Action, Config, FloatingDefaults, Keybinding, NordTheme, PresetStore};
 use codeverse_launcher::LauncherState;
 use codeverse_window::{FloatingManager, NodeId, WindowTree, WindowTreeExt, WorkspaceManager};
 use smithay::{
@@ -10,7 +15,7 @@ use smithay::{
         calloop::LoopHandle,
         wayland_server::{
             backend::{ClientData, ClientId, DisconnectReason},
-            Display, DisplayHandle,
+            Display, DisplayHandle, Resource,
         },
     },
     utils::{Clock, Logical, Monotonic, Point},
@@ -26,11 +31,14 @@ use smithay::{
             },
         },
         fractional_scale::FractionalScaleManagerState,
+        keyboard_shortcuts_inhibit::KeyboardShortcutsInhibitState,
         shm::ShmState,
         viewporter::ViewporterState,
     },
 };
 use smithay::wayland::selection::data_device::DataDeviceState;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 use tracing::info;
 
 /// Main compositor state
@@ -77,6 +85,13 @@ pub struct CodeVerseCompositor<BackendData: 'static> {
     /// Fractional scale manager state
     pub fractional_scale_manager_state: FractionalScaleManagerState,
 
+    /// Keyboard shortcuts inhibit state (zwp_keyboard_shortcuts_inhibit_manager_v1)
+    pub keyboard_shortcuts_inhibit_state: KeyboardShortcutsInhibitState,
+
+    /// Windows holding an active shortcuts inhibitor; compositor shortcuts
+    /// are forwarded to these windows while they are focused
+    pub shortcuts_inhibited_windows: HashSet<NodeId>,
+
     /// Output manager state (displays)
     pub output_manager_state: OutputManagerState,
 
@@ -101,6 +116,15 @@ pub struct CodeVerseCompositor<BackendData: 'static> {
     /// Nord theme
     pub theme: NordTheme,
 
+    /// Parsed keyboard and mouse-button bindings from the config
+    pub bindings: HashMap<Keybinding, Action>,
+
+    /// Parsed multi-step bindings ("Super+space w c")
+    pub chords: Vec<(Vec<Keybinding>, Action)>,
+
+    /// Progress through the chord being typed
+    pub chord_state: ChordState,
+
     /// Monotonic clock
     pub clock: Clock<Monotonic>,
 
@@ -116,12 +140,55 @@ pub struct CodeVerseCompositor<BackendData: 'static> {
     /// Is launcher currently active?
     pub launcher_active: bool,
 
+    /// Time of the last launcher input (for launcher.auto_close_ms)
+    pub launcher_last_activity: Instant,
+
+    /// Tracks Super presses for the tap-Super action
+    pub super_tap: SuperTapDetector,
+
+    /// Keeps destructive actions from firing again on key repeat
+    pub repeat_guard: RepeatGuard,
+
+    /// Pending "press again to quit" prompt (general.confirm_quit)
+    pub quit_confirm: QuitConfirm,
+
+    /// Errors shown on screen (general.error_toasts)
+    pub error_toasts: ErrorToasts,
+
+    /// Cursor hidden by a key press (input.hide_cursor_while_typing)
+    pub cursor_hidden_by_typing: bool,
+
+    /// Workspace switch slide in progress (general.animations)
+    pub workspace_slide: Option<WorkspaceSlide>,
+
+    /// Debounce state for the general.on_workspace_change hook
+    pub workspace_hook: WorkspaceHook,
+
+    /// Workspaces requested for apps launched with `:N` in the launcher
+    pub pending_placements: Placements,
+
+    /// Is the workspace overview currently shown?
+    pub overview_active: bool,
+
+    /// Workspace number highlighted in the overview
+    pub overview_selected: usize,
+
     /// Wallpaper cache for storing loaded and scaled textures
     pub wallpaper_cache: WallpaperCache,
 
+    /// App icons for title bars, keyed by app_id
+    pub icon_cache: IconCache,
+
+    /// Damage committed by windows since the last frame
+    pub surface_damage: SurfaceDamage,
+
     /// Cached screen geometry (updated during rendering, used by commit handler)
     pub last_screen_geometry: Option<codeverse_window::Rectangle>,
 
+    /// Every output is disconnected; windows stay on the virtual output
+    /// (keeping the last screen geometry) until one returns
+    pub offscreen: bool,
+
     /// Current pointer location (tracked for DRM/bare-metal backends)
     pub pointer_location: Point<f64, Logical>,
 
@@ -134,6 +201,12 @@ pub struct CodeVerseCompositor<BackendData: 'static> {
     /// IPC server for external tooling
     pub ipc_server: Option<codeverse_ipc::IpcServer>,
 
+    /// Saved layout presets
+    pub layout_presets: PresetStore,
+
+    /// Name of the most recently applied preset (for cycling)
+    pub active_preset: Option<String>,
+
     /// Backend-specific data
     pub backend_data: BackendData,
 }
@@ -158,6 +231,7 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         let layer_shell_state = WlrLayerShellState::new::<Self>(&display_handle);
         let viewporter_state = ViewporterState::new::<Self>(&display_handle);
         let fractional_scale_manager_state = FractionalScaleManagerState::new::<Self>(&display_handle);
+        let keyboard_shortcuts_inhibit_state = KeyboardShortcutsInhibitState::new::<Self>(&display_handle);
 
         // Create seat
         let mut seat = seat_state.new_wl_seat(&display_handle, "seat-0");
@@ -177,11 +251,22 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
 
         let window_tree = WindowTree::new();
         let workspace_manager = None; // Will be initialized when output is created
-        let floating_manager = FloatingManager::new();
+        let mut floating_manager = FloatingManager::new();
+        floating_manager.set_saved_geometries(FloatingDefaults::load().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load floating defaults: {}, starting empty", e);
+            FloatingDefaults::default()
+        }));
         let output_node = None;
         let theme = config.get_theme();
+        let bindings = config.keybindings.parse_all();
+        let chords = config.keybindings.parse_chords();
         let clock = Clock::new();
 
+        let layout_presets = PresetStore::load().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load layout presets: {}, starting empty", e);
+            PresetStore::default()
+        });
+
         Self {
             display_handle,
             loop_handle,
@@ -197,6 +282,8 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
             layer_surfaces: Vec::new(),
             viewporter_state,
             fractional_scale_manager_state,
+            keyboard_shortcuts_inhibit_state,
+            shortcuts_inhibited_windows: HashSet::new(),
             output_manager_state,
             seat,
             window_tree,
@@ -205,17 +292,36 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
             output_node,
             config,
             theme,
+            bindings,
+            chords,
+            chord_state: ChordState::default(),
             clock,
             running: true,
             socket_name: None,
             launcher: None, // Initialized lazily on first use
             launcher_active: false,
+            launcher_last_activity: Instant::now(),
+            super_tap: SuperTapDetector::default(),
+            repeat_guard: RepeatGuard::default(),
+            quit_confirm: QuitConfirm::default(),
+            error_toasts: ErrorToasts::default(),
+            cursor_hidden_by_typing: false,
+            workspace_slide: None,
+            workspace_hook: WorkspaceHook::default(),
+            pending_placements: Placements::default(),
+            overview_active: false,
+            overview_selected: 1,
             wallpaper_cache: WallpaperCache::new(),
+            icon_cache: IconCache::new(),
+            surface_damage: SurfaceDamage::new(),
             last_screen_geometry: None,
+            offscreen: false,
             pointer_location: (0.0, 0.0).into(),
             cursor_status: CursorImageStatus::default_named(),
             popups: Vec::new(),
             ipc_server: codeverse_ipc::IpcServer::bind().ok(),
+            layout_presets,
+            active_preset: None,
             backend_data,
         }
     }
@@ -229,12 +335,19 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         let output_id = self.window_tree.insert(output);
 
         // Create workspace manager
-        let workspace_manager = WorkspaceManager::new(&mut self.window_tree, output_id);
+        let mut workspace_manager = WorkspaceManager::new(&mut self.window_tree, output_id);
+
+        // Start on the configured workspace
+        let startup = self.config.workspaces.startup_workspace();
+        workspace_manager.switch_to_workspace(startup);
 
         self.output_node = Some(output_id);
         self.workspace_manager = Some(workspace_manager);
 
-        info!("Workspace manager initialized with output {:?}", output_id);
+        info!(
+            "Workspace manager initialized with output {:?} on workspace {}",
+            output_id, startup
+        );
     }
 
     /// Handle a new toplevel window
@@ -244,10 +357,22 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         // Insert window into tree
         if let Some(ref mut workspace_manager) = self.workspace_manager {
             if let Some(workspace_id) = workspace_manager.active_workspace() {
+                // Inherit the focused window's floating state if configured
+                let float_new_window = self.config.general.new_windows_inherit_floating
+                    && self.window_tree.focused().is_some_and(|focused_id| {
+                        self.window_tree.find_workspace(focused_id) == Some(workspace_id)
+                            && self.window_tree.get(focused_id).is_some_and(|c| c.is_floating)
+                    });
+
+                let previous_focus = self.window_tree.focused();
                 match self.window_tree.insert_window(toplevel.clone(), workspace_id) {
                     Ok(window_id) => {
                         info!("Window inserted into tree with id {:?}", window_id);
 
+                        if float_new_window {
+                            self.float_new_window(window_id, previous_focus);
+                        }
+
                         // Set border properties from config
                         if let Some(container) = self.window_tree.get_mut(window_id) {
                             container.border_width = self.config.general.border_width;
@@ -255,6 +380,9 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
                             container.border_color = self.theme.unfocused_border();
                         }
 
+                        // Open it elsewhere if it was launched with a workspace target
+                        self.place_launched_window(window_id);
+
                         // Update border colors for all windows (the new window may be focused)
                         self.update_window_border_colors();
 
@@ -267,6 +395,7 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
                         // Track the initial configured size
                         if let Some(container) = self.window_tree.get_mut(window_id) {
                             container.last_configured_size = Some((800, 600));
+                            container.first_configured_at = Some(Instant::now());
                         }
                     }
                     Err(e) => {
@@ -287,12 +416,37 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         }
     }
 
+    /// Float a window that just mapped on top of the stack. With
+    /// general.focus_new_windows off it's only raised and focus goes back
+    /// to `previous_focus`.
+    pub fn float_new_window(&mut self, window_id: NodeId, previous_focus: Option<NodeId>) {
+        let screen = self
+            .last_screen_geometry
+            .unwrap_or_else(|| codeverse_window::Rectangle::new(0, 0, 1920, 1080));
+        let focus = self.config.general.focus_new_windows;
+
+        if let Err(e) = self
+            .floating_manager
+            .float_new_window(&mut self.window_tree, window_id, screen, focus)
+        {
+            tracing::warn!("Failed to float new window: {}", e);
+            return;
+        }
+        if !focus {
+            self.window_tree.set_focused(previous_focus);
+        }
+    }
+
     /// Handle window close
     pub fn handle_toplevel_closed(&mut self, toplevel: &ToplevelSurface) {
         info!("Toplevel window closed");
 
         // Find and remove window from tree
         if let Some(window_id) = self.window_tree.find_window_by_handle(toplevel) {
+            self.shortcuts_inhibited_windows.remove(&window_id);
+            self.floating_manager.remove_window(window_id);
+            self.surface_damage.remove(window_id);
+
             match self.window_tree.remove_window(window_id) {
                 Ok(()) => {
                     info!("Window {:?} removed from tree", window_id);
@@ -306,6 +460,50 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         }
     }
 
+    /// Float a dialog over its parent when the client sets one (xdg_toplevel.set_parent)
+    pub fn handle_parent_changed(&mut self, toplevel: &ToplevelSurface) {
+        let Some(window_id) = self.window_tree.find_window_by_handle(toplevel) else {
+            return;
+        };
+        let Some(parent_id) = toplevel
+            .parent()
+            .and_then(|parent| self.window_tree.find_window_by_surface(&parent))
+        else {
+            return;
+        };
+
+        match self.floating_manager.set_transient(&mut self.window_tree, window_id, parent_id) {
+            Ok(()) => {
+                info!("Window {:?} is transient for {:?}", window_id, parent_id);
+                self.send_pending_configures();
+            }
+            Err(e) => tracing::warn!("Failed to float transient window: {}", e),
+        }
+    }
+
+    /// Re-tile a floating window dropped onto a tiled window of the active
+    /// workspace (`general.drop_floating_onto_tile`)
+    pub fn drop_floating_window(&mut self, window_id: NodeId, x: i32, y: i32) {
+        let Some(workspace_id) = self.workspace_manager.as_ref().and_then(|manager| manager.active_workspace()) else {
+            return;
+        };
+        let Some(target_id) = self
+            .floating_manager
+            .drop_target(&self.window_tree, workspace_id, window_id, x, y)
+        else {
+            return;
+        };
+
+        match self.floating_manager.drop_into_tile(&mut self.window_tree, window_id, target_id) {
+            Ok(()) => {
+                info!("Dropped window {:?} next to {:?}", window_id, target_id);
+                self.window_tree.set_focused(Some(window_id));
+                self.relayout_active_workspace();
+            }
+            Err(e) => tracing::warn!("Failed to tile dropped window: {}", e),
+        }
+    }
+
     /// Toggle the launcher on/off
     pub fn toggle_launcher(&mut self) {
         self.launcher_active = !self.launcher_active;
@@ -319,8 +517,10 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
 
             // Reset launcher state when opening
             if let Some(ref mut launcher) = self.launcher {
+                launcher.set_recent_count(self.config.launcher.recent_count);
                 launcher.reset();
             }
+            self.launcher_last_activity = Instant::now();
 
             info!("Launcher opened");
         } else {
@@ -328,17 +528,535 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         }
     }
 
+    /// Close the launcher if it has been idle longer than launcher.auto_close_ms.
+    /// Called once per event loop iteration.
+    pub fn check_launcher_timeout(&mut self) {
+        if self.launcher_active
+            && self
+                .config
+                .launcher
+                .should_auto_close(self.launcher_last_activity, Instant::now())
+        {
+            info!("Launcher idle timeout reached, closing");
+            self.launcher_active = false;
+        }
+    }
+
+    /// Close and remove windows that never committed a buffer within
+    /// general.unmapped_window_timeout_ms of their first configure.
+    /// Called once per event loop iteration.
+    pub fn reap_unmapped_windows(&mut self) {
+        let timeout = Duration::from_millis(self.config.general.unmapped_window_timeout_ms);
+        let now = Instant::now();
+        let overdue: Vec<_> = self
+            .window_tree
+            .find_windows()
+            .into_iter()
+            .filter_map(|window_id| {
+                let container = self.window_tree.get(window_id)?;
+                if container.should_reap(now, timeout) {
+                    container.window.clone().map(|toplevel| (window_id, toplevel))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        if overdue.is_empty() {
+            return;
+        }
+
+        for (window_id, toplevel) in overdue {
+            tracing::warn!(
+                "Window {:?} has not committed a buffer {}ms after being configured, removing it",
+                window_id, timeout.as_millis()
+            );
+            toplevel.send_close();
+            self.handle_toplevel_closed(&toplevel);
+        }
+
+        self.update_window_border_colors();
+        self.relayout_active_workspace();
+    }
+
+    /// Cancel a quit prompt that wasn't confirmed in time.
+    /// Called once per event loop iteration.
+    pub fn check_quit_timeout(&mut self) {
+        if self.quit_confirm.expire(Instant::now()) {
+            info!("Quit not confirmed, canceled");
+        }
+    }
+
+    /// Opacity a window is drawn with: its rule's opacity, else the
+    /// theme's default (focus doesn't change it)
+    pub fn window_opacity(&self, window_id: NodeId) -> f32 {
+        self.window_tree
+            .get(window_id)
+            .and_then(|container| container.opacity)
+            .unwrap_or(self.theme.opacity)
+    }
+
+    /// Where the taskbar is drawn, if it is enabled and not covered by a
+    /// fullscreen window
+    pub fn taskbar_strip(&self, screen: codeverse_window::Rectangle) -> Option<codeverse_window::Rectangle> {
+        let taskbar = &self.config.taskbar;
+        if !taskbar.enabled || self.fullscreen_window().is_some() {
+            return None;
+        }
+        Some(codeverse_window::taskbar_strip(screen, taskbar.edge == "top", taskbar.height))
+    }
+
+    /// Taskbar buttons with the window each one activates: every window,
+    /// in workspace order. There is no minimized state (minimize requests
+    /// are ignored), so no window is left out and none needs restoring.
+    pub fn taskbar_buttons(&self, screen: codeverse_window::Rectangle) -> Vec<(NodeId, codeverse_window::Rectangle)> {
+        let (Some(strip), Some(manager)) = (self.taskbar_strip(screen), self.workspace_manager.as_ref()) else {
+            return Vec::new();
+        };
+
+        let windows: Vec<NodeId> = (1..=self.config.workspaces.count)
+            .flat_map(|num| manager.workspace_windows(&self.window_tree, num))
+            .collect();
+        let buttons = codeverse_window::taskbar_buttons(strip, windows.len());
+        windows.into_iter().zip(buttons).collect()
+    }
+
+    /// Panels drawing the taskbar: the strip, then one button per window.
+    /// Buttons carry the app icon (see [`Self::taskbar_icons`]) but no
+    /// title, as there is no text rendering yet; the focused window's
+    /// button uses the accent color and windows on other workspaces are
+    /// dimmed.
+    pub fn taskbar_panels(&self, screen: codeverse_window::Rectangle) -> Vec<Panel> {
+        let Some(strip) = self.taskbar_strip(screen) else {
+            return Vec::new();
+        };
+
+        let focused = self.window_tree.focused();
+        let visible = self
+            .workspace_manager
+            .as_ref()
+            .map(|manager| manager.visible_windows(&self.window_tree))
+            .unwrap_or_default();
+
+        let mut panels = vec![Panel::new(strip, self.theme.colors.nord0).with_opacity(0.9)];
+        for (window_id, rect) in self.taskbar_buttons(screen) {
+            let color = if focused == Some(window_id) {
+                self.theme.accent()
+            } else if visible.contains(&window_id) {
+                self.theme.colors.nord2
+            } else {
+                self.theme.colors.nord1
+            };
+            panels.push(Panel::new(rect, color));
+        }
+        panels
+    }
+
+    /// Size of the app icon drawn in a floating title bar
+    pub fn title_bar_icon_size(&self) -> u32 {
+        self.floating_manager.title_bar_height().saturating_sub(ICON_PADDING * 2)
+    }
+
+    /// Size of the app icon drawn on a taskbar button
+    pub fn taskbar_icon_size(&self) -> u32 {
+        self.config
+            .taskbar
+            .height
+            .saturating_sub((codeverse_window::workspace::taskbar::TASKBAR_BUTTON_GAP + ICON_PADDING) * 2)
+    }
+
+    /// App icons on the taskbar buttons: the app_id and where to draw it,
+    /// for buttons wide enough to hold one
+    pub fn taskbar_icons(&self, screen: codeverse_window::Rectangle) -> Vec<(String, codeverse_window::Rectangle)> {
+        let size = self.taskbar_icon_size();
+        if size == 0 {
+            return Vec::new();
+        }
+
+        self.taskbar_buttons(screen)
+            .into_iter()
+            .filter(|(_, rect)| rect.width >= size + ICON_PADDING * 2)
+            .filter_map(|(window_id, rect)| {
+                let app_id = self.window_tree.get(window_id)?.app_id.clone()?;
+                let icon = codeverse_window::Rectangle::new(
+                    rect.x + ICON_PADDING as i32,
+                    rect.y + ICON_PADDING as i32,
+                    size,
+                    size,
+                );
+                Some((app_id, icon))
+            })
+            .collect()
+    }
+
+    /// Load a window's icons for its title bar and taskbar button, so the
+    /// render path only has to look them up
+    pub fn load_window_icons(&mut self, window_id: NodeId) {
+        let Some(app_id) = self.window_tree.get(window_id).and_then(|container| container.app_id.clone()) else {
+            return;
+        };
+        for size in [self.title_bar_icon_size(), self.taskbar_icon_size()] {
+            if size > 0 {
+                self.icon_cache.get_or_load(&app_id, size);
+            }
+        }
+    }
+
+    /// On-screen prompt shown while a quit waits for confirmation. It is a
+    /// plain warning-colored box: there is no text rendering yet, so the
+    /// "press again to quit" message only goes to the log.
+    pub fn quit_prompt(&self, screen: codeverse_window::Rectangle) -> Option<Panel> {
+        if !self.quit_confirm.is_pending(Instant::now()) {
+            return None;
+        }
+        Some(Panel::new(centered_rect(screen, 400, 80), self.theme.warning()).with_opacity(0.9))
+    }
+
+    /// Log an error the user should notice and, with general.error_toasts,
+    /// flag it on screen. The toast is only an indicator: there is no text
+    /// rendering yet, so the message itself is in the log.
+    pub fn show_error(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        tracing::error!("{}", message);
+        if self.config.general.error_toasts {
+            self.error_toasts.push(message, Instant::now());
+        }
+    }
+
+    /// Drop error toasts that have been shown long enough.
+    /// Called once per event loop iteration.
+    pub fn check_error_toasts(&mut self) {
+        self.error_toasts.expire(Instant::now());
+    }
+
+    /// Panels of the error toasts on screen, oldest at the top. Each is a
+    /// plain error-colored box; the message is not drawn.
+    pub fn error_toast_panels(&self, screen: codeverse_window::Rectangle) -> Vec<Panel> {
+        toast_rects(screen, self.error_toasts.visible().count())
+            .into_iter()
+            .map(|rect| Panel::new(rect, self.theme.error()).with_opacity(0.9))
+            .collect()
+    }
+
+    /// Whether the focused window currently inhibits compositor shortcuts
+    pub fn shortcuts_inhibited(&self) -> bool {
+        self.window_tree
+            .focused()
+            .is_some_and(|window_id| self.shortcuts_inhibited_windows.contains(&window_id))
+    }
+
+    /// Focus the most recently focused other window, switching to its
+    /// workspace if it lives on another one
+    pub fn focus_previous_window(&mut self) {
+        if let Some(window_id) = self.window_tree.previous_focused() {
+            self.activate_window(window_id);
+        }
+    }
+
+    /// Focus a window (keyboard included), switching to its workspace
+    /// first if needed
+    pub fn activate_window(&mut self, window_id: NodeId) {
+        let workspace_num = self
+            .workspace_manager
+            .as_ref()
+            .and_then(|manager| manager.workspace_num_of(&self.window_tree, window_id));
+
+        // Switch first so focus-follows-mouse doesn't override the target
+        if let Some(num) = workspace_num {
+            self.switch_workspace(num);
+        }
+
+        self.focus_window(window_id);
+        self.relayout_active_workspace();
+    }
+
+    /// Show or hide the workspace overview
+    pub fn toggle_overview(&mut self) {
+        self.overview_active = !self.overview_active;
+
+        if self.overview_active {
+            self.overview_selected = self
+                .workspace_manager
+                .as_ref()
+                .map(|m| m.active_workspace_num())
+                .unwrap_or(1);
+            info!("Workspace overview opened");
+        } else {
+            info!("Workspace overview closed");
+        }
+    }
+
+    /// Workspace numbers shown in the overview: every occupied workspace
+    /// plus the active one, in ascending order
+    pub fn overview_workspaces(&self) -> Vec<usize> {
+        let Some(ref manager) = self.workspace_manager else {
+            return Vec::new();
+        };
+
+        (1..=codeverse_window::MAX_WORKSPACES)
+            .filter(|&num| {
+                num == manager.active_workspace_num()
+                    || manager.workspace_has_windows(&self.window_tree, num)
+            })
+            .collect()
+    }
+
+    /// Overview grid cells paired with the workspace number they show
+    pub fn overview_cells(&self) -> Vec<(usize, codeverse_window::Rectangle)> {
+        const OVERVIEW_GAP: u32 = 40;
+
+        let screen_rect = self
+            .last_screen_geometry
+            .unwrap_or_else(|| codeverse_window::Rectangle::new(0, 0, 1920, 1080));
+        let workspaces = self.overview_workspaces();
+        let cells = codeverse_window::overview_grid(workspaces.len(), screen_rect, OVERVIEW_GAP);
+
+        workspaces.into_iter().zip(cells).collect()
+    }
+
+    /// Move the overview selection by one cell in a direction
+    pub fn overview_navigate(&mut self, direction: codeverse_window::Direction) {
+        use codeverse_window::Direction;
+
+        let workspaces = self.overview_workspaces();
+        let Some(index) = workspaces.iter().position(|&num| num == self.overview_selected) else {
+            self.overview_selected = workspaces.first().copied().unwrap_or(1);
+            return;
+        };
+
+        let columns = codeverse_window::overview_columns(workspaces.len());
+        let target = match direction {
+            Direction::Left => index.checked_sub(1),
+            Direction::Right => Some(index + 1),
+            Direction::Up => index.checked_sub(columns),
+            Direction::Down => Some(index + columns),
+        };
+
+        if let Some(&num) = target.and_then(|i| workspaces.get(i)) {
+            self.overview_selected = num;
+        }
+    }
+
+    /// Switch to a workspace from the overview and close it
+    pub fn overview_confirm(&mut self, workspace_num: usize) {
+        self.overview_active = false;
+        self.switch_workspace(workspace_num);
+    }
+
+    /// Make a workspace active and relayout it. Schedules the
+    /// general.on_workspace_change hook when the workspace actually changes.
+    pub fn switch_workspace(&mut self, workspace_num: usize) {
+        if let Some(ref mut manager) = self.workspace_manager {
+            let previous = manager.active_workspace_num();
+            // All outputs share one workspace manager for now, so global and
+            // per-output switching only differ once outputs get their own
+            let switched = codeverse_window::switch_outputs_to_workspace(
+                std::slice::from_mut(manager),
+                0,
+                workspace_num,
+                self.config.workspaces.is_global(),
+            );
+            if switched.is_some() && previous != workspace_num {
+                self.workspace_hook.workspace_changed(workspace_num, Instant::now());
+
+                if self.config.general.animations && self.config.general.workspace_switch_animation == "slide" {
+                    self.workspace_slide = Some(WorkspaceSlide::new(
+                        previous,
+                        workspace_num,
+                        Instant::now(),
+                        Duration::from_millis(self.theme.animation_duration as u64),
+                    ));
+                }
+            }
+        }
+        self.relayout_active_workspace();
+
+        // The cursor didn't move but the windows under it did
+        if self.config.general.focus_follows_mouse {
+            self.focus_window_under_pointer();
+        }
+    }
+
+    /// Move workspace `from` to slot `to`, taking its windows and name along
+    pub fn move_workspace(&mut self, from: usize, to: usize) -> Result<(), String> {
+        let manager = self.workspace_manager.as_mut().ok_or("No workspaces")?;
+        manager.move_workspace(from, to)?;
+
+        // Slot numbers changed under a running slide
+        self.workspace_slide = None;
+        self.relayout_active_workspace();
+        Ok(())
+    }
+
+    /// Advance the workspace switch slide for a frame drawn at `now`.
+    ///
+    /// Lays out the outgoing workspace so it can be drawn next to the new
+    /// one; returns `None` once the slide is over (or when there is none).
+    pub fn workspace_slide_frame(&mut self, now: Instant) -> Option<SlideFrame> {
+        let slide = self.workspace_slide?;
+        if slide.is_finished(now) {
+            self.workspace_slide = None;
+            return None;
+        }
+
+        let screen = self.last_screen_geometry?;
+        let work_area = self.work_area(screen);
+        let gap_width = self.workspace_gap_width(slide.from);
+        let manager = self.workspace_manager.as_ref()?;
+        manager.layout_workspace(&mut self.window_tree, slide.from, work_area, gap_width);
+
+        let (outgoing_offset, incoming_offset) = slide.offsets(now, screen.width as i32);
+        let outgoing_floating = manager
+            .get_workspace(slide.from)
+            .map(|workspace_id| self.floating_manager.stack_for_workspace(&self.window_tree, workspace_id))
+            .unwrap_or_default();
+        let outgoing = manager
+            .workspace_windows(&self.window_tree, slide.from)
+            .into_iter()
+            .filter(|window_id| !outgoing_floating.contains(window_id))
+            .collect();
+        Some(SlideFrame {
+            outgoing,
+            outgoing_floating,
+            outgoing_offset,
+            incoming_offset,
+        })
+    }
+
+    /// Give keyboard focus to the window under the pointer, if any
+    pub fn focus_window_under_pointer(&mut self) {
+        let Some(window_id) = self.window_under(self.pointer_location) else {
+            return;
+        };
+        if self.window_tree.focused() == Some(window_id) {
+            return;
+        }
+
+        self.focus_window(window_id);
+    }
+
+    /// Ask the focused window to close. With general.close_respects_modals
+    /// a window with an open dialog stays, and the dialog is focused instead.
+    pub fn close_focused_window(&mut self) {
+        let Some(focused_id) = self.window_tree.focused() else {
+            return;
+        };
+
+        if self.config.general.close_respects_modals {
+            if let Some(dialog_id) = self.floating_manager.transient_dialog(focused_id) {
+                info!("Window {:?} has a dialog open, focusing it instead of closing", focused_id);
+                self.focus_window(dialog_id);
+                return;
+            }
+        }
+
+        if let Some(window) = self.window_tree.get(focused_id).and_then(|container| container.window.as_ref()) {
+            window.send_close();
+        }
+    }
+
+    /// Focus a window on the active workspace: raise it (if floating) and
+    /// give it keyboard focus
+    pub fn focus_window(&mut self, window_id: NodeId) {
+        self.window_tree.set_focused(Some(window_id));
+        if self.window_tree.get(window_id).is_some_and(|container| container.is_floating) {
+            self.floating_manager.raise_window(window_id);
+        }
+        self.update_window_border_colors();
+
+        let surface = self
+            .window_tree
+            .get(window_id)
+            .and_then(|container| container.window.as_ref())
+            .map(|toplevel| toplevel.wl_surface().clone());
+        if let (Some(surface), Some(keyboard)) = (surface, self.seat.get_keyboard()) {
+            keyboard.set_focus(
+                self,
+                Some(crate::focus::KeyboardFocusTarget::Surface(surface)),
+                smithay::utils::SERIAL_COUNTER.next_serial(),
+            );
+        }
+    }
+
+    /// Window that should hold keyboard focus: the tree's focused window
+    pub fn keyboard_focus_window(&self) -> Option<NodeId> {
+        self.window_tree.focused().filter(|&id| {
+            self.window_tree
+                .get(id)
+                .is_some_and(|container| container.container_type == codeverse_window::ContainerType::Window)
+        })
+    }
+
+    /// The session is active again after a VT switch. Keys held when
+    /// switching away (e.g. Ctrl+Alt) were released on the other VT, so
+    /// release them here, then re-enter the focused window so it gets
+    /// fresh key and modifier state and typing works right away.
+    pub fn session_activated(&mut self) {
+        use smithay::backend::input::KeyState;
+        use smithay::input::keyboard::FilterResult;
+
+        let Some(keyboard) = self.seat.get_keyboard() else {
+            return;
+        };
+
+        let time = self.clock.now().as_millis() as u32;
+        for keycode in keyboard.pressed_keys() {
+            keyboard.input::<(), _>(
+                self,
+                keycode,
+                KeyState::Released,
+                smithay::utils::SERIAL_COUNTER.next_serial(),
+                time,
+                |_, _, _| FilterResult::Intercept(()),
+            );
+        }
+        self.super_tap.cancel();
+        self.repeat_guard.key_released();
+
+        let target = self
+            .keyboard_focus_window()
+            .and_then(|window_id| self.window_tree.get(window_id))
+            .and_then(|container| container.window.as_ref())
+            .map(|toplevel| crate::focus::KeyboardFocusTarget::Surface(toplevel.wl_surface().clone()));
+        keyboard.set_focus(self, None, smithay::utils::SERIAL_COUNTER.next_serial());
+        keyboard.set_focus(self, target, smithay::utils::SERIAL_COUNTER.next_serial());
+    }
+
+    /// Run the workspace-change hook once the active workspace has settled.
+    /// Called once per event loop iteration.
+    pub fn run_workspace_hook(&mut self) {
+        let Some(workspace_num) = self.workspace_hook.poll(Instant::now()) else {
+            return;
+        };
+        let Some(ref command) = self.config.general.on_workspace_change else {
+            return;
+        };
+
+        let name = match self.workspace_manager {
+            Some(ref manager) => manager.workspace_name(workspace_num, &self.config.workspaces),
+            None => self.config.workspaces.name_for(workspace_num),
+        };
+        spawn_hook(workspace_change_command(command, workspace_num, &name));
+    }
+
     /// Launch the selected app from the launcher
     pub fn launch_selected_app(&mut self) -> Result<(), String> {
         if !self.launcher_active {
             return Err("Launcher is not active".to_string());
         }
 
-        let launcher = self.launcher.as_ref().ok_or("Launcher not initialized")?;
-        let app = launcher.selected_app().ok_or("No app selected")?;
+        let launcher = self.launcher.as_mut().ok_or("Launcher not initialized")?;
+        let app = launcher.selected_app().ok_or("No app selected")?.clone();
+        let workspace_target = launcher.workspace_target();
 
         info!("Launching app: {} ({})", app.name, app.exec);
 
+        if !app.is_installed() {
+            let message = format!("Failed to launch {}: '{}' not found", app.name, app.get_command());
+            self.show_error(message.clone());
+            return Err(message);
+        }
+
         // Get the command to execute
         let command = app.get_command();
 
@@ -355,13 +1073,24 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         match cmd.spawn() {
             Ok(child) => {
                 info!("Launched {} (PID: {})", app.name, child.id());
+                launcher.record_launch(&app);
+                if let Some(workspace) = workspace_target {
+                    info!("{} will open on workspace {}", app.name, workspace);
+                    self.pending_placements.push(PendingPlacement {
+                        pid: child.id(),
+                        app,
+                        workspace,
+                        launched_at: Instant::now(),
+                    });
+                }
                 // Close launcher after successful launch
                 self.launcher_active = false;
                 Ok(())
             }
             Err(e) => {
-                tracing::error!("Failed to launch {}: {}", app.name, e);
-                Err(format!("Failed to launch: {}", e))
+                let message = format!("Failed to launch {}: {}", app.name, e);
+                self.show_error(message.clone());
+                Err(message)
             }
         }
     }
@@ -374,14 +1103,29 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
             Ok(new_config) => {
                 self.config = new_config;
                 self.theme = self.config.get_theme();
+                self.bindings = self.config.keybindings.parse_all();
+                self.chords = self.config.keybindings.parse_chords();
+                self.chord_state.reset();
                 info!("Configuration reloaded successfully");
 
-                // Update border colors after config reload
+                // Re-apply window rules and border colors after config reload
+                for window_id in self.window_tree.find_windows() {
+                    self.apply_window_rules(window_id);
+                }
                 self.update_window_border_colors();
 
                 // Clear wallpaper cache to force reload on next render
                 self.wallpaper_cache.clear();
                 info!("Wallpaper cache cleared");
+
+                // Colors, borders and the wallpaper may all have changed
+                self.surface_damage.damage_all();
+
+                // Desktop entries (and icon sizes) may have changed as well
+                self.icon_cache.clear();
+                for window_id in self.window_tree.find_windows() {
+                    self.load_window_icons(window_id);
+                }
             }
             Err(e) => {
                 tracing::error!("Failed to reload config: {}", e);
@@ -389,6 +1133,275 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         }
     }
 
+    /// Update the cached title and app ID of a window from its toplevel state
+    pub fn update_window_metadata(&mut self, toplevel: &ToplevelSurface) {
+        use smithay::wayland::{compositor::with_states, shell::xdg::XdgToplevelSurfaceData};
+
+        let (title, app_id) = with_states(toplevel.wl_surface(), |states| {
+            let data = states
+                .data_map
+                .get::<XdgToplevelSurfaceData>()
+                .unwrap()
+                .lock()
+                .unwrap();
+            (data.title.clone(), data.app_id.clone())
+        });
+
+        if let Some(window_id) = self.window_tree.find_window_by_handle(toplevel) {
+            self.set_window_metadata(window_id, title, app_id);
+        }
+    }
+
+    /// Set a window's title and app ID and apply what depends on them
+    pub fn set_window_metadata(&mut self, window_id: NodeId, title: Option<String>, app_id: Option<String>) {
+        let Some(container) = self.window_tree.get_mut(window_id) else {
+            return;
+        };
+        let app_id_arrived = container.app_id.is_none() && app_id.is_some();
+        container.title = title;
+        container.app_id = app_id;
+
+        // Rules match on app_id/title, which clients usually set after creation
+        self.apply_window_rules(window_id);
+        self.place_launched_window(window_id);
+        self.load_window_icons(window_id);
+
+        // Windows floated when mapped didn't have an app_id to look up yet
+        if app_id_arrived && self.floating_manager.apply_saved_geometry(&mut self.window_tree, window_id) {
+            self.send_pending_configures();
+        }
+
+        self.update_window_border_colors();
+    }
+
+    /// Move a window launched with a workspace target (`:N` in the launcher)
+    /// to that workspace, matching it by client PID or app_id
+    pub fn place_launched_window(&mut self, window_id: NodeId) {
+        let Some(container) = self.window_tree.get(window_id) else {
+            return;
+        };
+        let pid = container
+            .window
+            .as_ref()
+            .and_then(|toplevel| toplevel.wl_surface().client())
+            .and_then(|client| client.get_credentials(&self.display_handle).ok())
+            .map(|credentials| credentials.pid as u32);
+        let app_id = container.app_id.clone();
+
+        let Some(workspace) = self.pending_placements.take_match(pid, app_id.as_deref(), Instant::now()) else {
+            return;
+        };
+        let Some(ref mut manager) = self.workspace_manager else {
+            return;
+        };
+        if manager.workspace_num_of(&self.window_tree, window_id) == Some(workspace) {
+            return;
+        }
+
+        match manager.move_window_to_workspace(&mut self.window_tree, window_id, workspace) {
+            Ok(()) => self.relayout_active_workspace(),
+            Err(e) => tracing::warn!("Failed to open launched window on workspace {}: {}", workspace, e),
+        }
+    }
+
+    /// Area available for tiling on an output: the output rectangle minus
+    /// the configured `[margins]` (gaps are applied inside this by the layout).
+    /// While a window is fullscreen the margins are reclaimed.
+    pub fn work_area(&self, output: codeverse_window::Rectangle) -> codeverse_window::Rectangle {
+        if self.fullscreen_window().is_some() {
+            return output;
+        }
+
+        let margins = &self.config.margins;
+
+        // Keep windows clear of the taskbar
+        let (taskbar_top, taskbar_bottom) = match self.taskbar_strip(output) {
+            Some(strip) if self.config.taskbar.edge == "top" => (strip.height, 0),
+            Some(strip) => (0, strip.height),
+            None => (0, 0),
+        };
+        let top = margins.top + taskbar_top;
+        let bottom = margins.bottom + taskbar_bottom;
+
+        codeverse_window::Rectangle::new(
+            output.x + margins.left as i32,
+            output.y + top as i32,
+            output.width.saturating_sub(margins.left + margins.right),
+            output.height.saturating_sub(top + bottom),
+        )
+    }
+
+    /// The last output went away. Windows, workspaces and layouts are left
+    /// untouched on the virtual output so they survive a reconnect; with
+    /// general.allow_no_output they are laid out against its size instead.
+    pub fn outputs_lost(&mut self) {
+        self.offscreen = true;
+        self.floating_manager.finish_operation();
+
+        if self.config.general.allow_no_output {
+            let (width, height) = self.config.general.virtual_output_size;
+            info!("No outputs, laying out against a {}x{} virtual output", width, height);
+            self.last_screen_geometry = Some(codeverse_window::Rectangle::new(0, 0, width, height));
+            self.relayout_active_workspace();
+            return;
+        }
+
+        info!(
+            "All outputs disconnected, keeping windows offscreen ({})",
+            if self.config.general.pause_without_outputs { "rendering paused" } else { "clients keep running" }
+        );
+    }
+
+    /// An output is available again: resume and lay windows out on it
+    pub fn output_restored(&mut self, geometry: codeverse_window::Rectangle) {
+        if !self.offscreen {
+            return;
+        }
+
+        info!("Output reconnected, restoring windows onto {:?}", geometry);
+        self.offscreen = false;
+        if !geometry.is_empty() {
+            self.last_screen_geometry = Some(geometry);
+        }
+        self.relayout_active_workspace();
+    }
+
+    /// Whether rendering is paused because no output is connected
+    pub fn rendering_paused(&self) -> bool {
+        self.offscreen && self.config.general.pause_without_outputs && !self.config.general.allow_no_output
+    }
+
+    /// Recalculate the layout of the active workspace and configure resized windows
+    pub fn relayout_active_workspace(&mut self) {
+        let screen_rect = self
+            .last_screen_geometry
+            .unwrap_or_else(|| codeverse_window::Rectangle::new(0, 0, 1920, 1080));
+        let work_area = self.work_area(screen_rect);
+
+        let gap_width = self.effective_gap_width();
+        if let Some(ref mut manager) = self.workspace_manager {
+            manager.layout_active_workspace(&mut self.window_tree, work_area, gap_width);
+        }
+        self.send_pending_configures();
+    }
+
+    /// Gap width for the active workspace (see `workspace_gap_width`)
+    pub fn effective_gap_width(&self) -> i32 {
+        match self.workspace_manager {
+            Some(ref manager) => self.workspace_gap_width(manager.active_workspace_num()),
+            None => self.config.general.gap_width as i32,
+        }
+    }
+
+    /// Gap width for a workspace: its per-workspace override, or the global
+    /// gap shrunk by tiled window count when adaptive gaps are enabled
+    pub fn workspace_gap_width(&self, workspace_num: usize) -> i32 {
+        if let Some(gap) = self.config.workspaces.gap_for(workspace_num) {
+            return gap as i32;
+        }
+
+        let tiled_count = self
+            .workspace_manager
+            .as_ref()
+            .map(|manager| {
+                manager
+                    .workspace_windows(&self.window_tree, workspace_num)
+                    .into_iter()
+                    .filter(|&id| self.window_tree.get(id).map(|c| !c.is_floating).unwrap_or(false))
+                    .count()
+            })
+            .unwrap_or(0);
+
+        self.config.general.effective_gap_width(tiled_count) as i32
+    }
+
+    /// Save the tiling arrangement of the active workspace as a named preset
+    pub fn save_preset(&mut self, name: &str) -> Result<(), String> {
+        let workspace_id = self
+            .workspace_manager
+            .as_ref()
+            .and_then(|m| m.active_workspace())
+            .ok_or("No active workspace")?;
+
+        let preset = self.window_tree.snapshot_preset(workspace_id);
+        self.layout_presets.insert(name, preset);
+        self.active_preset = Some(name.to_string());
+
+        self.layout_presets
+            .save()
+            .map_err(|e| format!("Failed to save presets: {}", e))?;
+
+        info!("Saved layout preset '{}'", name);
+        Ok(())
+    }
+
+    /// Apply a named preset to the active workspace
+    pub fn apply_preset(&mut self, name: &str) -> Result<(), String> {
+        let workspace_id = self
+            .workspace_manager
+            .as_ref()
+            .and_then(|m| m.active_workspace())
+            .ok_or("No active workspace")?;
+
+        let preset = self
+            .layout_presets
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("No preset named '{}'", name))?;
+
+        self.window_tree.apply_preset(workspace_id, &preset)?;
+        self.active_preset = Some(name.to_string());
+
+        self.update_window_border_colors();
+        self.relayout_active_workspace();
+
+        info!("Applied layout preset '{}'", name);
+        Ok(())
+    }
+
+    /// Apply the next saved preset (alphabetically) to the active workspace
+    pub fn cycle_preset(&mut self) -> Result<(), String> {
+        let next = self
+            .layout_presets
+            .next_after(self.active_preset.as_deref())
+            .map(|name| name.to_string())
+            .ok_or("No layout presets saved")?;
+
+        self.apply_preset(&next)
+    }
+
+    /// Save the focused floating window's geometry as where windows of its
+    /// app open from now on when floated
+    pub fn save_floating_default(&mut self) -> Result<(), String> {
+        let focused_id = self.window_tree.focused().ok_or("No focused window")?;
+        let app_id = self
+            .floating_manager
+            .save_default_geometry(&self.window_tree, focused_id)?;
+
+        self.floating_manager
+            .saved_geometries()
+            .save()
+            .map_err(|e| format!("Failed to save floating defaults: {}", e))?;
+
+        info!("Saved floating geometry for '{}'", app_id);
+        Ok(())
+    }
+
+    /// Layout of the container holding the focused window (what `change_layout` changes)
+    pub fn focused_layout(&self) -> Option<codeverse_window::LayoutMode> {
+        let focused_id = self.window_tree.focused()?;
+        let parent_id = self.window_tree.parent(focused_id)?;
+        self.window_tree.get(parent_id).map(|parent| parent.layout)
+    }
+
+    /// Change the focused container's layout by config name ("splith", "tabbed", ...)
+    pub fn set_layout(&mut self, name: &str) -> Result<(), String> {
+        let layout: codeverse_window::LayoutMode = name.parse()?;
+        self.window_tree.change_layout(layout)?;
+        self.relayout_active_workspace();
+        Ok(())
+    }
+
     /// Get the wallpaper path for the current workspace
     pub fn get_wallpaper_path(&self, workspace_index: Option<usize>) -> Option<&str> {
         // Check for per-workspace wallpaper first
@@ -437,7 +1450,7 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
             // Read geometry and check if configure is needed
             let configure_info = if let Some(container) = self.window_tree.get(window_id) {
                 let geom = container.geometry;
-                let new_size = (geom.width, geom.height);
+                let new_size = container.snapped_size((geom.width, geom.height));
 
                 // Skip if size is zero (not yet laid out)
                 if new_size.0 == 0 || new_size.1 == 0 {
@@ -470,6 +1483,73 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         }
     }
 
+    /// Fullscreen window on the active workspace, if any
+    pub fn fullscreen_window(&self) -> Option<NodeId> {
+        self.workspace_manager
+            .as_ref()?
+            .visible_windows(&self.window_tree)
+            .into_iter()
+            .find(|&id| {
+                self.window_tree
+                    .get(id)
+                    .is_some_and(|c| c.is_fullscreen && !c.is_floating)
+            })
+    }
+
+    /// Enter or leave fullscreen for a tiled window (xdg_toplevel.set_fullscreen)
+    pub fn set_fullscreen(&mut self, toplevel: &ToplevelSurface, fullscreen: bool) {
+        use smithay::reexports::wayland_protocols::xdg::shell::server::xdg_toplevel;
+
+        let Some(window_id) = self.window_tree.find_window_by_handle(toplevel) else {
+            return;
+        };
+        let Some(container) = self.window_tree.get_mut(window_id) else {
+            return;
+        };
+        if container.is_floating {
+            tracing::debug!("Ignoring fullscreen request from floating window {:?}", window_id);
+            return;
+        }
+
+        info!("Window {:?} {} fullscreen", window_id, if fullscreen { "entered" } else { "left" });
+        container.is_fullscreen = fullscreen;
+        toplevel.with_pending_state(|state| {
+            if fullscreen {
+                state.states.set(xdg_toplevel::State::Fullscreen);
+            } else {
+                state.states.unset(xdg_toplevel::State::Fullscreen);
+            }
+        });
+
+        self.relayout_active_workspace();
+        toplevel.send_pending_configure();
+    }
+
+    /// Floating windows on the active workspace, in stacking order (bottom to top)
+    pub fn active_floating_stack(&self) -> Vec<NodeId> {
+        self.workspace_manager
+            .as_ref()
+            .and_then(|manager| manager.active_workspace())
+            .map(|workspace_id| {
+                self.floating_manager
+                    .stack_for_workspace(&self.window_tree, workspace_id)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Tiled windows the pointer can land on: just the fullscreen window
+    /// while there is one, since it hides the rest (floating windows are
+    /// still drawn above it)
+    fn tiled_windows_under_pointer(&self) -> Vec<NodeId> {
+        if let Some(fullscreen_id) = self.fullscreen_window() {
+            return vec![fullscreen_id];
+        }
+        self.workspace_manager
+            .as_ref()
+            .map(|manager| manager.visible_windows(&self.window_tree))
+            .unwrap_or_default()
+    }
+
     /// Find the Wayland surface under a given point (for seat pointer focus).
     /// Returns the focus target and surface-local coordinates.
     /// Checks floating windows first (top of stack), then tiled windows.
@@ -478,7 +1558,7 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         let y = pos.y as i32;
 
         // Check floating windows first (rendered on top), reverse stacking order
-        for &window_id in self.floating_manager.get_stack().iter().rev() {
+        for window_id in self.active_floating_stack().into_iter().rev() {
             if let Some(container) = self.window_tree.get(window_id) {
                 if container.is_floating {
                     if let Some(ref toplevel) = container.window {
@@ -503,23 +1583,20 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         }
 
         // Check tiled windows
-        if let Some(ref manager) = self.workspace_manager {
-            let visible = manager.visible_windows(&self.window_tree);
-            for window_id in visible {
-                if let Some(container) = self.window_tree.get(window_id) {
-                    if !container.is_floating {
-                        let geom = container.geometry;
-                        if x >= geom.x && x < geom.x + geom.width as i32
-                            && y >= geom.y && y < geom.y + geom.height as i32
-                        {
-                            if let Some(ref toplevel) = container.window {
-                                let surface = toplevel.wl_surface().clone();
-                                let surface_local = Point::from((
-                                    pos.x - geom.x as f64,
-                                    pos.y - geom.y as f64,
-                                ));
-                                return Some((crate::focus::PointerFocusTarget::Surface(surface), surface_local));
-                            }
+        for window_id in self.tiled_windows_under_pointer() {
+            if let Some(container) = self.window_tree.get(window_id) {
+                if !container.is_floating {
+                    let geom = container.geometry;
+                    if x >= geom.x && x < geom.x + geom.width as i32
+                        && y >= geom.y && y < geom.y + geom.height as i32
+                    {
+                        if let Some(ref toplevel) = container.window {
+                            let surface = toplevel.wl_surface().clone();
+                            let surface_local = Point::from((
+                                pos.x - geom.x as f64,
+                                pos.y - geom.y as f64,
+                            ));
+                            return Some((crate::focus::PointerFocusTarget::Surface(surface), surface_local));
                         }
                     }
                 }
@@ -536,7 +1613,7 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         let y = pos.y as i32;
 
         // Check floating windows first (including title bar area)
-        for &window_id in self.floating_manager.get_stack().iter().rev() {
+        for window_id in self.active_floating_stack().into_iter().rev() {
             if let Some(container) = self.window_tree.get(window_id) {
                 if container.is_floating {
                     let geom = container.geometry;
@@ -552,17 +1629,14 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         }
 
         // Check tiled windows
-        if let Some(ref manager) = self.workspace_manager {
-            let visible = manager.visible_windows(&self.window_tree);
-            for window_id in visible {
-                if let Some(container) = self.window_tree.get(window_id) {
-                    if !container.is_floating {
-                        let geom = container.geometry;
-                        if x >= geom.x && x < geom.x + geom.width as i32
-                            && y >= geom.y && y < geom.y + geom.height as i32
-                        {
-                            return Some(window_id);
-                        }
+        for window_id in self.tiled_windows_under_pointer() {
+            if let Some(container) = self.window_tree.get(window_id) {
+                if !container.is_floating {
+                    let geom = container.geometry;
+                    if x >= geom.x && x < geom.x + geom.width as i32
+                        && y >= geom.y && y < geom.y + geom.height as i32
+                    {
+                        return Some(window_id);
                     }
                 }
             }
@@ -590,44 +1664,7 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         };
 
         for (cmd, stream) in pending {
-            let response = match cmd {
-                IpcCommand::Ping => IpcResponse::Pong,
-                IpcCommand::GetWorkspaces => {
-                    let (active, count) = self.workspace_manager.as_ref()
-                        .map(|m| (m.active_workspace_num(), 10))
-                        .unwrap_or((1, 10));
-                    IpcResponse::Workspaces { active, count }
-                }
-                IpcCommand::SwitchWorkspace { number } => {
-                    if let Some(ref mut manager) = self.workspace_manager {
-                        manager.switch_to_workspace(number);
-                    }
-                    IpcResponse::Ok
-                }
-                IpcCommand::GetFocusedWindow => {
-                    let title = self.window_tree.focused()
-                        .map(|_| "focused".to_string());
-                    IpcResponse::FocusedWindow { title }
-                }
-                IpcCommand::CloseWindow => {
-                    if let Some(focused_id) = self.window_tree.focused() {
-                        if let Some(container) = self.window_tree.get(focused_id) {
-                            if let Some(ref window) = container.window {
-                                window.send_close();
-                            }
-                        }
-                    }
-                    IpcResponse::Ok
-                }
-                IpcCommand::ReloadConfig => {
-                    self.reload_config();
-                    IpcResponse::Ok
-                }
-                IpcCommand::ToggleLauncher => {
-                    self.toggle_launcher();
-                    IpcResponse::Ok
-                }
-            };
+            let response = self.handle_ipc_command(cmd);
 
             if let Err(e) = IpcServer::respond(stream, &response) {
                 tracing::warn!("Failed to send IPC response: {}", e);
@@ -635,6 +1672,81 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         }
     }
 
+    /// Run a single IPC command and build its response
+    pub fn handle_ipc_command(&mut self, cmd: codeverse_ipc::IpcCommand) -> codeverse_ipc::IpcResponse {
+        use codeverse_ipc::{IpcCommand, IpcRect, IpcResponse};
+
+        match cmd {
+            IpcCommand::Ping => IpcResponse::Pong,
+            IpcCommand::GetWorkspaces => {
+                let (active, count) = self.workspace_manager.as_ref()
+                    .map(|m| (m.active_workspace_num(), 10))
+                    .unwrap_or((1, 10));
+                IpcResponse::Workspaces { active, count }
+            }
+            IpcCommand::SwitchWorkspace { number } => {
+                self.switch_workspace(number);
+                IpcResponse::Ok
+            }
+            IpcCommand::MoveWorkspace { from, to } => match self.move_workspace(from, to) {
+                Ok(()) => IpcResponse::Ok,
+                Err(message) => IpcResponse::Error { message },
+            },
+            IpcCommand::GetFocusedWindow => {
+                let title = self.window_tree.focused()
+                    .map(|_| "focused".to_string());
+                IpcResponse::FocusedWindow { title }
+            }
+            IpcCommand::CloseWindow => {
+                self.close_focused_window();
+                IpcResponse::Ok
+            }
+            IpcCommand::ReloadConfig => {
+                self.reload_config();
+                IpcResponse::Ok
+            }
+            IpcCommand::ToggleLauncher => {
+                self.toggle_launcher();
+                IpcResponse::Ok
+            }
+            IpcCommand::SavePreset { name } => match self.save_preset(&name) {
+                Ok(()) => IpcResponse::Ok,
+                Err(message) => IpcResponse::Error { message },
+            },
+            IpcCommand::ApplyPreset { name } => match self.apply_preset(&name) {
+                Ok(()) => IpcResponse::Ok,
+                Err(message) => IpcResponse::Error { message },
+            },
+            IpcCommand::CyclePreset => match self.cycle_preset() {
+                Ok(()) => IpcResponse::Ok,
+                Err(message) => IpcResponse::Error { message },
+            },
+            IpcCommand::GetLayout => match self.focused_layout() {
+                Some(layout) => IpcResponse::Layout { layout: layout.name().to_string() },
+                None => IpcResponse::Error { message: "No focused container".to_string() },
+            },
+            IpcCommand::SetLayout { layout } => match self.set_layout(&layout) {
+                Ok(()) => IpcResponse::Ok,
+                Err(message) => IpcResponse::Error { message },
+            },
+            IpcCommand::GetWorkArea => match self.last_screen_geometry {
+                Some(screen) => {
+                    let to_ipc = |rect: codeverse_window::Rectangle| IpcRect {
+                        x: rect.x,
+                        y: rect.y,
+                        width: rect.width,
+                        height: rect.height,
+                    };
+                    IpcResponse::WorkArea {
+                        screen: to_ipc(screen),
+                        work_area: to_ipc(self.work_area(screen)),
+                    }
+                }
+                None => IpcResponse::Error { message: "No output".to_string() },
+            },
+        }
+    }
+
     /// Update border colors for all windows based on focus state
     pub fn update_window_border_colors(&mut self) {
         let focused_color = self.theme.focused_border();
@@ -645,6 +1757,14 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
         let window_ids: Vec<NodeId> = self.window_tree.find_windows();
 
         for window_id in window_ids {
+            // Rules beat the workspace's override, which beats the global width
+            let default_border_width = self
+                .workspace_manager
+                .as_ref()
+                .and_then(|manager| manager.workspace_num_of(&self.window_tree, window_id))
+                .and_then(|num| self.config.workspaces.border_width_for(num))
+                .unwrap_or(self.config.general.border_width);
+
             if let Some(container) = self.window_tree.get_mut(window_id) {
                 let is_focused = Some(window_id) == focused_id;
                 container.border_color = if is_focused {
@@ -652,7 +1772,35 @@ impl<BackendData> CodeVerseCompositor<BackendData> {
                 } else {
                     unfocused_color
                 };
-                container.border_width = self.config.general.border_width;
+                container.border_width = container
+                    .border_width_override
+                    .unwrap_or(default_border_width);
+            }
+        }
+    }
+
+    /// Re-evaluate window rules for a window and store its overrides
+    pub fn apply_window_rules(&mut self, window_id: NodeId) {
+        let Some(container) = self.window_tree.get_mut(window_id) else {
+            return;
+        };
+        container.border_width_override = self
+            .config
+            .rule_border_width(container.app_id.as_deref(), container.title.as_deref());
+        container.opacity = self
+            .config
+            .rule_opacity(container.app_id.as_deref(), container.title.as_deref());
+        container.resize_increment = self
+            .config
+            .rule_resize_increment(container.app_id.as_deref(), container.title.as_deref());
+
+        // A rule's group color goes to the group holding the window
+        let group_background = self
+            .config
+            .rule_group_background(container.app_id.as_deref(), container.title.as_deref());
+        if let Some(color) = group_background {
+            if let Some(parent) = container.parent.and_then(|parent_id| self.window_tree.get_mut(parent_id)) {
+                parent.background_color = Some(color);
             }
         }
     }
@@ -681,3 +1829,505 @@ impl ClientData for ClientState {
         info!("Client disconnected");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{add_window, create_test_compositor};
+    use codeverse_config::WindowRule;
+    use codeverse_window::{Container, ContainerType};
+
+    #[test]
+    fn test_workspace_gap_and_border_overrides() {
+        use codeverse_config::WorkspaceOverrides;
+
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        compositor.config.general.gap_width = 10;
+        compositor.config.general.border_width = 2;
+        compositor.config.workspaces.per_workspace.push(WorkspaceOverrides {
+            workspace: 3,
+            gap: Some(0),
+            border_width: Some(0),
+        });
+
+        let regular = add_window(&mut compositor, 1);
+        let focus = add_window(&mut compositor, 3);
+
+        // Workspace 3 uses its own gap; the others keep the global one
+        assert_eq!(compositor.effective_gap_width(), 10);
+        compositor.switch_workspace(3);
+        assert_eq!(compositor.effective_gap_width(), 0);
+        assert_eq!(compositor.workspace_gap_width(1), 10);
+
+        compositor.update_window_border_colors();
+        assert_eq!(compositor.window_tree.get(regular).unwrap().border_width, 2);
+        assert_eq!(compositor.window_tree.get(focus).unwrap().border_width, 0);
+    }
+
+    #[test]
+    fn test_window_rule_opacity() {
+        let mut compositor = create_test_compositor();
+        compositor.config.window_rules.push(WindowRule {
+            app_id: Some("foot".to_string()),
+            opacity: Some(0.95),
+            ..WindowRule::default()
+        });
+
+        let mut terminal = Container::new(NodeId::default(), ContainerType::Window);
+        terminal.app_id = Some("foot".to_string());
+        let terminal_id = compositor.window_tree.insert(terminal);
+        let browser_id = compositor
+            .window_tree
+            .insert(Container::new(NodeId::default(), ContainerType::Window));
+
+        compositor.apply_window_rules(terminal_id);
+        compositor.apply_window_rules(browser_id);
+
+        // The rule's opacity holds whether the window is focused or not
+        for focused in [terminal_id, browser_id] {
+            compositor.window_tree.set_focused(Some(focused));
+            compositor.update_window_border_colors();
+            assert_eq!(compositor.window_opacity(terminal_id), 0.95);
+            assert_eq!(compositor.window_opacity(browser_id), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_window_rule_border_width() {
+        let mut compositor = create_test_compositor();
+        compositor.config.general.border_width = 3;
+        compositor.config.window_rules.push(WindowRule {
+            app_id: Some("mpv".to_string()),
+            border_width: Some(0),
+            ..WindowRule::default()
+        });
+
+        let mut video = Container::new(NodeId::default(), ContainerType::Window);
+        video.app_id = Some("mpv".to_string());
+        let video_id = compositor.window_tree.insert(video);
+
+        let mut editor = Container::new(NodeId::default(), ContainerType::Window);
+        editor.app_id = Some("editor".to_string());
+        let editor_id = compositor.window_tree.insert(editor);
+
+        compositor.apply_window_rules(video_id);
+        compositor.apply_window_rules(editor_id);
+        compositor.update_window_border_colors();
+
+        assert_eq!(compositor.window_tree.get(video_id).unwrap().border_width, 0);
+        assert_eq!(compositor.window_tree.get(editor_id).unwrap().border_width, 3);
+    }
+
+    #[test]
+    fn test_focus_previous_across_workspaces() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+
+        let a = add_window(&mut compositor, 1);
+        let b = add_window(&mut compositor, 2);
+
+        // Focus A on workspace 1, then B on workspace 2
+        compositor.window_tree.set_focused(Some(a));
+        compositor.workspace_manager.as_mut().unwrap().switch_to_workspace(2);
+        compositor.window_tree.set_focused(Some(b));
+
+        compositor.focus_previous_window();
+        assert_eq!(compositor.window_tree.focused(), Some(a));
+        assert_eq!(compositor.workspace_manager.as_ref().unwrap().active_workspace_num(), 1);
+
+        // Invoking it again toggles back to B
+        compositor.focus_previous_window();
+        assert_eq!(compositor.window_tree.focused(), Some(b));
+    }
+
+    #[test]
+    fn test_failed_launch_shows_error_toast() {
+        use codeverse_launcher::{App, AppDiscovery, FrecencyStore};
+
+        let mut compositor = create_test_compositor();
+        let app = App {
+            name: "Missing Editor".to_string(),
+            exec: "codeverse-no-such-editor %F".to_string(),
+            description: None,
+            desktop_file: std::path::PathBuf::from("/test/missing-editor.desktop"),
+            terminal: false,
+            icon: None,
+            wm_class: None,
+        };
+        compositor.launcher = Some(LauncherState::with_discovery(
+            AppDiscovery::from_apps(vec![app]),
+            FrecencyStore::default(),
+        ));
+        compositor.launcher_active = true;
+
+        assert!(compositor.launch_selected_app().is_err());
+        let messages: Vec<&str> = compositor.error_toasts.visible().map(|toast| toast.message.as_str()).collect();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("Missing Editor"));
+        assert_eq!(compositor.error_toast_panels(codeverse_window::Rectangle::new(0, 0, 1920, 1080)).len(), 1);
+
+        // Toasts can be turned off; the launch still fails
+        compositor.config.general.error_toasts = false;
+        compositor.error_toasts = ErrorToasts::default();
+        assert!(compositor.launch_selected_app().is_err());
+        assert_eq!(compositor.error_toasts.visible().count(), 0);
+    }
+
+    #[test]
+    fn test_close_focuses_modal_dialog() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+
+        let editor = add_window(&mut compositor, 1);
+        let dialog = add_window(&mut compositor, 1);
+        compositor
+            .floating_manager
+            .set_transient(&mut compositor.window_tree, dialog, editor)
+            .unwrap();
+
+        // Off by default: the close goes to the editor itself
+        compositor.window_tree.set_focused(Some(editor));
+        compositor.close_focused_window();
+        assert_eq!(compositor.window_tree.focused(), Some(editor));
+
+        compositor.config.general.close_respects_modals = true;
+        compositor.close_focused_window();
+        assert_eq!(compositor.window_tree.focused(), Some(dialog));
+        assert_eq!(compositor.floating_manager.get_stack().last(), Some(&dialog));
+
+        // Closing the dialog itself isn't redirected
+        compositor.close_focused_window();
+        assert_eq!(compositor.window_tree.focused(), Some(dialog));
+    }
+
+    #[test]
+    fn test_session_activation_refocuses_focused_window() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        let workspace_id = compositor.workspace_manager.as_ref().unwrap().get_workspace(1).unwrap();
+
+        add_window(&mut compositor, 1);
+        let b = add_window(&mut compositor, 1);
+
+        compositor.window_tree.set_focused(Some(b));
+        assert_eq!(compositor.keyboard_focus_window(), Some(b));
+
+        // Coming back from another VT keeps focus where it was
+        compositor.session_activated();
+        assert_eq!(compositor.window_tree.focused(), Some(b));
+        assert_eq!(compositor.keyboard_focus_window(), Some(b));
+
+        // A focused container isn't a keyboard focus target
+        compositor.window_tree.set_focused(Some(workspace_id));
+        assert_eq!(compositor.keyboard_focus_window(), None);
+    }
+
+    #[test]
+    fn test_work_area_margins() {
+        let mut compositor = create_test_compositor();
+        let output = codeverse_window::Rectangle::new(0, 0, 1920, 1080);
+
+        // No margins by default
+        assert_eq!(compositor.work_area(output), output);
+
+        compositor.config.margins.top = 40;
+        assert_eq!(compositor.work_area(output), codeverse_window::Rectangle::new(0, 40, 1920, 1040));
+
+        compositor.config.margins.bottom = 20;
+        assert_eq!(compositor.work_area(output), codeverse_window::Rectangle::new(0, 40, 1920, 1020));
+
+        compositor.config.margins.left = 10;
+        assert_eq!(compositor.work_area(output), codeverse_window::Rectangle::new(10, 40, 1910, 1020));
+
+        compositor.config.margins.right = 30;
+        assert_eq!(compositor.work_area(output), codeverse_window::Rectangle::new(10, 40, 1880, 1020));
+    }
+
+    #[test]
+    fn test_taskbar_buttons() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        let output = codeverse_window::Rectangle::new(0, 0, 1920, 1080);
+
+        let a = add_window(&mut compositor, 3);
+        let b = add_window(&mut compositor, 1);
+
+        // Disabled by default
+        assert!(compositor.taskbar_buttons(output).is_empty());
+        assert_eq!(compositor.work_area(output), output);
+
+        compositor.config.taskbar.enabled = true;
+        assert_eq!(compositor.work_area(output), codeverse_window::Rectangle::new(0, 0, 1920, 1048));
+
+        // One button per window, in workspace order
+        let buttons = compositor.taskbar_buttons(output);
+        assert_eq!(buttons.iter().map(|&(id, _)| id).collect::<Vec<_>>(), vec![b, a]);
+        assert!(buttons.iter().all(|(_, rect)| rect.y >= 1048));
+        assert_eq!(compositor.taskbar_panels(output).len(), 3);
+
+        // Activating a button's window switches to its workspace
+        compositor.activate_window(buttons[1].0);
+        assert_eq!(compositor.window_tree.focused(), Some(a));
+        assert_eq!(compositor.workspace_manager.as_ref().unwrap().active_workspace_num(), 3);
+
+        compositor.config.taskbar.edge = "top".to_string();
+        assert_eq!(compositor.work_area(output), codeverse_window::Rectangle::new(0, 32, 1920, 1048));
+    }
+
+    #[test]
+    fn test_taskbar_icons() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        compositor.config.taskbar.enabled = true;
+        let output = codeverse_window::Rectangle::new(0, 0, 1920, 1080);
+
+        let a = add_window(&mut compositor, 1);
+        add_window(&mut compositor, 1);
+        compositor.window_tree.get_mut(a).unwrap().app_id = Some("firefox".to_string());
+
+        // Windows without an app_id get no icon; the icon sits inside its button
+        assert_eq!(compositor.taskbar_icon_size(), 16);
+        assert_eq!(
+            compositor.taskbar_icons(output),
+            vec![("firefox".to_string(), codeverse_window::Rectangle::new(8, 1056, 16, 16))]
+        );
+
+        // Too short for an icon
+        compositor.config.taskbar.height = 16;
+        assert!(compositor.taskbar_icons(output).is_empty());
+    }
+
+    #[test]
+    fn test_pointer_hits_fullscreen_window() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        let output = codeverse_window::Rectangle::new(0, 0, 1920, 1080);
+
+        let tiled = add_window(&mut compositor, 1);
+        let fullscreen = add_window(&mut compositor, 1);
+        let dialog = add_window(&mut compositor, 1);
+        compositor.window_tree.get_mut(tiled).unwrap().geometry = codeverse_window::Rectangle::new(0, 0, 960, 1080);
+        let container = compositor.window_tree.get_mut(fullscreen).unwrap();
+        container.geometry = output;
+        container.is_fullscreen = true;
+        compositor.floating_manager.toggle_floating(&mut compositor.window_tree, dialog, output).unwrap();
+        compositor.window_tree.get_mut(dialog).unwrap().geometry = codeverse_window::Rectangle::new(100, 100, 400, 300);
+
+        // The tiled window hidden behind the fullscreen one can't be hit
+        assert_eq!(compositor.window_under(Point::from((700.0, 700.0))), Some(fullscreen));
+
+        // Floating windows are drawn above it
+        assert_eq!(compositor.window_under(Point::from((200.0, 200.0))), Some(dialog));
+    }
+
+    #[test]
+    fn test_saved_floating_geometry_applied_when_app_id_arrives() {
+        use codeverse_config::FloatingGeometry;
+
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        let output = codeverse_window::Rectangle::new(0, 0, 1920, 1080);
+        let mut saved = FloatingDefaults::default();
+        saved.insert("pavucontrol", FloatingGeometry { x: 1400, y: 60, width: 480, height: 600 });
+        compositor.floating_manager.set_saved_geometries(saved);
+
+        // Floated when mapped, before the client set its app_id
+        let window = add_window(&mut compositor, 1);
+        compositor
+            .floating_manager
+            .float_new_window(&mut compositor.window_tree, window, output, true)
+            .unwrap();
+        assert_eq!(compositor.window_tree.get(window).unwrap().geometry, codeverse_window::Rectangle::new(560, 240, 800, 600));
+
+        compositor.set_window_metadata(window, Some("Volume Control".to_string()), Some("pavucontrol".to_string()));
+        assert_eq!(compositor.window_tree.get(window).unwrap().geometry, codeverse_window::Rectangle::new(1400, 60, 480, 600));
+
+        // Later title updates leave a moved window where it is
+        compositor.window_tree.get_mut(window).unwrap().geometry.x = 0;
+        compositor.set_window_metadata(window, Some("Volume".to_string()), Some("pavucontrol".to_string()));
+        assert_eq!(compositor.window_tree.get(window).unwrap().geometry.x, 0);
+    }
+
+    #[test]
+    fn test_slide_frame_includes_outgoing_floating_windows() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        let output = codeverse_window::Rectangle::new(0, 0, 1920, 1080);
+        compositor.last_screen_geometry = Some(output);
+
+        let tiled = add_window(&mut compositor, 1);
+        let floating = add_window(&mut compositor, 1);
+        compositor
+            .floating_manager
+            .float_new_window(&mut compositor.window_tree, floating, output, true)
+            .unwrap();
+        let incoming = add_window(&mut compositor, 2);
+
+        let now = Instant::now();
+        compositor.workspace_manager.as_mut().unwrap().switch_to_workspace(2);
+        compositor.workspace_slide = Some(WorkspaceSlide::new(1, 2, now, Duration::from_millis(200)));
+
+        // The outgoing workspace's floating windows slide out with it
+        let frame = compositor.workspace_slide_frame(now).unwrap();
+        assert_eq!(frame.outgoing, vec![tiled]);
+        assert_eq!(frame.outgoing_floating, vec![floating]);
+        assert!(!frame.outgoing.contains(&incoming));
+    }
+
+    #[test]
+    fn test_work_area_reclaimed_while_fullscreen() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        let output = codeverse_window::Rectangle::new(0, 0, 1920, 1080);
+        compositor.config.margins.top = 40;
+        compositor.config.margins.left = 10;
+
+        let window = add_window(&mut compositor, 1);
+        assert_eq!(compositor.work_area(output), codeverse_window::Rectangle::new(10, 40, 1910, 1040));
+
+        compositor.window_tree.get_mut(window).unwrap().is_fullscreen = true;
+        assert_eq!(compositor.fullscreen_window(), Some(window));
+        assert_eq!(compositor.work_area(output), output);
+
+        // A fullscreen window on another workspace doesn't count
+        compositor.switch_workspace(2);
+        assert_eq!(compositor.fullscreen_window(), None);
+        assert_eq!(compositor.work_area(output), codeverse_window::Rectangle::new(10, 40, 1910, 1040));
+    }
+
+    #[test]
+    fn test_ipc_get_and_set_layout() {
+        use codeverse_ipc::{IpcCommand, IpcResponse};
+
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        let workspace_id = compositor.workspace_manager.as_ref().unwrap().get_workspace(1).unwrap();
+
+        // Nothing focused yet
+        assert!(matches!(compositor.handle_ipc_command(IpcCommand::GetLayout), IpcResponse::Error { .. }));
+
+        let window = add_window(&mut compositor, 1);
+        compositor.window_tree.set_focused(Some(window));
+
+        for name in ["splitv", "tabbed", "stacking", "splith"] {
+            let response = compositor.handle_ipc_command(IpcCommand::SetLayout { layout: name.to_string() });
+            assert!(matches!(response, IpcResponse::Ok));
+            assert_eq!(compositor.window_tree.get(workspace_id).unwrap().layout.name(), name);
+
+            match compositor.handle_ipc_command(IpcCommand::GetLayout) {
+                IpcResponse::Layout { layout } => assert_eq!(layout, name),
+                other => panic!("unexpected response: {:?}", other),
+            }
+        }
+
+        // Unknown names are rejected and leave the layout alone
+        let response = compositor.handle_ipc_command(IpcCommand::SetLayout { layout: "spiral".to_string() });
+        assert!(matches!(response, IpcResponse::Error { .. }));
+        assert_eq!(compositor.focused_layout(), Some(codeverse_window::LayoutMode::SplitH));
+    }
+
+    #[test]
+    fn test_ipc_get_work_area() {
+        use codeverse_ipc::{IpcCommand, IpcRect, IpcResponse};
+
+        let mut compositor = create_test_compositor();
+
+        // No output yet
+        assert!(matches!(compositor.handle_ipc_command(IpcCommand::GetWorkArea), IpcResponse::Error { .. }));
+
+        compositor.last_screen_geometry = Some(codeverse_window::Rectangle::new(0, 0, 2560, 1440));
+        compositor.config.margins.top = 32;
+        compositor.config.margins.left = 48;
+
+        match compositor.handle_ipc_command(IpcCommand::GetWorkArea) {
+            IpcResponse::WorkArea { screen, work_area } => {
+                assert_eq!(screen, IpcRect { x: 0, y: 0, width: 2560, height: 1440 });
+                assert_eq!(work_area, IpcRect { x: 48, y: 32, width: 2512, height: 1408 });
+            }
+            other => panic!("unexpected response: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_windows_survive_output_reconnect() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        let screen = codeverse_window::Rectangle::new(0, 0, 1920, 1080);
+        compositor.last_screen_geometry = Some(screen);
+
+        let manager = compositor.workspace_manager.as_ref().unwrap();
+        let workspace_1 = manager.get_workspace(1).unwrap();
+        let workspace_2 = manager.get_workspace(2).unwrap();
+
+        let windows: Vec<NodeId> = [1, 1, 2]
+            .into_iter()
+            .map(|workspace_num| add_window(&mut compositor, workspace_num))
+            .collect();
+        compositor.relayout_active_workspace();
+        let geometries: Vec<_> = windows.iter().map(|&id| compositor.window_tree.get(id).unwrap().geometry).collect();
+
+        compositor.outputs_lost();
+        assert!(compositor.offscreen);
+        assert!(compositor.rendering_paused());
+
+        compositor.output_restored(screen);
+        assert!(!compositor.offscreen);
+        assert!(!compositor.rendering_paused());
+
+        // Same windows, same workspaces, same layout
+        for (&window, geometry) in windows.iter().zip(geometries) {
+            let container = compositor.window_tree.get(window).unwrap();
+            assert_eq!(container.geometry, geometry);
+        }
+        assert_eq!(compositor.window_tree.find_workspace(windows[0]), Some(workspace_1));
+        assert_eq!(compositor.window_tree.find_workspace(windows[2]), Some(workspace_2));
+    }
+
+    #[test]
+    fn test_layout_against_virtual_output() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        compositor.config.general.allow_no_output = true;
+        compositor.config.general.virtual_output_size = (1280, 720);
+        compositor.config.general.gap_width = 0;
+
+        let window = add_window(&mut compositor, 1);
+
+        // No real output ever showed up
+        assert_eq!(compositor.last_screen_geometry, None);
+        compositor.outputs_lost();
+
+        assert!(compositor.offscreen);
+        assert!(!compositor.rendering_paused());
+        assert_eq!(
+            compositor.window_tree.get(window).unwrap().geometry,
+            codeverse_window::Rectangle::new(0, 0, 1280, 720)
+        );
+    }
+
+    #[test]
+    fn test_focus_follows_mouse_on_workspace_switch() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        compositor.config.general.focus_follows_mouse = true;
+        compositor.last_screen_geometry = Some(codeverse_window::Rectangle::new(0, 0, 1920, 1080));
+
+        let a = add_window(&mut compositor, 1);
+        add_window(&mut compositor, 2);
+        let right = add_window(&mut compositor, 2);
+
+        compositor.window_tree.set_focused(Some(a));
+        compositor.pointer_location = (1500.0, 500.0).into();
+
+        // The right-hand window of workspace 2 ends up under the cursor
+        compositor.switch_workspace(2);
+        assert_eq!(compositor.window_tree.focused(), Some(right));
+
+        // Without focus-follows-mouse the switch leaves focus alone
+        compositor.config.general.focus_follows_mouse = false;
+        compositor.window_tree.set_focused(Some(a));
+        compositor.switch_workspace(2);
+        assert_eq!(compositor.window_tree.focused(), Some(a));
+    }
+}
@@ -1,13 +1,62 @@
 use crate::compositor::CodeVerseCompositor;
+use crate::render::CommitDamage;
 use smithay::{
-    backend::renderer::utils::on_commit_buffer_handler,
+    backend::renderer::{buffer_dimensions, utils::on_commit_buffer_handler},
+    reexports::wayland_server::protocol::wl_surface::WlSurface,
+    utils::Transform,
     wayland::{
         buffer::BufferHandler,
-        compositor::{get_parent, is_sync_subsurface, CompositorHandler, CompositorState},
+        compositor::{
+            get_parent, is_sync_subsurface, with_states, BufferAssignment, CompositorHandler,
+            CompositorState, Damage, SurfaceAttributes,
+        },
     },
 };
 use tracing::debug;
 
+/// Damage, buffer scale and buffer transform of a commit.
+/// Must run before `on_commit_buffer_handler`, which consumes the damage.
+fn commit_damage(surface: &WlSurface) -> CommitDamage {
+    with_states(surface, |states| {
+        let mut guard = states.cached_state.get::<SurfaceAttributes>();
+        let attributes = guard.current();
+
+        let new_buffer = match attributes.buffer {
+            Some(BufferAssignment::NewBuffer(ref buffer)) => {
+                buffer_dimensions(buffer).map(|size| (size.w as u32, size.h as u32))
+            }
+            _ => None,
+        };
+
+        let mut surface_rects = Vec::new();
+        let mut buffer_rects = Vec::new();
+        for damage in &attributes.damage {
+            match damage {
+                Damage::Surface(rect) => surface_rects.push(codeverse_window::Rectangle::from(*rect)),
+                Damage::Buffer(rect) => buffer_rects.push(codeverse_window::Rectangle::from(*rect)),
+            }
+        }
+
+        CommitDamage {
+            surface_rects,
+            buffer_rects,
+            buffer_scale: attributes.buffer_scale,
+            buffer_transform: Transform::from(attributes.buffer_transform),
+            new_buffer,
+        }
+    })
+}
+
+/// Whether a commit attaches a new buffer
+fn commits_buffer(surface: &WlSurface) -> bool {
+    with_states(surface, |states| {
+        matches!(
+            states.cached_state.get::<SurfaceAttributes>().current().buffer,
+            Some(BufferAssignment::NewBuffer(_))
+        )
+    })
+}
+
 impl<BackendData: 'static> CompositorHandler for CodeVerseCompositor<BackendData> {
     fn compositor_state(&mut self) -> &mut CompositorState {
         &mut self.compositor_state
@@ -20,7 +69,21 @@ impl<BackendData: 'static> CompositorHandler for CodeVerseCompositor<BackendData
         &client.get_data::<crate::compositor::ClientState>().unwrap().compositor_state
     }
 
-    fn commit(&mut self, surface: &smithay::reexports::wayland_server::protocol::wl_surface::WlSurface) {
+    fn commit(&mut self, surface: &WlSurface) {
+        // Record what the window redrew for damage-tracked rendering;
+        // popups, subsurfaces and layer surfaces aren't tracked per window
+        if let Some(window_id) = self.window_tree.find_window_by_surface(surface) {
+            self.surface_damage.record(window_id, commit_damage(surface));
+
+            if commits_buffer(surface) {
+                if let Some(container) = self.window_tree.get_mut(window_id) {
+                    container.has_buffer = true;
+                }
+            }
+        } else {
+            self.surface_damage.damage_all();
+        }
+
         on_commit_buffer_handler::<Self>(surface);
 
         // Handle subsurface synchronization
@@ -56,9 +119,10 @@ impl<BackendData: 'static> CompositorHandler for CodeVerseCompositor<BackendData
                         .unwrap_or(false);
 
                     if needs_layout {
+                        let work_area = self.work_area(screen_rect);
+                        let gap_width = self.effective_gap_width();
                         if let Some(ref mut manager) = self.workspace_manager {
-                            let gap_width = self.config.general.gap_width as i32;
-                            manager.layout_active_workspace(&mut self.window_tree, screen_rect, gap_width);
+                            manager.layout_active_workspace(&mut self.window_tree, work_area, gap_width);
                         }
                         self.send_pending_configures();
                     }
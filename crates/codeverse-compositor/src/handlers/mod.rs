@@ -5,6 +5,7 @@ pub mod fractional_scale;
 pub mod layer_shell;
 pub mod output;
 pub mod selection;
+pub mod shortcuts_inhibit;
 pub mod shm;
 pub mod viewporter;
 pub mod xdg_decoration;
@@ -0,0 +1,33 @@
+use crate::compositor::CodeVerseCompositor;
+use smithay::{
+    delegate_keyboard_shortcuts_inhibit,
+    wayland::keyboard_shortcuts_inhibit::{
+        KeyboardShortcutsInhibitHandler, KeyboardShortcutsInhibitState, KeyboardShortcutsInhibitor,
+    },
+};
+use tracing::info;
+
+impl<BackendData: 'static> KeyboardShortcutsInhibitHandler for CodeVerseCompositor<BackendData> {
+    fn keyboard_shortcuts_inhibit_state(&mut self) -> &mut KeyboardShortcutsInhibitState {
+        &mut self.keyboard_shortcuts_inhibit_state
+    }
+
+    fn new_inhibitor(&mut self, inhibitor: KeyboardShortcutsInhibitor) {
+        // Grant every request for a known window; the inhibitor only takes
+        // effect while that window is focused (see handle_keyboard_shortcut)
+        if let Some(window_id) = self.window_tree.find_window_by_surface(inhibitor.wl_surface()) {
+            info!("Keyboard shortcuts inhibited for window {:?}", window_id);
+            inhibitor.activate();
+            self.shortcuts_inhibited_windows.insert(window_id);
+        }
+    }
+
+    fn inhibitor_destroyed(&mut self, inhibitor: KeyboardShortcutsInhibitor) {
+        if let Some(window_id) = self.window_tree.find_window_by_surface(inhibitor.wl_surface()) {
+            info!("Keyboard shortcuts no longer inhibited for window {:?}", window_id);
+            self.shortcuts_inhibited_windows.remove(&window_id);
+        }
+    }
+}
+
+delegate_keyboard_shortcuts_inhibit!(@<BackendData: 'static> CodeVerseCompositor<BackendData>);
@@ -1,6 +1,6 @@
 use crate::compositor::CodeVerseCompositor;
 use smithay::{
-    reexports::wayland_server::protocol::wl_seat::WlSeat,
+    reexports::wayland_server::protocol::{wl_output::WlOutput, wl_seat::WlSeat},
     utils::Serial,
     wayland::shell::xdg::{
         PopupSurface, PositionerState, ToplevelSurface, XdgShellHandler, XdgShellState,
@@ -49,6 +49,27 @@ impl<BackendData: 'static> XdgShellHandler for CodeVerseCompositor<BackendData>
         surface.send_configure().ok();
     }
 
+    fn title_changed(&mut self, surface: ToplevelSurface) {
+        self.update_window_metadata(&surface);
+    }
+
+    fn app_id_changed(&mut self, surface: ToplevelSurface) {
+        self.update_window_metadata(&surface);
+    }
+
+    fn parent_changed(&mut self, surface: ToplevelSurface) {
+        self.handle_parent_changed(&surface);
+    }
+
+    fn fullscreen_request(&mut self, surface: ToplevelSurface, _output: Option<WlOutput>) {
+        // There is a single layout area, so the requested output doesn't matter
+        self.set_fullscreen(&surface, true);
+    }
+
+    fn unfullscreen_request(&mut self, surface: ToplevelSurface) {
+        self.set_fullscreen(&surface, false);
+    }
+
     fn toplevel_destroyed(&mut self, surface: ToplevelSurface) {
         self.handle_toplevel_closed(&surface);
     }
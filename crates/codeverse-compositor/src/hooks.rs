@@ -0,0 +1,114 @@
+use std::process::{Child, Command};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// How long the active workspace must stay put before the hook runs,
+/// so rapid switching only reports the workspace the user settled on
+pub const WORKSPACE_HOOK_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Build the command for `general.on_workspace_change`.
+///
+/// The command runs through `sh -c` with the workspace number and name as
+/// `$1` and `$2`; they are also exported as `CODEVERSE_WORKSPACE` and
+/// `CODEVERSE_WORKSPACE_NAME`.
+pub fn workspace_change_command(command: &str, number: usize, name: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(command)
+        .arg("codeverse-workspace-hook")
+        .arg(number.to_string())
+        .arg(name)
+        .env("CODEVERSE_WORKSPACE", number.to_string())
+        .env("CODEVERSE_WORKSPACE_NAME", name);
+    cmd
+}
+
+/// Spawn a hook command without waiting for it
+pub fn spawn_hook(mut cmd: Command) {
+    match cmd.spawn() {
+        Ok(child) => {
+            info!("Started hook (PID: {})", child.id());
+            reap_in_background(child, "Hook");
+        }
+        Err(e) => warn!("Failed to run hook: {}", e),
+    }
+}
+
+/// Wait for a detached child on its own thread so it doesn't linger as a
+/// zombie once it exits; `what` names it in the log if it fails
+pub fn reap_in_background(mut child: Child, what: &'static str) {
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => warn!("{} (PID: {}) exited with {}", what, child.id(), status),
+        Ok(_) => {}
+        Err(e) => warn!("Failed to wait for {} (PID: {}): {}", what, child.id(), e),
+    });
+}
+
+/// Debounces workspace changes for the workspace-change hook
+#[derive(Debug, Default)]
+pub struct WorkspaceHook {
+    /// Latest workspace switched to and when
+    pending: Option<(usize, Instant)>,
+    /// Workspace the hook last ran for
+    last_reported: Option<usize>,
+}
+
+impl WorkspaceHook {
+    /// Note that the active workspace changed
+    pub fn workspace_changed(&mut self, number: usize, now: Instant) {
+        self.pending = Some((number, now));
+    }
+
+    /// Get the workspace to report once it has been active for the
+    /// debounce delay (switching away and back reports nothing)
+    pub fn poll(&mut self, now: Instant) -> Option<usize> {
+        let (number, changed_at) = self.pending?;
+        if now.saturating_duration_since(changed_at) < WORKSPACE_HOOK_DEBOUNCE {
+            return None;
+        }
+
+        self.pending = None;
+        if self.last_reported == Some(number) {
+            return None;
+        }
+
+        self.last_reported = Some(number);
+        Some(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_change_command() {
+        let cmd = workspace_change_command("notify-send \"$1\"", 3, "code");
+
+        assert_eq!(cmd.get_program(), "sh");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["-c", "notify-send \"$1\"", "codeverse-workspace-hook", "3", "code"]);
+
+        let envs: Vec<_> = cmd.get_envs().collect();
+        assert!(envs.contains(&("CODEVERSE_WORKSPACE".as_ref(), Some("3".as_ref()))));
+        assert!(envs.contains(&("CODEVERSE_WORKSPACE_NAME".as_ref(), Some("code".as_ref()))));
+    }
+
+    #[test]
+    fn test_workspace_hook_debounce() {
+        let start = Instant::now();
+        let mut hook = WorkspaceHook::default();
+
+        // Rapid switches 1 -> 2 -> 3 only report 3
+        hook.workspace_changed(2, start);
+        hook.workspace_changed(3, start + Duration::from_millis(50));
+        assert_eq!(hook.poll(start + Duration::from_millis(100)), None);
+        assert_eq!(hook.poll(start + Duration::from_millis(250)), Some(3));
+        assert_eq!(hook.poll(start + Duration::from_millis(500)), None);
+
+        // Switching away and back again reports nothing new
+        hook.workspace_changed(4, start + Duration::from_secs(1));
+        hook.workspace_changed(3, start + Duration::from_millis(1050));
+        assert_eq!(hook.poll(start + Duration::from_secs(2)), None);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::compositor::CodeVerseCompositor;
+use codeverse_config::{Action, SplitDirection};
+use codeverse_window::{Direction, LayoutMode, Orientation, WindowTreeExt};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Execute a bound action (from a keyboard shortcut or mouse-button binding)
+pub fn dispatch_action<BackendData: 'static>(
+    compositor: &mut CodeVerseCompositor<BackendData>,
+    action: &Action,
+) {
+    let guard_window = Duration::from_millis(compositor.config.general.repeat_guard_ms);
+    if !compositor.repeat_guard.allow(action, Instant::now(), guard_window) {
+        debug!("Ignoring repeated {:?}", action);
+        return;
+    }
+
+    debug!("Dispatching action: {:?}", action);
+
+    match action {
+        Action::Quit => {
+            if compositor.config.general.confirm_quit && !compositor.quit_confirm.press(Instant::now()) {
+                // The on-screen prompt has no text, so say what it means here
+                warn!(
+                    "Quit requested, press the quit binding again within {}s to confirm",
+                    super::quit::QUIT_CONFIRM_TIMEOUT.as_secs()
+                );
+                return;
+            }
+            info!("Quit action triggered, exiting compositor");
+            compositor.running = false;
+        }
+        Action::CloseWindow => compositor.close_focused_window(),
+        Action::NavigateFocus(direction) => {
+            let dir = match direction {
+                codeverse_config::Direction::Left => Direction::Left,
+                codeverse_config::Direction::Right => Direction::Right,
+                codeverse_config::Direction::Up => Direction::Up,
+                codeverse_config::Direction::Down => Direction::Down,
+            };
+            compositor.window_tree.navigate_focus(dir);
+        }
+        Action::Split(direction) => {
+            let orientation = match direction {
+                SplitDirection::Horizontal => Orientation::Horizontal,
+                SplitDirection::Vertical => Orientation::Vertical,
+            };
+            if let Err(e) = compositor.window_tree.split_focused(orientation) {
+                warn!("Failed to split: {}", e);
+            }
+        }
+        Action::SwitchWorkspace(workspace_num) => {
+            let (active, previous) = compositor
+                .workspace_manager
+                .as_ref()
+                .map(|manager| (manager.active_workspace_num(), manager.previous_workspace_num()))
+                .unwrap_or((0, None));
+
+            if *workspace_num != active {
+                compositor.switch_workspace(*workspace_num);
+            } else if let (true, Some(previous)) = (compositor.config.workspaces.number_key_toggles_back, previous) {
+                debug!("Workspace {} already active, going back to {}", active, previous);
+                compositor.switch_workspace(previous);
+            }
+        }
+        Action::MoveToWorkspace(workspace_num) => {
+            if let (Some(focused_id), Some(ref mut manager)) =
+                (compositor.window_tree.focused(), compositor.workspace_manager.as_mut())
+            {
+                if let Err(e) = manager.move_window_to_workspace(
+                    &mut compositor.window_tree,
+                    focused_id,
+                    *workspace_num,
+                ) {
+                    warn!("Failed to move window to workspace: {}", e);
+                }
+            }
+        }
+        Action::MoveWorkspaceTo(workspace_num) => {
+            let active = compositor
+                .workspace_manager
+                .as_ref()
+                .map(|manager| manager.active_workspace_num());
+            if let Some(active) = active {
+                if let Err(e) = compositor.move_workspace(active, *workspace_num) {
+                    warn!("Failed to move workspace: {}", e);
+                }
+            }
+        }
+        Action::ChangeLayout(name) => {
+            if let Err(e) = compositor.set_layout(name) {
+                warn!("Failed to change layout: {}", e);
+            }
+        }
+        Action::WrapContainer(name) => {
+            let layout: LayoutMode = match name.parse() {
+                Ok(layout) => layout,
+                Err(e) => {
+                    warn!("{}", e);
+                    return;
+                }
+            };
+
+            if let Err(e) = compositor.window_tree.wrap_in_container(layout) {
+                warn!("Failed to wrap container: {}", e);
+            } else {
+                compositor.relayout_active_workspace();
+            }
+        }
+        Action::UnwrapContainer => {
+            if let Err(e) = compositor.window_tree.unwrap_container() {
+                warn!("Failed to unwrap container: {}", e);
+            } else {
+                compositor.relayout_active_workspace();
+            }
+        }
+        Action::TearOff => {
+            if let Some(focused_id) = compositor.window_tree.focused() {
+                let x = compositor.pointer_location.x as i32;
+                let y = compositor.pointer_location.y as i32;
+
+                if let Err(e) = compositor.floating_manager.tear_off(&mut compositor.window_tree, focused_id, x, y) {
+                    warn!("Failed to tear off window: {}", e);
+                } else {
+                    compositor.update_window_border_colors();
+                    compositor.relayout_active_workspace();
+                }
+            }
+        }
+        Action::ToggleFloating => {
+            if let Some(focused_id) = compositor.window_tree.focused() {
+                // Use cached screen geometry if available, otherwise use a default
+                let screen_geometry = compositor.last_screen_geometry
+                    .unwrap_or_else(|| codeverse_window::Rectangle::new(0, 0, 1920, 1080));
+
+                if let Err(e) = compositor.floating_manager.toggle_floating(
+                    &mut compositor.window_tree,
+                    focused_id,
+                    screen_geometry,
+                ) {
+                    warn!("Failed to toggle floating: {}", e);
+                } else {
+                    compositor.relayout_active_workspace();
+                }
+            }
+        }
+        Action::ResizeToGrid(slot) => {
+            if let Some(focused_id) = compositor.window_tree.focused() {
+                let screen_geometry = compositor.last_screen_geometry
+                    .unwrap_or_else(|| codeverse_window::Rectangle::new(0, 0, 1920, 1080));
+                let target = codeverse_window::grid_slot_rect(*slot, compositor.work_area(screen_geometry));
+
+                if let Err(e) = compositor.floating_manager.float_at(
+                    &mut compositor.window_tree,
+                    focused_id,
+                    target,
+                ) {
+                    warn!("Failed to resize window to grid: {}", e);
+                } else {
+                    compositor.relayout_active_workspace();
+                }
+            }
+        }
+        Action::SaveFloatingDefault => {
+            if let Err(e) = compositor.save_floating_default() {
+                warn!("Failed to save floating default: {}", e);
+            }
+        }
+        Action::ToggleLauncher => compositor.toggle_launcher(),
+        Action::ToggleOverview => compositor.toggle_overview(),
+        Action::FocusPrevious => compositor.focus_previous_window(),
+        Action::ReloadConfig => compositor.reload_config(),
+        Action::SpawnTerminal => {
+            if let Err(e) = super::keyboard::spawn_test_window(compositor.socket_name.as_deref()) {
+                compositor.show_error(e);
+            }
+        }
+    }
+}
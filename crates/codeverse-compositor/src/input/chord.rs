@@ -0,0 +1,194 @@
+use codeverse_config::{Action, Keybinding};
+use smithay::input::keyboard::ModifiersState;
+use std::time::{Duration, Instant};
+use xkbcommon::xkb::Keysym;
+
+/// How long a chord waits for its next key before starting over
+pub const CHORD_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outcome of feeding a key press to [`ChordState`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChordStep {
+    /// Not part of any chord; handle the key as usual
+    NoMatch,
+    /// Advanced a chord that needs more keys
+    Pending,
+    /// Broke off a chord in progress (the key is swallowed)
+    Canceled,
+    /// Completed a chord
+    Complete(Action),
+}
+
+/// Progress through multi-step bindings like "Super+space w c"
+#[derive(Debug, Default)]
+pub struct ChordState {
+    /// Indices of the chords still matching the keys pressed so far
+    candidates: Vec<usize>,
+    /// Number of steps matched so far
+    position: usize,
+    /// When the last step was matched
+    last_step_at: Option<Instant>,
+}
+
+impl ChordState {
+    /// Feed a non-modifier key press, matched against `chords`
+    pub fn key_press(
+        &mut self,
+        chords: &[(Vec<Keybinding>, Action)],
+        keysym: Keysym,
+        modifiers: ModifiersState,
+        now: Instant,
+    ) -> ChordStep {
+        self.expire(now);
+
+        let in_progress = self.is_pending();
+        let position = self.position;
+        let candidates: Vec<usize> = if in_progress {
+            std::mem::take(&mut self.candidates)
+        } else {
+            (0..chords.len()).collect()
+        };
+
+        let matching: Vec<usize> = candidates
+            .into_iter()
+            .filter(|&i| {
+                chords[i].0.get(position).is_some_and(|step| {
+                    step.matches(keysym, modifiers.logo, modifiers.shift, modifiers.ctrl, modifiers.alt)
+                })
+            })
+            .collect();
+
+        if matching.is_empty() {
+            self.reset();
+            return if in_progress { ChordStep::Canceled } else { ChordStep::NoMatch };
+        }
+
+        if let Some(&done) = matching.iter().find(|&&i| chords[i].0.len() == position + 1) {
+            self.reset();
+            return ChordStep::Complete(chords[done].1.clone());
+        }
+
+        self.candidates = matching;
+        self.position = position + 1;
+        self.last_step_at = Some(now);
+        ChordStep::Pending
+    }
+
+    /// Whether a chord is waiting for its next key
+    pub fn is_pending(&self) -> bool {
+        self.position > 0
+    }
+
+    /// Start over if the next key didn't come in time; returns whether a
+    /// chord in progress was dropped
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let timed_out = self
+            .last_step_at
+            .is_some_and(|at| now.saturating_duration_since(at) >= CHORD_TIMEOUT);
+        if timed_out {
+            self.reset();
+        }
+        timed_out
+    }
+
+    /// Drop any chord in progress
+    pub fn reset(&mut self) {
+        self.candidates.clear();
+        self.position = 0;
+        self.last_step_at = None;
+    }
+}
+
+/// Whether `keysym` is a modifier key, which doesn't advance or break a chord
+pub fn is_modifier_key(keysym: Keysym) -> bool {
+    matches!(
+        keysym,
+        Keysym::Super_L
+            | Keysym::Super_R
+            | Keysym::Shift_L
+            | Keysym::Shift_R
+            | Keysym::Control_L
+            | Keysym::Control_R
+            | Keysym::Alt_L
+            | Keysym::Alt_R
+            | Keysym::Meta_L
+            | Keysym::Meta_R
+            | Keysym::ISO_Level3_Shift
+            | Keysym::Caps_Lock
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modifiers(logo: bool) -> ModifiersState {
+        ModifiersState {
+            logo,
+            ..Default::default()
+        }
+    }
+
+    fn chords() -> Vec<(Vec<Keybinding>, Action)> {
+        vec![
+            (Keybinding::parse_sequence("Super+space w c").unwrap(), Action::CloseWindow),
+            (Keybinding::parse_sequence("Super+space w f").unwrap(), Action::ToggleFloating),
+            (Keybinding::parse_sequence("Super+space r").unwrap(), Action::ReloadConfig),
+        ]
+    }
+
+    #[test]
+    fn test_chord_steps() {
+        let chords = chords();
+        let start = Instant::now();
+        let mut state = ChordState::default();
+
+        // Keys that don't start a chord pass through
+        assert_eq!(state.key_press(&chords, Keysym::w, modifiers(false), start), ChordStep::NoMatch);
+
+        // Step through "Super+space w c"
+        assert_eq!(state.key_press(&chords, Keysym::space, modifiers(true), start), ChordStep::Pending);
+        assert!(state.is_pending());
+        assert_eq!(state.key_press(&chords, Keysym::w, modifiers(false), start), ChordStep::Pending);
+        assert_eq!(
+            state.key_press(&chords, Keysym::c, modifiers(false), start),
+            ChordStep::Complete(Action::CloseWindow)
+        );
+        assert!(!state.is_pending());
+
+        // A shorter chord sharing the leader completes on its own
+        state.key_press(&chords, Keysym::space, modifiers(true), start);
+        assert_eq!(
+            state.key_press(&chords, Keysym::r, modifiers(false), start),
+            ChordStep::Complete(Action::ReloadConfig)
+        );
+
+        // A wrong key cancels and is swallowed; the next one is normal again
+        state.key_press(&chords, Keysym::space, modifiers(true), start);
+        assert_eq!(state.key_press(&chords, Keysym::x, modifiers(false), start), ChordStep::Canceled);
+        assert_eq!(state.key_press(&chords, Keysym::c, modifiers(false), start), ChordStep::NoMatch);
+    }
+
+    #[test]
+    fn test_chord_timeout() {
+        let chords = chords();
+        let start = Instant::now();
+        let mut state = ChordState::default();
+
+        state.key_press(&chords, Keysym::space, modifiers(true), start);
+        state.key_press(&chords, Keysym::w, modifiers(false), start + Duration::from_secs(1));
+        assert!(!state.expire(start + Duration::from_secs(2)));
+
+        // Too long since the last step: the chord starts over
+        let late = start + Duration::from_secs(1) + CHORD_TIMEOUT;
+        assert_eq!(state.key_press(&chords, Keysym::f, modifiers(false), late), ChordStep::NoMatch);
+        assert!(!state.is_pending());
+
+        state.key_press(&chords, Keysym::space, modifiers(true), late);
+        assert!(state.expire(late + CHORD_TIMEOUT));
+        assert!(!state.is_pending());
+
+        assert!(is_modifier_key(Keysym::Super_L));
+        assert!(!is_modifier_key(Keysym::space));
+    }
+}
@@ -1,7 +1,10 @@
+use super::actions::dispatch_action;
+use super::chord::{is_modifier_key, ChordStep};
 use crate::compositor::CodeVerseCompositor;
-use codeverse_window::{Direction, LayoutMode, Orientation, WindowTreeExt};
+use codeverse_config::{canonical_key, Action, Direction, Keybinding, Modifier, SplitDirection};
 use smithay::input::keyboard::ModifiersState;
 use std::process::Command;
+use std::time::Instant;
 use tracing::{debug, info, warn};
 use xkbcommon::xkb::Keysym;
 
@@ -11,193 +14,129 @@ pub fn handle_keyboard_shortcut<BackendData: 'static>(
     keysym: Keysym,
     modifiers: ModifiersState,
 ) -> bool {
+    // Any key press hides the cursor until the pointer moves
+    if compositor.config.input.hide_cursor_while_typing {
+        compositor.cursor_hidden_by_typing = true;
+    }
+
+    // Focused client asked to receive all keys (VMs, remote desktops, games)
+    if compositor.shortcuts_inhibited() {
+        return false;
+    }
+
     // If launcher is active, handle launcher input first
     if compositor.launcher_active {
         return handle_launcher_input(compositor, keysym, modifiers);
     }
 
-    // Check if Super (Logo/Mod) key is pressed
-    let logo_pressed = modifiers.logo;
-    let shift_pressed = modifiers.shift;
-
-    // Super+d: Toggle launcher
-    if logo_pressed && !shift_pressed && keysym == Keysym::d {
-        info!("Toggling launcher (Super+d pressed)");
-        compositor.toggle_launcher();
-        return true;
+    // Workspace overview captures navigation keys while shown
+    if compositor.overview_active {
+        return handle_overview_input(compositor, keysym, modifiers);
     }
 
-    // Super+Shift+Q: Quit compositor
-    if logo_pressed && shift_pressed && keysym == Keysym::q {
-        info!("Quit shortcut pressed, exiting compositor");
-        compositor.running = false;
-        return true;
+    // Chords take the keys they continue; a key that breaks one is swallowed
+    if !compositor.chords.is_empty() && !is_modifier_key(keysym) {
+        match compositor
+            .chord_state
+            .key_press(&compositor.chords, keysym, modifiers, Instant::now())
+        {
+            ChordStep::NoMatch => {}
+            ChordStep::Pending => {
+                debug!("Chord pending after {:?}", keysym);
+                return true;
+            }
+            ChordStep::Canceled => {
+                debug!("Chord canceled by {:?}", keysym);
+                return true;
+            }
+            ChordStep::Complete(action) => {
+                dispatch_action(compositor, &action);
+                return true;
+            }
+        }
     }
 
-    // Super+Shift+R: Reload configuration
-    if logo_pressed && shift_pressed && keysym == Keysym::r {
-        info!("Reload config shortcut pressed");
-        compositor.reload_config();
+    // Configured bindings win; the built-in keys only cover combos the
+    // config doesn't bind
+    let held = Modifier::held(modifiers.logo, modifiers.shift, modifiers.ctrl, modifiers.alt);
+    let action = compositor
+        .bindings
+        .get(&Keybinding::for_key(keysym, held))
+        .cloned()
+        .or_else(|| builtin_action(keysym, modifiers));
+
+    if let Some(action) = action {
+        dispatch_action(compositor, &action);
         return true;
     }
 
-    // Navigation: Super+h/j/k/l
-    if logo_pressed && !shift_pressed {
-        let direction = match keysym {
-            Keysym::h => Some(Direction::Left),
-            Keysym::j => Some(Direction::Down),
-            Keysym::k => Some(Direction::Up),
-            Keysym::l => Some(Direction::Right),
-            _ => None,
-        };
-
-        if let Some(dir) = direction {
-            debug!("Navigation shortcut: {:?}", dir);
-            compositor.window_tree.navigate_focus(dir);
-            return true;
-        }
-    }
+    false // Shortcut not handled
+}
 
-    // Split: Super+b (horizontal) / Super+v (vertical)
-    if logo_pressed && !shift_pressed {
-        let orientation = match keysym {
-            Keysym::b => Some(Orientation::Horizontal),
-            Keysym::v => Some(Orientation::Vertical),
-            _ => None,
-        };
+/// Keys that work without a config entry for them
+fn builtin_action(keysym: Keysym, modifiers: ModifiersState) -> Option<Action> {
+    // Shift+q arrives as Q
+    let keysym = canonical_key(keysym);
 
-        if let Some(orient) = orientation {
-            debug!("Split shortcut: {:?}", orient);
-            if let Err(e) = compositor.window_tree.split_focused(orient) {
-                tracing::warn!("Failed to split: {}", e);
-            }
-            return true;
-        }
-    }
+    // Check if Super (Logo/Mod) key is pressed
+    let logo_pressed = modifiers.logo;
+    let shift_pressed = modifiers.shift;
 
-    // Workspace switching: Super+1-0
     if logo_pressed && !shift_pressed {
-        if let Some(workspace_num) = keysym_to_workspace_num(keysym) {
-            debug!("Switching to workspace {}", workspace_num);
-            if let Some(ref mut manager) = compositor.workspace_manager {
-                manager.switch_to_workspace(workspace_num);
-
-                // Recalculate layout for the new workspace
-                if let Some(_workspace_id) = manager.active_workspace() {
-                    // Use cached screen geometry if available, otherwise use a default
-                    let screen_geometry = compositor.last_screen_geometry
-                        .unwrap_or_else(|| codeverse_window::Rectangle::new(0, 0, 1920, 1080));
-                    let gap_width = compositor.config.general.gap_width as i32;
-                    manager.layout_active_workspace(&mut compositor.window_tree, screen_geometry, gap_width);
-                }
-            }
-            return true;
+        match keysym {
+            // Super+d: Toggle launcher
+            Keysym::d => Some(Action::ToggleLauncher),
+
+            // Super+Tab: Toggle workspace overview
+            Keysym::Tab => Some(Action::ToggleOverview),
+
+            // Navigation: Super+h/j/k/l
+            Keysym::h => Some(Action::NavigateFocus(Direction::Left)),
+            Keysym::j => Some(Action::NavigateFocus(Direction::Down)),
+            Keysym::k => Some(Action::NavigateFocus(Direction::Up)),
+            Keysym::l => Some(Action::NavigateFocus(Direction::Right)),
+
+            // Split: Super+b (horizontal) / Super+v (vertical)
+            Keysym::b => Some(Action::Split(SplitDirection::Horizontal)),
+            Keysym::v => Some(Action::Split(SplitDirection::Vertical)),
+
+            // Layout switching: Super+e/w/s/t
+            Keysym::e => Some(Action::ChangeLayout("splith".to_string())),
+            Keysym::w => Some(Action::ChangeLayout("splitv".to_string())),
+            Keysym::s => Some(Action::ChangeLayout("stacking".to_string())),
+            Keysym::t => Some(Action::ChangeLayout("tabbed".to_string())),
+
+            // Workspace switching: Super+1-0
+            _ => keysym_to_workspace_num(keysym).map(Action::SwitchWorkspace),
         }
-    }
+    } else if logo_pressed && shift_pressed {
+        match keysym {
+            // Super+Shift+Q: Quit compositor
+            Keysym::q => Some(Action::Quit),
 
-    // Move window to workspace: Super+Shift+1-0
-    if logo_pressed && shift_pressed {
-        if let Some(workspace_num) = keysym_to_workspace_num(keysym) {
-            debug!("Moving window to workspace {}", workspace_num);
-            if let (Some(focused_id), Some(ref mut manager)) =
-                (compositor.window_tree.focused(), compositor.workspace_manager.as_mut())
-            {
-                if let Err(e) = manager.move_window_to_workspace(
-                    &mut compositor.window_tree,
-                    focused_id,
-                    workspace_num,
-                ) {
-                    tracing::warn!("Failed to move window to workspace: {}", e);
-                }
-            }
-            return true;
-        }
-    }
+            // Super+Shift+R: Reload configuration
+            Keysym::r => Some(Action::ReloadConfig),
 
-    // Layout switching: Super+e/w/s/t
-    if logo_pressed && !shift_pressed {
-        let layout_mode = match keysym {
-            Keysym::e => Some(LayoutMode::SplitH),  // Horizontal split
-            Keysym::w => Some(LayoutMode::SplitV),  // Vertical split
-            Keysym::s => Some(LayoutMode::Stacking), // Stacking
-            Keysym::t => Some(LayoutMode::Tabbed),   // Tabbed
-            _ => None,
-        };
-
-        if let Some(layout) = layout_mode {
-            debug!("Layout switching shortcut: {:?}", layout);
-            if let Err(e) = compositor.window_tree.change_layout(layout) {
-                tracing::warn!("Failed to change layout: {}", e);
-            } else {
-                // Recalculate layout after changing mode
-                if let Some(ref mut manager) = compositor.workspace_manager {
-                    if let Some(_workspace_id) = manager.active_workspace() {
-                        // Use cached screen geometry if available, otherwise use a default
-                        let screen_geometry = compositor.last_screen_geometry
-                            .unwrap_or_else(|| codeverse_window::Rectangle::new(0, 0, 1920, 1080));
-                        let gap_width = compositor.config.general.gap_width as i32;
-                        manager.layout_active_workspace(&mut compositor.window_tree, screen_geometry, gap_width);
-                    }
-                }
-            }
-            return true;
-        }
-    }
+            // Super+Shift+C: Close focused window
+            Keysym::c => Some(Action::CloseWindow),
 
-    // Super+Shift+Space: Toggle floating mode
-    if logo_pressed && shift_pressed && keysym == Keysym::space {
-        debug!("Toggle floating shortcut");
-        if let Some(focused_id) = compositor.window_tree.focused() {
-            // Use cached screen geometry if available, otherwise use a default
-            let screen_geometry = compositor.last_screen_geometry
-                .unwrap_or_else(|| codeverse_window::Rectangle::new(0, 0, 1920, 1080));
-
-            if let Err(e) = compositor.floating_manager.toggle_floating(
-                &mut compositor.window_tree,
-                focused_id,
-                screen_geometry,
-            ) {
-                tracing::warn!("Failed to toggle floating: {}", e);
-            } else {
-                // Recalculate layout after toggle
-                if let Some(ref mut manager) = compositor.workspace_manager {
-                    if let Some(_workspace_id) = manager.active_workspace() {
-                        let gap_width = compositor.config.general.gap_width as i32;
-                        manager.layout_active_workspace(&mut compositor.window_tree, screen_geometry, gap_width);
-                    }
-                }
-            }
-        }
-        return true;
-    }
+            // Super+Shift+Space: Toggle floating mode
+            Keysym::space => Some(Action::ToggleFloating),
 
-    // Super+Shift+C: Close focused window
-    if logo_pressed && shift_pressed && keysym == Keysym::c {
-        debug!("Close window shortcut");
-        if let Some(focused_id) = compositor.window_tree.focused() {
-            // Find the toplevel surface and close it
-            if let Some(container) = compositor.window_tree.get(focused_id) {
-                if let Some(ref window) = container.window {
-                    window.send_close();
-                }
-            }
+            // Move window to workspace: Super+Shift+1-0
+            _ => keysym_to_workspace_num(keysym).map(Action::MoveToWorkspace),
         }
-        return true;
+    } else if keysym == Keysym::F12 {
+        // F12: Spawn test window (no modifier needed to avoid conflicts)
+        Some(Action::SpawnTerminal)
+    } else {
+        None
     }
-
-    // F12: Spawn test window (no modifier needed to avoid conflicts)
-    if keysym == Keysym::F12 {
-        info!("Spawning test window (F12 pressed)");
-        spawn_test_window(compositor.socket_name.as_deref());
-        return true;
-    }
-
-    false // Shortcut not handled
 }
 
 /// Spawn a test window for testing the compositor
 /// Tries multiple terminal emulators in order of preference
-fn spawn_test_window(socket_name: Option<&str>) {
+pub(crate) fn spawn_test_window(socket_name: Option<&str>) -> Result<(), String> {
     // Prefer terminals that don't use D-Bus single-instance activation,
     // since GApplication-based apps (ptyxis, gnome-terminal) will delegate
     // to an existing instance on the parent session instead of opening here.
@@ -257,7 +196,7 @@ fn spawn_test_window(socket_name: Option<&str>) {
                         }
                     }
                 });
-                return;
+                return Ok(());
             }
             Err(_) => {
                 continue;
@@ -266,6 +205,34 @@ fn spawn_test_window(socket_name: Option<&str>) {
     }
 
     warn!("Failed to spawn test window - no terminal emulator found. Tried: {:?}", terminals);
+    Err(format!("No terminal found (tried {})", terminals.join(", ")))
+}
+
+/// Track Super taps; called for both key presses and releases.
+/// Runs `general.tap_super_action` when Super is tapped on its own.
+/// Releases also re-arm actions held back by the repeat guard.
+pub fn handle_super_tap<BackendData: 'static>(
+    compositor: &mut CodeVerseCompositor<BackendData>,
+    keysym: Keysym,
+    pressed: bool,
+) {
+    if !pressed {
+        compositor.repeat_guard.key_released();
+    }
+
+    if compositor.shortcuts_inhibited() {
+        compositor.super_tap.cancel();
+        return;
+    }
+
+    if !compositor.super_tap.key_event(keysym, pressed) {
+        return;
+    }
+
+    if let Some(action) = compositor.config.general.tap_super_action.clone() {
+        debug!("Super tapped, running {:?}", action);
+        dispatch_action(compositor, &action);
+    }
 }
 
 /// Handle keyboard input when launcher is active
@@ -274,6 +241,8 @@ fn handle_launcher_input<BackendData: 'static>(
     keysym: Keysym,
     modifiers: ModifiersState,
 ) -> bool {
+    compositor.launcher_last_activity = std::time::Instant::now();
+
     // Escape: Close launcher
     if keysym == Keysym::Escape {
         debug!("Closing launcher (Escape pressed)");
@@ -336,6 +305,53 @@ fn handle_launcher_input<BackendData: 'static>(
     false // Not handled
 }
 
+/// Handle keyboard input when the workspace overview is shown
+fn handle_overview_input<BackendData: 'static>(
+    compositor: &mut CodeVerseCompositor<BackendData>,
+    keysym: Keysym,
+    modifiers: ModifiersState,
+) -> bool {
+    use codeverse_window::Direction;
+
+    // Escape or Super+Tab: Close overview without switching
+    if keysym == Keysym::Escape || (modifiers.logo && keysym == Keysym::Tab) {
+        debug!("Closing workspace overview");
+        compositor.overview_active = false;
+        return true;
+    }
+
+    // Enter: Switch to the selected workspace
+    if keysym == Keysym::Return || keysym == Keysym::KP_Enter {
+        let selected = compositor.overview_selected;
+        debug!("Overview: switching to workspace {}", selected);
+        compositor.overview_confirm(selected);
+        return true;
+    }
+
+    // Number keys: Jump straight to a workspace
+    if let Some(workspace_num) = keysym_to_workspace_num(keysym) {
+        compositor.overview_confirm(workspace_num);
+        return true;
+    }
+
+    // Arrow keys or h/j/k/l: Move selection
+    let direction = match keysym {
+        Keysym::Left | Keysym::h => Some(Direction::Left),
+        Keysym::Down | Keysym::j => Some(Direction::Down),
+        Keysym::Up | Keysym::k => Some(Direction::Up),
+        Keysym::Right | Keysym::l => Some(Direction::Right),
+        _ => None,
+    };
+
+    if let Some(direction) = direction {
+        compositor.overview_navigate(direction);
+        debug!("Overview: selected workspace {}", compositor.overview_selected);
+        return true;
+    }
+
+    false // Not handled
+}
+
 /// Convert keysym to character (basic ASCII only for MVP)
 fn keysym_to_char(keysym: Keysym, shift: bool) -> Option<char> {
     // Letters
@@ -374,6 +390,7 @@ fn keysym_to_char(keysym: Keysym, shift: bool) -> Option<char> {
         Keysym::bracketleft => Some(if shift { '{' } else { '[' }),
         Keysym::bracketright => Some(if shift { '}' } else { ']' }),
         Keysym::semicolon => Some(if shift { ':' } else { ';' }),
+        Keysym::colon => Some(':'),
         Keysym::apostrophe => Some(if shift { '"' } else { '\'' }),
         Keysym::comma => Some(if shift { '<' } else { ',' }),
         Keysym::period => Some(if shift { '>' } else { '.' }),
@@ -387,21 +404,8 @@ fn keysym_to_char(keysym: Keysym, shift: bool) -> Option<char> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::compositor::CodeVerseCompositor;
-    use smithay::{
-        input::keyboard::ModifiersState,
-        reexports::calloop::EventLoop,
-        reexports::wayland_server::Display,
-    };
-
-    // Helper to create a test compositor
-    fn create_test_compositor() -> CodeVerseCompositor<()> {
-        let mut event_loop: EventLoop<CodeVerseCompositor<()>> = EventLoop::try_new().unwrap();
-        let loop_handle = event_loop.handle();
-        let mut display: Display<CodeVerseCompositor<()>> = Display::new().unwrap();
-
-        CodeVerseCompositor::new(&mut display, loop_handle, ())
-    }
+    use crate::testing::create_test_compositor;
+    use smithay::input::keyboard::ModifiersState;
 
     // Helper to create modifier state
     fn modifiers(logo: bool, shift: bool, ctrl: bool, alt: bool) -> ModifiersState {
@@ -506,6 +510,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_startup_workspace() {
+        let mut compositor = create_test_compositor();
+        compositor.config.workspaces.count = 10;
+        compositor.config.workspaces.startup = 4;
+        compositor.init_workspace_manager();
+
+        let manager = compositor.workspace_manager.as_ref().unwrap();
+        assert_eq!(manager.active_workspace_num(), 4);
+    }
+
+    #[test]
+    fn test_shortcuts_forwarded_when_inhibited() {
+        use codeverse_window::{Container, ContainerType, NodeId};
+
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+
+        let window_id = compositor
+            .window_tree
+            .insert(Container::new(NodeId::default(), ContainerType::Window));
+        compositor.window_tree.set_focused(Some(window_id));
+        compositor.shortcuts_inhibited_windows.insert(window_id);
+
+        let result = handle_keyboard_shortcut(
+            &mut compositor,
+            Keysym::d,
+            modifiers(true, false, false, false),
+        );
+        assert!(!result, "Super+d should be forwarded to the inhibiting client");
+        assert!(!compositor.launcher_active);
+
+        // Once the window loses focus, shortcuts work again
+        compositor.window_tree.set_focused(None);
+        let result = handle_keyboard_shortcut(
+            &mut compositor,
+            Keysym::d,
+            modifiers(true, false, false, false),
+        );
+        assert!(result);
+        assert!(compositor.launcher_active);
+    }
+
+    #[test]
+    fn test_overview_navigation() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+
+        let result = handle_keyboard_shortcut(
+            &mut compositor,
+            Keysym::Tab,
+            modifiers(true, false, false, false),
+        );
+        assert!(result, "Super+Tab should be handled");
+        assert!(compositor.overview_active);
+
+        // Number key in the overview switches and closes it
+        handle_keyboard_shortcut(&mut compositor, Keysym::_3, modifiers(false, false, false, false));
+        assert!(!compositor.overview_active);
+        assert_eq!(compositor.workspace_manager.as_ref().unwrap().active_workspace_num(), 3);
+    }
+
+    #[test]
+    fn test_number_key_toggles_back() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        let active = |compositor: &CodeVerseCompositor<()>| {
+            compositor.workspace_manager.as_ref().unwrap().active_workspace_num()
+        };
+        let super_only = modifiers(true, false, false, false);
+
+        // Disabled: pressing the active workspace's number stays put
+        handle_keyboard_shortcut(&mut compositor, Keysym::_3, super_only);
+        handle_keyboard_shortcut(&mut compositor, Keysym::_3, super_only);
+        assert_eq!(active(&compositor), 3);
+
+        // Enabled: it returns to the previous workspace, and back again
+        compositor.config.workspaces.number_key_toggles_back = true;
+        handle_keyboard_shortcut(&mut compositor, Keysym::_3, super_only);
+        assert_eq!(active(&compositor), 1);
+        handle_keyboard_shortcut(&mut compositor, Keysym::_1, super_only);
+        assert_eq!(active(&compositor), 3);
+    }
+
     #[test]
     fn test_layout_switching() {
         let mut compositor = create_test_compositor();
@@ -576,6 +664,62 @@ mod tests {
         assert!(!result, "Super+Z (unbound) should not be handled");
     }
 
+    #[test]
+    fn test_configured_keybinding() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        compositor.bindings.insert(
+            codeverse_config::Keybinding::parse("Ctrl+Alt+Right").unwrap(),
+            Action::SwitchWorkspace(2),
+        );
+
+        assert!(handle_keyboard_shortcut(&mut compositor, Keysym::Right, modifiers(false, false, true, true)));
+        assert_eq!(compositor.workspace_manager.as_ref().unwrap().active_workspace_num(), 2);
+
+        // Only with exactly the bound modifiers
+        assert!(!handle_keyboard_shortcut(&mut compositor, Keysym::Right, modifiers(false, false, true, false)));
+    }
+
+    #[test]
+    fn test_configured_keybinding_overrides_builtin() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        compositor.bindings.insert(
+            codeverse_config::Keybinding::parse("Super+d").unwrap(),
+            Action::SwitchWorkspace(3),
+        );
+
+        assert!(handle_keyboard_shortcut(&mut compositor, Keysym::d, modifiers(true, false, false, false)));
+        assert_eq!(compositor.workspace_manager.as_ref().unwrap().active_workspace_num(), 3);
+        assert!(!compositor.launcher_active);
+
+        // Built-ins still cover keys the config leaves out, shifted keysym included
+        compositor.bindings.clear();
+        assert!(handle_keyboard_shortcut(&mut compositor, Keysym::Q, modifiers(true, true, false, false)));
+        assert!(!compositor.running);
+    }
+
+    #[test]
+    fn test_chord_shortcut() {
+        let mut compositor = create_test_compositor();
+        compositor.chords = vec![(
+            codeverse_config::Keybinding::parse_sequence("Super+space x q").unwrap(),
+            Action::Quit,
+        )];
+
+        // Holding Super doesn't touch the chord; each step is swallowed
+        assert!(!handle_keyboard_shortcut(&mut compositor, Keysym::Super_L, modifiers(true, false, false, false)));
+        assert!(handle_keyboard_shortcut(&mut compositor, Keysym::space, modifiers(true, false, false, false)));
+        assert!(handle_keyboard_shortcut(&mut compositor, Keysym::x, modifiers(false, false, false, false)));
+        assert!(compositor.running);
+
+        assert!(handle_keyboard_shortcut(&mut compositor, Keysym::q, modifiers(false, false, false, false)));
+        assert!(!compositor.running);
+
+        // Once done, keys reach clients again
+        assert!(!handle_keyboard_shortcut(&mut compositor, Keysym::x, modifiers(false, false, false, false)));
+    }
+
     #[test]
     fn test_keysym_to_workspace_num() {
         assert_eq!(keysym_to_workspace_num(Keysym::_1), Some(1));
@@ -1,5 +1,15 @@
+pub mod actions;
+pub mod chord;
 pub mod keyboard;
 pub mod pointer;
+pub mod quit;
+pub mod repeat;
+pub mod tap;
 
-pub use keyboard::handle_keyboard_shortcut;
-pub use pointer::{handle_pointer_axis, handle_pointer_button, handle_pointer_motion};
+pub use actions::dispatch_action;
+pub use chord::ChordState;
+pub use keyboard::{handle_keyboard_shortcut, handle_super_tap};
+pub use pointer::{handle_pointer_axis, handle_pointer_button, handle_pointer_motion, handle_tablet_pad_button};
+pub use quit::QuitConfirm;
+pub use repeat::RepeatGuard;
+pub use tap::SuperTapDetector;
@@ -1,10 +1,15 @@
+use super::actions::dispatch_action;
 use crate::compositor::CodeVerseCompositor;
+use codeverse_config::{Keybinding, Modifier};
 use codeverse_window::{MouseOperation, ResizeEdge};
+use smithay::input::keyboard::ModifiersState;
 use smithay::input::pointer::AxisFrame;
-use smithay::utils::{Logical, Point, Serial, SERIAL_COUNTER};
+use smithay::utils::{Logical, Point, Serial};
 use tracing::debug;
 
-/// Handle pointer button press/release
+/// Handle pointer button press/release.
+/// Returns true if the event was consumed by a mouse-button binding and
+/// should not be forwarded to clients.
 pub fn handle_pointer_button<BackendData: 'static>(
     compositor: &mut CodeVerseCompositor<BackendData>,
     button: u32,
@@ -12,29 +17,83 @@ pub fn handle_pointer_button<BackendData: 'static>(
     _serial: Serial,
     _time: u32,
     location: Point<f64, Logical>,
-) {
-    let modifiers = compositor
-        .seat
-        .get_keyboard()
-        .unwrap()
-        .modifier_state();
+) -> bool {
+    let modifiers = held_modifiers(compositor);
+
+    // Mouse-button bindings (e.g. "Button8" for the back button)
+    let held = Modifier::held(modifiers.logo, modifiers.shift, modifiers.ctrl, modifiers.alt);
+    let bound_action = compositor.bindings.get(&Keybinding::for_button(button, held)).cloned();
+
+    if let Some(action) = bound_action {
+        // Dispatch on press; swallow the matching release as well
+        if state == smithay::backend::input::ButtonState::Pressed {
+            dispatch_action(compositor, &action);
+        } else {
+            compositor.repeat_guard.key_released();
+        }
+        return true;
+    }
 
     let logo_pressed = modifiers.logo;
     let x = location.x as i32;
     let y = location.y as i32;
 
+    // Clicking while Super is held is not a Super tap
+    if state == smithay::backend::input::ButtonState::Pressed {
+        compositor.super_tap.cancel();
+    }
+
+    // Workspace overview: clicking a cell switches to that workspace.
+    // Clients don't receive clicks while the overview is shown.
+    if compositor.overview_active {
+        if state == smithay::backend::input::ButtonState::Pressed {
+            let clicked = compositor
+                .overview_cells()
+                .into_iter()
+                .find(|(_, cell)| cell.contains_point(x, y))
+                .map(|(workspace_num, _)| workspace_num);
+
+            if let Some(workspace_num) = clicked {
+                compositor.overview_confirm(workspace_num);
+            }
+        }
+        return true;
+    }
+
+    // Taskbar: clicking a button focuses its window; the strip itself
+    // belongs to the compositor, so clients don't see these clicks
+    if let Some(screen) = compositor.last_screen_geometry {
+        if compositor.taskbar_strip(screen).is_some_and(|strip| strip.contains_point(x, y)) {
+            if state == smithay::backend::input::ButtonState::Pressed {
+                let buttons = compositor.taskbar_buttons(screen);
+                let rects: Vec<_> = buttons.iter().map(|&(_, rect)| rect).collect();
+                if let Some(index) = codeverse_window::taskbar_button_at(&rects, x, y) {
+                    compositor.activate_window(buttons[index].0);
+                }
+            }
+            return true;
+        }
+    }
+
     // Mouse button constants
     const BTN_LEFT: u32 = 0x110;
     const BTN_RIGHT: u32 = 0x111;
 
     if state == smithay::backend::input::ButtonState::Pressed {
-        // Find window under cursor
-        if let Some(window_id) = compositor.floating_manager.find_window_at(&compositor.window_tree, x, y) {
-            // Set focus to clicked window
-            compositor.window_tree.set_focused(Some(window_id));
+        // Find floating window under cursor on the active workspace
+        let active_workspace = compositor
+            .workspace_manager
+            .as_ref()
+            .and_then(|manager| manager.active_workspace());
+        let window_under = active_workspace.and_then(|workspace_id| {
+            compositor
+                .floating_manager
+                .find_window_at(&compositor.window_tree, workspace_id, x, y)
+        });
 
-            // Raise window to top
-            compositor.floating_manager.raise_window(window_id);
+        if let Some(window_id) = window_under {
+            // Focus and raise the clicked window
+            compositor.focus_window(window_id);
 
             // Super+LeftClick: Start moving
             if logo_pressed && button == BTN_LEFT {
@@ -47,7 +106,7 @@ pub fn handle_pointer_button<BackendData: 'static>(
                 ) {
                     tracing::warn!("Failed to start move: {}", e);
                 }
-                return;
+                return false;
             }
 
             // Super+RightClick: Start resizing
@@ -69,7 +128,7 @@ pub fn handle_pointer_button<BackendData: 'static>(
                 ) {
                     tracing::warn!("Failed to start resize: {}", e);
                 }
-                return;
+                return false;
             }
 
             // Regular click on title bar: Start moving (without modifier)
@@ -83,16 +142,59 @@ pub fn handle_pointer_button<BackendData: 'static>(
                 ) {
                     tracing::warn!("Failed to start move: {}", e);
                 }
-                return;
+                return false;
             }
         }
     } else {
         // Button released: finish operation
-        if !matches!(compositor.floating_manager.current_operation(), MouseOperation::None) {
+        let operation = compositor.floating_manager.current_operation();
+        if !matches!(operation, MouseOperation::None) {
             debug!("Finishing mouse operation");
             compositor.floating_manager.finish_operation();
+
+            if let MouseOperation::Moving { window, .. } = operation {
+                if compositor.config.general.drop_floating_onto_tile {
+                    compositor.drop_floating_window(window, x, y);
+                }
+            }
         }
     }
+
+    false
+}
+
+/// Handle a tablet pad button press/release (`button` is libinput's
+/// zero-based pad button number). Pad buttons only trigger bindings like
+/// "Pad1"; without a tablet protocol there is no client to send them to.
+pub fn handle_tablet_pad_button<BackendData: 'static>(
+    compositor: &mut CodeVerseCompositor<BackendData>,
+    button: u32,
+    state: smithay::backend::input::ButtonState,
+) {
+    let modifiers = held_modifiers(compositor);
+    let held = Modifier::held(modifiers.logo, modifiers.shift, modifiers.ctrl, modifiers.alt);
+    let bound_action = compositor.bindings.get(&Keybinding::for_pad_button(button, held)).cloned();
+
+    let Some(action) = bound_action else {
+        debug!("Tablet pad button {} is not bound", button);
+        return;
+    };
+
+    if state == smithay::backend::input::ButtonState::Pressed {
+        dispatch_action(compositor, &action);
+    } else {
+        compositor.repeat_guard.key_released();
+    }
+}
+
+/// Modifiers held on the seat keyboard; without a keyboard (e.g. it was
+/// just unplugged) none are held
+fn held_modifiers<BackendData: 'static>(compositor: &CodeVerseCompositor<BackendData>) -> ModifiersState {
+    compositor
+        .seat
+        .get_keyboard()
+        .map(|keyboard| keyboard.modifier_state())
+        .unwrap_or_default()
 }
 
 /// Handle pointer motion
@@ -104,6 +206,9 @@ pub fn handle_pointer_motion<BackendData: 'static>(
     let x = location.x as i32;
     let y = location.y as i32;
 
+    // Moving the pointer brings back a cursor hidden by typing
+    compositor.cursor_hidden_by_typing = false;
+
     // Update ongoing operation
     if !matches!(compositor.floating_manager.current_operation(), MouseOperation::None) {
         if let Err(e) = compositor.floating_manager.update_operation(&mut compositor.window_tree, x, y) {
@@ -115,26 +220,7 @@ pub fn handle_pointer_motion<BackendData: 'static>(
     // Focus follows mouse: when enabled, move keyboard focus to
     // whichever window the pointer enters.
     if compositor.config.general.focus_follows_mouse {
-        if let Some(window_id) = compositor.window_under(location) {
-            let current_focus = compositor.window_tree.focused();
-            if current_focus != Some(window_id) {
-                compositor.window_tree.set_focused(Some(window_id));
-                compositor.update_window_border_colors();
-
-                let serial = SERIAL_COUNTER.next_serial();
-                let kb_surface = compositor.window_tree.get(window_id)
-                    .and_then(|c| c.window.as_ref())
-                    .map(|t| t.wl_surface().clone());
-                if let Some(surface) = kb_surface {
-                    let keyboard = compositor.seat.get_keyboard().unwrap();
-                    keyboard.set_focus(
-                        compositor,
-                        Some(crate::focus::KeyboardFocusTarget::Surface(surface)),
-                        serial,
-                    );
-                }
-            }
-        }
+        compositor.focus_window_under_pointer();
     }
 }
 
@@ -145,3 +231,158 @@ pub fn handle_pointer_axis<BackendData: 'static>(
 ) {
     // Axis events not used for now
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{add_window, create_test_compositor};
+    use codeverse_config::{Action, Keybinding};
+    use smithay::backend::input::ButtonState;
+    use smithay::utils::SERIAL_COUNTER;
+
+    #[test]
+    fn test_mouse_button_binding_dispatches_action() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        compositor.bindings.insert(
+            Keybinding::parse("Button8").unwrap(),
+            Action::SwitchWorkspace(3),
+        );
+
+        const BTN_SIDE: u32 = 0x113;
+        let location = Point::from((0.0, 0.0));
+
+        let consumed = handle_pointer_button(
+            &mut compositor,
+            BTN_SIDE,
+            ButtonState::Pressed,
+            SERIAL_COUNTER.next_serial(),
+            0,
+            location,
+        );
+        assert!(consumed, "Bound button press should not reach clients");
+        assert_eq!(compositor.workspace_manager.as_ref().unwrap().active_workspace_num(), 3);
+
+        let consumed = handle_pointer_button(
+            &mut compositor,
+            BTN_SIDE,
+            ButtonState::Released,
+            SERIAL_COUNTER.next_serial(),
+            0,
+            location,
+        );
+        assert!(consumed, "Bound button release should not reach clients");
+
+        // Unbound buttons are passed through
+        let consumed = handle_pointer_button(
+            &mut compositor,
+            0x110,
+            ButtonState::Pressed,
+            SERIAL_COUNTER.next_serial(),
+            0,
+            location,
+        );
+        assert!(!consumed);
+    }
+
+    #[test]
+    fn test_tablet_pad_button_dispatches_action() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        compositor.bindings.insert(Keybinding::parse("Pad2").unwrap(), Action::SwitchWorkspace(4));
+
+        // Libinput numbers pad buttons from zero, so "Pad2" is button 1
+        handle_tablet_pad_button(&mut compositor, 0, ButtonState::Pressed);
+        assert_eq!(compositor.workspace_manager.as_ref().unwrap().active_workspace_num(), 1);
+
+        handle_tablet_pad_button(&mut compositor, 1, ButtonState::Pressed);
+        handle_tablet_pad_button(&mut compositor, 1, ButtonState::Released);
+        assert_eq!(compositor.workspace_manager.as_ref().unwrap().active_workspace_num(), 4);
+    }
+
+    #[test]
+    fn test_pointer_input_without_seat_keyboard() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        compositor.config.general.focus_follows_mouse = true;
+        compositor.bindings.insert(
+            Keybinding::parse("Button8").unwrap(),
+            Action::SwitchWorkspace(2),
+        );
+
+        // Keyboard unplugged: modifiers read as released instead of panicking
+        compositor.seat.remove_keyboard();
+        assert!(compositor.seat.get_keyboard().is_none());
+
+        let location = Point::from((100.0, 100.0));
+        handle_pointer_motion(&mut compositor, location, 0);
+
+        const BTN_LEFT: u32 = 0x110;
+        const BTN_SIDE: u32 = 0x113;
+        let consumed = handle_pointer_button(
+            &mut compositor,
+            BTN_LEFT,
+            ButtonState::Pressed,
+            SERIAL_COUNTER.next_serial(),
+            0,
+            location,
+        );
+        assert!(!consumed);
+
+        // Bindings without modifiers still work
+        let consumed = handle_pointer_button(
+            &mut compositor,
+            BTN_SIDE,
+            ButtonState::Pressed,
+            SERIAL_COUNTER.next_serial(),
+            0,
+            location,
+        );
+        assert!(consumed);
+        assert_eq!(compositor.workspace_manager.as_ref().unwrap().active_workspace_num(), 2);
+    }
+
+    #[test]
+    fn test_focus_follows_mouse() {
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        let left = add_window(&mut compositor, 1);
+        let right = add_window(&mut compositor, 1);
+        compositor.window_tree.get_mut(left).unwrap().geometry = codeverse_window::Rectangle::new(0, 0, 960, 1080);
+        compositor.window_tree.get_mut(right).unwrap().geometry = codeverse_window::Rectangle::new(960, 0, 960, 1080);
+        compositor.window_tree.set_focused(Some(left));
+
+        let location = Point::from((1200.0, 500.0));
+        compositor.pointer_location = location;
+
+        // Disabled by default
+        handle_pointer_motion(&mut compositor, location, 0);
+        assert_eq!(compositor.window_tree.focused(), Some(left));
+
+        compositor.config.general.focus_follows_mouse = true;
+        handle_pointer_motion(&mut compositor, location, 0);
+        assert_eq!(compositor.window_tree.focused(), Some(right));
+    }
+
+    #[test]
+    fn test_cursor_hidden_while_typing() {
+        use crate::input::handle_keyboard_shortcut;
+        use smithay::input::keyboard::ModifiersState;
+        use xkbcommon::xkb::Keysym;
+
+        let mut compositor = create_test_compositor();
+        compositor.init_workspace_manager();
+        let location = Point::from((100.0, 100.0));
+
+        // Disabled: typing leaves the cursor alone
+        handle_keyboard_shortcut(&mut compositor, Keysym::a, ModifiersState::default());
+        assert!(!compositor.cursor_hidden_by_typing);
+
+        // Enabled: a key press hides it, pointer motion brings it back
+        compositor.config.input.hide_cursor_while_typing = true;
+        handle_keyboard_shortcut(&mut compositor, Keysym::a, ModifiersState::default());
+        assert!(compositor.cursor_hidden_by_typing);
+        handle_pointer_motion(&mut compositor, location, 0);
+        assert!(!compositor.cursor_hidden_by_typing);
+    }
+}
@@ -0,0 +1,64 @@
+use std::time::{Duration, Instant};
+
+/// How long the "press again to quit" prompt waits for the second press
+pub const QUIT_CONFIRM_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Two-press confirmation for the Quit action (general.confirm_quit)
+#[derive(Debug, Default)]
+pub struct QuitConfirm {
+    /// When the first press armed the prompt
+    armed_at: Option<Instant>,
+}
+
+impl QuitConfirm {
+    /// Register a Quit press; returns whether to actually quit. The first
+    /// press arms the prompt, a second one before it times out confirms.
+    pub fn press(&mut self, now: Instant) -> bool {
+        if self.is_pending(now) {
+            self.armed_at = None;
+            return true;
+        }
+
+        self.armed_at = Some(now);
+        false
+    }
+
+    /// Whether the prompt is showing
+    pub fn is_pending(&self, now: Instant) -> bool {
+        self.armed_at
+            .is_some_and(|armed_at| now.saturating_duration_since(armed_at) < QUIT_CONFIRM_TIMEOUT)
+    }
+
+    /// Drop a prompt that timed out; returns whether one was canceled
+    pub fn expire(&mut self, now: Instant) -> bool {
+        if self.armed_at.is_some() && !self.is_pending(now) {
+            self.armed_at = None;
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quit_confirm() {
+        let start = Instant::now();
+        let mut confirm = QuitConfirm::default();
+
+        // First press arms, second within the timeout quits
+        assert!(!confirm.press(start));
+        assert!(confirm.is_pending(start + Duration::from_secs(1)));
+        assert!(confirm.press(start + Duration::from_secs(1)));
+        assert!(!confirm.is_pending(start + Duration::from_secs(1)));
+
+        // Waiting too long cancels; the next press only arms again
+        assert!(!confirm.press(start + Duration::from_secs(10)));
+        assert!(!confirm.expire(start + Duration::from_secs(11)));
+        assert!(confirm.expire(start + Duration::from_secs(10) + QUIT_CONFIRM_TIMEOUT));
+        assert!(!confirm.is_pending(start + Duration::from_secs(14)));
+        assert!(!confirm.press(start + Duration::from_secs(14)));
+    }
+}
@@ -0,0 +1,65 @@
+use codeverse_config::Action;
+use std::time::{Duration, Instant};
+
+/// Stops destructive actions (Quit, CloseWindow) from firing again on a
+/// stuck or repeated key: the same action is ignored within the guard
+/// window unless a key was released in between.
+#[derive(Debug, Default)]
+pub struct RepeatGuard {
+    /// Last non-repeatable action dispatched and when
+    last: Option<(Action, Instant)>,
+}
+
+impl RepeatGuard {
+    /// Check whether `action` may run now, recording it if it does.
+    /// A zero `window` disables the guard.
+    pub fn allow(&mut self, action: &Action, now: Instant, window: Duration) -> bool {
+        if action.is_repeatable() || window.is_zero() {
+            return true;
+        }
+
+        if let Some((last_action, at)) = &self.last {
+            if last_action == action && now.saturating_duration_since(*at) < window {
+                return false;
+            }
+        }
+
+        self.last = Some((action.clone(), now));
+        true
+    }
+
+    /// Note a key release; the next press may fire the same action again
+    pub fn key_released(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rapid_close_without_release_fires_once() {
+        let start = Instant::now();
+        let window = Duration::from_millis(500);
+        let mut guard = RepeatGuard::default();
+
+        assert!(guard.allow(&Action::CloseWindow, start, window));
+        assert!(!guard.allow(&Action::CloseWindow, start + Duration::from_millis(30), window));
+
+        // Other actions are unaffected
+        assert!(guard.allow(&Action::Quit, start + Duration::from_millis(40), window));
+        assert!(guard.allow(&Action::ToggleFloating, start + Duration::from_millis(50), window));
+        assert!(guard.allow(&Action::ToggleFloating, start + Duration::from_millis(60), window));
+
+        // Releasing the key re-arms the action
+        guard.key_released();
+        assert!(guard.allow(&Action::CloseWindow, start + Duration::from_millis(100), window));
+
+        // So does waiting out the guard window
+        assert!(guard.allow(&Action::CloseWindow, start + Duration::from_secs(1), window));
+
+        // A zero window disables the guard
+        assert!(guard.allow(&Action::CloseWindow, start + Duration::from_secs(1), Duration::ZERO));
+    }
+}
@@ -0,0 +1,62 @@
+use xkbcommon::xkb::Keysym;
+
+/// Detects a "tap" of the Super key: Super pressed and released with no
+/// other key or mouse button pressed in between.
+#[derive(Debug, Default)]
+pub struct SuperTapDetector {
+    /// Super is held and nothing else has been pressed yet
+    armed: bool,
+}
+
+impl SuperTapDetector {
+    /// Feed a key event; returns true when it completes a Super tap
+    pub fn key_event(&mut self, keysym: Keysym, pressed: bool) -> bool {
+        let is_super = matches!(keysym, Keysym::Super_L | Keysym::Super_R);
+
+        match (is_super, pressed) {
+            (true, true) => {
+                self.armed = true;
+                false
+            }
+            (true, false) => std::mem::take(&mut self.armed),
+            (false, true) => {
+                self.armed = false;
+                false
+            }
+            (false, false) => false,
+        }
+    }
+
+    /// Cancel a pending tap (e.g. a mouse button was used while Super was held)
+    pub fn cancel(&mut self) {
+        self.armed = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_super_tap_detection() {
+        let mut detector = SuperTapDetector::default();
+
+        // Press and release Super alone: a tap
+        assert!(!detector.key_event(Keysym::Super_L, true));
+        assert!(detector.key_event(Keysym::Super_L, false));
+
+        // Press Super, press d, release: not a tap
+        assert!(!detector.key_event(Keysym::Super_L, true));
+        assert!(!detector.key_event(Keysym::d, true));
+        assert!(!detector.key_event(Keysym::d, false));
+        assert!(!detector.key_event(Keysym::Super_L, false));
+
+        // Mouse button while Super is held: not a tap
+        assert!(!detector.key_event(Keysym::Super_R, true));
+        detector.cancel();
+        assert!(!detector.key_event(Keysym::Super_R, false));
+
+        // A stray release without a press: not a tap
+        assert!(!detector.key_event(Keysym::Super_L, false));
+    }
+}
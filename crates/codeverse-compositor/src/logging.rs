@@ -0,0 +1,156 @@
+use codeverse_config::{Config, LoggingConfig};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing_subscriber::fmt::writer::{BoxMakeWriter, MakeWriterExt};
+use tracing_subscriber::EnvFilter;
+
+/// Whether a write of `incoming` bytes should first rotate a log file that
+/// currently holds `current` bytes. A zero `max_size` disables rotation, and
+/// an empty file is never rotated (a single oversized write still lands).
+pub fn should_rotate(current: u64, incoming: u64, max_size: u64) -> bool {
+    max_size > 0 && current > 0 && current + incoming > max_size
+}
+
+/// Log file that moves itself aside to `<name>.1` when it grows past `max_size`
+pub struct RotatingFile {
+    path: PathBuf,
+    max_size: u64,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    /// Open (appending to) the log file at `path`, creating parent directories
+    pub fn open(path: &Path, max_size: u64) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            max_size,
+            file,
+            size,
+        })
+    }
+
+    /// Path of the previous log kept after rotation
+    pub fn rotated_path(&self) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(".1");
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        fs::rename(&self.path, self.rotated_path())?;
+
+        self.file = OpenOptions::new().create(true).write(true).truncate(true).open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if should_rotate(self.size, buf.len() as u64, self.max_size) {
+            self.rotate()?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Install the global tracing subscriber for both backends.
+///
+/// Reads the `[logging]` section of the user config (falling back to
+/// defaults if it can't be loaded); `RUST_LOG` overrides the level.
+/// On a TTY (DRM backend) stdout is the console, which nobody can read
+/// once we take over the display, so the log file is always enabled there.
+pub fn init(on_tty: bool) {
+    let mut logging = Config::load().map(|config| config.logging).unwrap_or_default();
+    if on_tty {
+        logging.file = true;
+    }
+    init_with(&logging);
+}
+
+fn init_with(logging: &LoggingConfig) {
+    let filter = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new(&logging.level))
+        .unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let file = if logging.file {
+        match LoggingConfig::log_path().map_err(|e| e.to_string()).and_then(|path| {
+            RotatingFile::open(&path, logging.max_size)
+                .map(|file| (path.clone(), file))
+                .map_err(|e| format!("{:?}: {}", path, e))
+        }) {
+            Ok(opened) => Some(opened),
+            Err(e) => {
+                eprintln!("Failed to open log file, logging to stdout only: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let log_path = file.as_ref().map(|(path, _)| path.clone());
+
+    let writer = match file {
+        Some((_, file)) if logging.stdout => BoxMakeWriter::new(io::stdout.and(Mutex::new(file))),
+        Some((_, file)) => BoxMakeWriter::new(Mutex::new(file)),
+        None => BoxMakeWriter::new(io::stdout),
+    };
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        // Escape codes would end up in the log file
+        .with_ansi(log_path.is_none())
+        .init();
+
+    if let Some(path) = log_path {
+        tracing::info!("Logging to {:?}", path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_rotate() {
+        assert!(!should_rotate(0, 100, 50));
+        assert!(!should_rotate(40, 10, 50));
+        assert!(should_rotate(41, 10, 50));
+        assert!(!should_rotate(1_000_000, 10, 0));
+    }
+
+    #[test]
+    fn test_rotating_file() {
+        let dir = std::env::temp_dir().join(format!("codeverse-log-test-{}", std::process::id()));
+        let path = dir.join("compositor.log");
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut file = RotatingFile::open(&path, 10).unwrap();
+        file.write_all(b"first\n").unwrap();
+        file.write_all(b"second\n").unwrap();
+
+        assert_eq!(fs::read_to_string(file.rotated_path()).unwrap(), "first\n");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,20 +1,33 @@
+mod animation;
+mod autostart;
 mod backend;
 mod compositor;
 mod focus;
 mod handlers;
+mod hooks;
 mod input;
+mod logging;
+mod placement;
 mod render;
+#[cfg(test)]
+mod testing;
+mod toast;
 
 use std::env;
 use tracing::info;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    info!("CodeVerse Compositor starting...");
-
     // Auto-detect backend based on environment
     // Use winit if DISPLAY or WAYLAND_DISPLAY is set (running in X11/Wayland session)
     // Otherwise use DRM (running directly on TTY)
-    if should_use_winit() {
+    let use_winit = should_use_winit();
+
+    // Set up logging before starting a backend so both log the same way
+    logging::init(!use_winit);
+
+    info!("CodeVerse Compositor starting...");
+
+    if use_winit {
         info!("Using Winit backend (nested session)");
         backend::init_winit()?;
     } else {
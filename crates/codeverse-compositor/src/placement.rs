@@ -0,0 +1,90 @@
+use codeverse_launcher::App;
+use std::time::{Duration, Instant};
+
+/// How long a launched app has to open its window before its workspace
+/// target is forgotten
+pub const PLACEMENT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An app launched with a workspace target (`:N` in the launcher)
+#[derive(Debug, Clone)]
+pub struct PendingPlacement {
+    /// PID of the launched process
+    pub pid: u32,
+    /// The launched app, to recognize its window by app_id
+    pub app: App,
+    /// Workspace its first window should open on
+    pub workspace: usize,
+    /// When it was launched
+    pub launched_at: Instant,
+}
+
+impl PendingPlacement {
+    /// Whether a window from `pid` with `app_id` belongs to this launch
+    pub fn matches(&self, pid: Option<u32>, app_id: Option<&str>) -> bool {
+        pid == Some(self.pid) || app_id.is_some_and(|app_id| self.app.matches_app_id(app_id))
+    }
+}
+
+/// Workspace targets waiting for the launched apps' windows
+#[derive(Debug, Default)]
+pub struct Placements {
+    pending: Vec<PendingPlacement>,
+}
+
+impl Placements {
+    /// Remember where a launched app's window should go
+    pub fn push(&mut self, placement: PendingPlacement) {
+        self.pending.push(placement);
+    }
+
+    /// Take the workspace for a new window, if it came from a targeted launch.
+    /// Each launch places one window; stale launches are dropped.
+    pub fn take_match(&mut self, pid: Option<u32>, app_id: Option<&str>, now: Instant) -> Option<usize> {
+        self.pending
+            .retain(|placement| now.saturating_duration_since(placement.launched_at) < PLACEMENT_TIMEOUT);
+
+        let index = self.pending.iter().position(|placement| placement.matches(pid, app_id))?;
+        Some(self.pending.remove(index).workspace)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn firefox(pid: u32, workspace: usize, launched_at: Instant) -> PendingPlacement {
+        PendingPlacement {
+            pid,
+            app: App {
+                name: "Firefox".to_string(),
+                exec: "firefox %u".to_string(),
+                description: None,
+                desktop_file: PathBuf::from("/usr/share/applications/firefox.desktop"),
+                terminal: false,
+                icon: None,
+                wm_class: None,
+            },
+            workspace,
+            launched_at,
+        }
+    }
+
+    #[test]
+    fn test_take_match() {
+        let now = Instant::now();
+        let mut placements = Placements::default();
+        placements.push(firefox(100, 3, now));
+        placements.push(firefox(200, 5, now));
+
+        // Matched by PID first, then by app_id; each launch places one window
+        assert_eq!(placements.take_match(Some(200), None, now), Some(5));
+        assert_eq!(placements.take_match(Some(999), Some("foot"), now), None);
+        assert_eq!(placements.take_match(None, Some("firefox"), now), Some(3));
+        assert_eq!(placements.take_match(None, Some("firefox"), now), None);
+
+        // Launches whose window never showed up are forgotten
+        placements.push(firefox(300, 2, now));
+        assert_eq!(placements.take_match(Some(300), None, now + PLACEMENT_TIMEOUT), None);
+    }
+}
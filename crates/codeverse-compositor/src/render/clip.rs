@@ -0,0 +1,18 @@
+use smithay::{
+    backend::renderer::element::{utils::CropRenderElement, Element},
+    utils::{Physical, Rectangle, Scale},
+};
+
+/// Crop surface elements to the `output` area, dropping the ones entirely
+/// outside it, so off-screen parts of large buffers and popups aren't drawn
+/// or tracked as damage
+pub fn crop_to_output<E: Element>(
+    elements: impl IntoIterator<Item = E>,
+    scale: impl Into<Scale<f64>> + Copy,
+    output: Rectangle<i32, Physical>,
+) -> Vec<CropRenderElement<E>> {
+    elements
+        .into_iter()
+        .filter_map(|element| CropRenderElement::from_element(element, scale, output))
+        .collect()
+}
@@ -0,0 +1,265 @@
+//! Per-window damage collected from surface commits
+//!
+//! The commit handler records what each client redrew. Buffer damage is
+//! mapped into surface-local logical coordinates with the buffer's scale
+//! and transform, so a frame can turn it into the output region that
+//! changed. Anything the commits can't describe (other surfaces, resized
+//! buffers, config reloads) asks for a full redraw instead.
+//!
+//! The winit backend passes this damage to the host when presenting. DRM
+//! outputs go through smithay's `DrmCompositor`, which tracks damage per
+//! render element itself.
+
+use codeverse_window::{NodeId, Rectangle};
+use smithay::utils::Transform;
+use std::collections::HashMap;
+
+/// Damage reported by a single commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitDamage {
+    /// Damage in surface-local logical coordinates (wl_surface.damage)
+    pub surface_rects: Vec<Rectangle>,
+    /// Damage in buffer coordinates (wl_surface.damage_buffer)
+    pub buffer_rects: Vec<Rectangle>,
+    /// Buffer scale of the commit
+    pub buffer_scale: i32,
+    /// Buffer transform of the commit
+    pub buffer_transform: Transform,
+    /// Size of the buffer, if the commit attaches a new one
+    pub new_buffer: Option<(u32, u32)>,
+}
+
+/// Damage and buffer state of one window since the last frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowDamage {
+    /// Damaged regions, surface-local
+    pub rects: Vec<Rectangle>,
+    /// Buffer scale of the latest commit
+    pub buffer_scale: i32,
+    /// Buffer transform of the latest commit
+    pub buffer_transform: Transform,
+    /// Size of the attached buffer, used to map buffer damage of commits
+    /// that don't attach a new one
+    pub buffer_size: Option<(u32, u32)>,
+}
+
+impl Default for WindowDamage {
+    fn default() -> Self {
+        Self {
+            rects: Vec::new(),
+            buffer_scale: 1,
+            buffer_transform: Transform::Normal,
+            buffer_size: None,
+        }
+    }
+}
+
+/// Map a damaged rectangle of a `buffer_size` buffer into surface-local
+/// logical coordinates, undoing the buffer transform and scale. Partially
+/// covered logical pixels are included.
+pub fn buffer_to_surface(rect: Rectangle, buffer_size: (u32, u32), scale: i32, transform: Transform) -> Rectangle {
+    let (w, h) = (buffer_size.0 as i32, buffer_size.1 as i32);
+    let (x, y, rw, rh) = (rect.x, rect.y, rect.width as i32, rect.height as i32);
+
+    // Rotations map through their inverse; flips are their own inverse
+    let (x, y) = match transform {
+        Transform::Normal => (x, y),
+        Transform::_90 => (y, w - x - rw),
+        Transform::_180 => (w - x - rw, h - y - rh),
+        Transform::_270 => (h - y - rh, x),
+        Transform::Flipped => (w - x - rw, y),
+        Transform::Flipped90 => (y, x),
+        Transform::Flipped180 => (x, h - y - rh),
+        Transform::Flipped270 => (h - y - rh, w - x - rw),
+    };
+    let (rw, rh) = match transform {
+        Transform::_90 | Transform::_270 | Transform::Flipped90 | Transform::Flipped270 => (rh, rw),
+        _ => (rw, rh),
+    };
+
+    let scale = scale.max(1);
+    let left = x.div_euclid(scale);
+    let top = y.div_euclid(scale);
+    let right = (x + rw + scale - 1).div_euclid(scale);
+    let bottom = (y + rh + scale - 1).div_euclid(scale);
+    Rectangle::new(left, top, (right - left).max(0) as u32, (bottom - top).max(0) as u32)
+}
+
+/// Damage for all windows, keyed by window
+#[derive(Debug, Default)]
+pub struct SurfaceDamage {
+    windows: HashMap<NodeId, WindowDamage>,
+    /// Something changed that window damage doesn't cover
+    full_redraw: bool,
+}
+
+impl SurfaceDamage {
+    /// Create an empty damage store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a commit: add its damage (buffer damage mapped to the surface)
+    /// and remember the buffer state. A buffer that changes size, scale or
+    /// transform may uncover what was behind it, so that asks for a full redraw.
+    pub fn record(&mut self, window_id: NodeId, commit: CommitDamage) {
+        let entry = self.windows.entry(window_id).or_default();
+
+        if let Some(size) = commit.new_buffer {
+            let previous = entry.buffer_size.map(|old| (old, entry.buffer_scale, entry.buffer_transform));
+            if previous.is_some_and(|previous| previous != (size, commit.buffer_scale, commit.buffer_transform)) {
+                self.full_redraw = true;
+            }
+            entry.buffer_size = Some(size);
+        }
+        entry.buffer_scale = commit.buffer_scale;
+        entry.buffer_transform = commit.buffer_transform;
+
+        entry.rects.extend(commit.surface_rects.into_iter().filter(|rect| !rect.is_empty()));
+        if let Some(size) = entry.buffer_size {
+            let mapped = commit
+                .buffer_rects
+                .into_iter()
+                .map(|rect| buffer_to_surface(rect, size, commit.buffer_scale, commit.buffer_transform));
+            entry.rects.extend(mapped.filter(|rect| !rect.is_empty()));
+        }
+    }
+
+    /// Redraw everything on the next frame
+    pub fn damage_all(&mut self) {
+        self.full_redraw = true;
+    }
+
+    /// Output regions to repaint for windows drawn with their surface at
+    /// the given locations, or `None` when the whole output must be redrawn
+    pub fn frame_damage(&self, windows: &[(NodeId, i32, i32)]) -> Option<Vec<Rectangle>> {
+        if self.full_redraw {
+            return None;
+        }
+
+        let rects = windows
+            .iter()
+            .filter_map(|(window_id, x, y)| Some((self.windows.get(window_id)?, x, y)))
+            .flat_map(|(damage, x, y)| {
+                damage
+                    .rects
+                    .iter()
+                    .map(move |rect| Rectangle::new(rect.x + x, rect.y + y, rect.width, rect.height))
+            })
+            .collect();
+        Some(rects)
+    }
+
+    /// Drop all pending damage after a frame
+    pub fn clear_damage(&mut self) {
+        for damage in self.windows.values_mut() {
+            damage.rects.clear();
+        }
+        self.full_redraw = false;
+    }
+
+    /// Forget a closed window
+    pub fn remove(&mut self, window_id: NodeId) {
+        self.windows.remove(&window_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codeverse_window::{Container, ContainerType, WindowTree};
+
+    fn surface_commit(rects: Vec<Rectangle>, buffer_scale: i32, buffer_transform: Transform) -> CommitDamage {
+        CommitDamage {
+            surface_rects: rects,
+            buffer_rects: Vec::new(),
+            buffer_scale,
+            buffer_transform,
+            new_buffer: None,
+        }
+    }
+
+    #[test]
+    fn test_damage_accumulates_across_commits() {
+        let mut tree = WindowTree::new();
+        let window = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        let other = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        let mut damage = SurfaceDamage::new();
+
+        damage.record(window, surface_commit(vec![Rectangle::new(0, 0, 10, 10)], 1, Transform::Normal));
+        damage.record(
+            window,
+            surface_commit(vec![Rectangle::new(20, 20, 5, 5), Rectangle::new(0, 0, 0, 5)], 2, Transform::_90),
+        );
+        assert!(!damage.windows.contains_key(&other));
+
+        // The latest buffer state wins; empty rects are dropped
+        let state = &damage.windows[&window];
+        assert_eq!(state.buffer_scale, 2);
+        assert_eq!(state.buffer_transform, Transform::_90);
+        assert_eq!(state.rects, vec![Rectangle::new(0, 0, 10, 10), Rectangle::new(20, 20, 5, 5)]);
+
+        // A frame drops the damage but keeps the buffer state
+        damage.clear_damage();
+        assert!(damage.windows[&window].rects.is_empty());
+        assert_eq!(damage.windows[&window].buffer_scale, 2);
+    }
+
+    #[test]
+    fn test_buffer_damage_maps_to_output() {
+        let mut tree = WindowTree::new();
+        let window = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        let mut damage = SurfaceDamage::new();
+
+        // A 200x400 buffer rotated by 90 degrees at scale 2 is a 200x100
+        // surface; its top-left corner ends up at the surface's bottom-left
+        let commit = CommitDamage {
+            surface_rects: Vec::new(),
+            buffer_rects: vec![Rectangle::new(0, 0, 20, 40)],
+            buffer_scale: 2,
+            buffer_transform: Transform::_90,
+            new_buffer: Some((200, 400)),
+        };
+        damage.record(window, commit);
+        assert_eq!(damage.frame_damage(&[(window, 100, 50)]), Some(vec![Rectangle::new(100, 140, 20, 10)]));
+        damage.clear_damage();
+
+        // Later commits map buffer damage with the buffer they don't replace;
+        // odd buffer pixels round out to whole logical pixels
+        let commit = CommitDamage {
+            surface_rects: Vec::new(),
+            buffer_rects: vec![Rectangle::new(199, 399, 1, 1)],
+            buffer_scale: 2,
+            buffer_transform: Transform::_90,
+            new_buffer: None,
+        };
+        damage.record(window, commit);
+        assert_eq!(damage.frame_damage(&[(window, 0, 0)]), Some(vec![Rectangle::new(199, 0, 1, 1)]));
+
+        // Windows not drawn this frame don't add damage
+        assert_eq!(damage.frame_damage(&[]), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_resized_buffer_redraws_everything() {
+        let mut tree = WindowTree::new();
+        let window = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        let mut damage = SurfaceDamage::new();
+
+        let mut commit = surface_commit(vec![Rectangle::new(0, 0, 800, 600)], 1, Transform::Normal);
+        commit.new_buffer = Some((800, 600));
+        damage.record(window, commit.clone());
+        damage.record(window, commit.clone());
+        assert!(damage.frame_damage(&[(window, 0, 0)]).is_some());
+
+        commit.new_buffer = Some((640, 480));
+        damage.record(window, commit);
+        assert_eq!(damage.frame_damage(&[(window, 0, 0)]), None);
+
+        damage.clear_damage();
+        assert_eq!(damage.frame_damage(&[(window, 0, 0)]), Some(Vec::new()));
+
+        damage.damage_all();
+        assert_eq!(damage.frame_damage(&[(window, 0, 0)]), None);
+    }
+}
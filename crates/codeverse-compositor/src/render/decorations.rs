@@ -13,86 +13,123 @@ use smithay::{
 /// Render element for window borders
 pub type BorderRenderElement = SolidColorRenderElement;
 
-/// Create border render elements for a window
+/// How far window shadows (`general.shadows_enabled`) reach past the border
+pub const SHADOW_RADIUS: u32 = 12;
+
+/// Opacity of a shadow right at the window's border
+const SHADOW_ALPHA: f32 = 0.3;
+
+/// Rectangles of a border ring `width` thick, `offset` outside of `rect`
+fn ring(rect: Rectangle<i32, Physical>, offset: i32, width: i32, color: NordColor) -> [(Rectangle<i32, Physical>, NordColor); 4] {
+    let x = rect.loc.x - offset;
+    let y = rect.loc.y - offset;
+    let w = rect.size.w + offset * 2;
+    let h = rect.size.h + offset * 2;
+
+    [
+        // Top
+        (Rectangle::from_loc_and_size((x - width, y - width), (w + width * 2, width)), color),
+        // Bottom
+        (Rectangle::from_loc_and_size((x - width, y + h), (w + width * 2, width)), color),
+        // Left
+        (Rectangle::from_loc_and_size((x - width, y), (width, h)), color),
+        // Right
+        (Rectangle::from_loc_and_size((x + w, y), (width, h)), color),
+    ]
+}
+
+/// Rectangles and colors making up a window's border
 ///
-/// Creates four SolidColorRenderElements representing the top, bottom,
-/// left, and right borders around a window.
-pub fn create_border_elements(
+/// With an `inner` border (`theme.inner_border_width`/`inner_border_color`)
+/// the innermost pixels of the border get the inner color, so the total
+/// width (and the layout) stays the same.
+pub fn border_rects(
     window_rect: Rectangle<i32, Physical>,
     border_width: u32,
     color: NordColor,
-    id: &str,
-) -> Vec<BorderRenderElement> {
+    inner: Option<(u32, NordColor)>,
+) -> Vec<(Rectangle<i32, Physical>, NordColor)> {
     if border_width == 0 {
         return vec![];
     }
 
-    let mut elements = Vec::with_capacity(4);
     let border_width = border_width as i32;
-    let color_array = color.to_f32_array();
-    let color = Color32F::new(color_array[0], color_array[1], color_array[2], color_array[3]);
-
-    let x = window_rect.loc.x;
-    let y = window_rect.loc.y;
-    let width = window_rect.size.w;
-    let height = window_rect.size.h;
-
-    // Top border
-    let top_rect = Rectangle::from_loc_and_size(
-        (x - border_width, y - border_width),
-        (width + border_width * 2, border_width),
-    );
-    elements.push(SolidColorRenderElement::new(
-        Id::new(),
-        top_rect,
-        1,  // commit counter
-        color,
-        Kind::Unspecified,
-    ));
-
-    // Bottom border
-    let bottom_rect = Rectangle::from_loc_and_size(
-        (x - border_width, y + height),
-        (width + border_width * 2, border_width),
-    );
-    elements.push(SolidColorRenderElement::new(
-        Id::new(),
-        bottom_rect,
-        1,
-        color,
-        Kind::Unspecified,
-    ));
-
-    // Left border
-    let left_rect = Rectangle::from_loc_and_size(
-        (x - border_width, y),
-        (border_width, height),
-    );
-    elements.push(SolidColorRenderElement::new(
-        Id::new(),
-        left_rect,
-        1,
-        color,
-        Kind::Unspecified,
-    ));
-
-    // Right border
-    let right_rect = Rectangle::from_loc_and_size(
-        (x + width, y),
-        (border_width, height),
-    );
-    elements.push(SolidColorRenderElement::new(
-        Id::new(),
-        right_rect,
-        1,
-        color,
-        Kind::Unspecified,
-    ));
+    let (inner_width, inner_color) = inner
+        .map(|(width, color)| ((width as i32).min(border_width), color))
+        .unwrap_or((0, color));
+
+    let mut rects = Vec::with_capacity(8);
+    if inner_width < border_width {
+        rects.extend(ring(window_rect, inner_width, border_width - inner_width, color));
+    }
+    if inner_width > 0 {
+        rects.extend(ring(window_rect, 0, inner_width, inner_color));
+    }
+    rects
+}
+
+/// Rectangles and colors of a soft shadow around a window
+///
+/// The shadow starts outside the `border_width` border and is made of
+/// `radius` one-pixel rings of black, fading from [`SHADOW_ALPHA`] at the
+/// border to nothing at the outer edge.
+pub fn shadow_rects(
+    window_rect: Rectangle<i32, Physical>,
+    border_width: u32,
+    radius: u32,
+) -> Vec<(Rectangle<i32, Physical>, NordColor)> {
+    (0..radius)
+        .filter_map(|i| {
+            let remaining = (radius - i) as f32 / radius as f32;
+            let alpha = (SHADOW_ALPHA * remaining * remaining * 255.0).round() as u8;
+            (alpha > 0).then(|| ring(window_rect, (border_width + i) as i32, 1, NordColor::rgba(0, 0, 0, alpha)))
+        })
+        .flatten()
+        .collect()
+}
+
+/// Create shadow render elements for a window (draw them behind its border)
+pub fn create_shadow_elements(
+    window_rect: Rectangle<i32, Physical>,
+    border_width: u32,
+    radius: u32,
+) -> Vec<BorderRenderElement> {
+    solid_elements(shadow_rects(window_rect, border_width, radius))
+}
+
+/// Solid color render elements for colored rectangles
+fn solid_elements(rects: Vec<(Rectangle<i32, Physical>, NordColor)>) -> Vec<BorderRenderElement> {
+    rects
+        .into_iter()
+        .map(|(rect, color)| {
+            let [r, g, b, a] = color.to_f32_array();
+            SolidColorRenderElement::new(
+                Id::new(),
+                rect,
+                1, // commit counter
+                Color32F::new(r, g, b, a),
+                Kind::Unspecified,
+            )
+        })
+        .collect()
+}
 
+/// Create border render elements for a window
+///
+/// Creates four SolidColorRenderElements representing the top, bottom,
+/// left, and right borders around a window, plus four more for the inner
+/// ring of a double border.
+pub fn create_border_elements(
+    window_rect: Rectangle<i32, Physical>,
+    border_width: u32,
+    color: NordColor,
+    inner: Option<(u32, NordColor)>,
+    id: &str,
+) -> Vec<BorderRenderElement> {
     // Suppress unused id warning - id can be used for debugging later
     let _ = id;
 
-    elements
+    solid_elements(border_rects(window_rect, border_width, color, inner))
 }
 
 #[cfg(test)]
@@ -103,7 +140,7 @@ mod tests {
     fn test_create_borders() {
         let rect: Rectangle<i32, Physical> = Rectangle::from_loc_and_size((100, 100), (400, 300));
         let color = NordColor::rgb(0x88, 0xc0, 0xd0);
-        let borders = create_border_elements(rect, 2, color, "test-window");
+        let borders = create_border_elements(rect, 2, color, None, "test-window");
 
         // Should create 4 border elements
         assert_eq!(borders.len(), 4);
@@ -113,9 +150,63 @@ mod tests {
     fn test_zero_border_width() {
         let rect: Rectangle<i32, Physical> = Rectangle::from_loc_and_size((100, 100), (400, 300));
         let color = NordColor::rgb(0x88, 0xc0, 0xd0);
-        let borders = create_border_elements(rect, 0, color, "test-window");
+        let borders = create_border_elements(rect, 0, color, None, "test-window");
 
         // Zero border width should return empty
         assert_eq!(borders.len(), 0);
     }
+
+    #[test]
+    fn test_double_border() {
+        let rect: Rectangle<i32, Physical> = Rectangle::from_loc_and_size((100, 100), (400, 300));
+        let outer = NordColor::rgb(0x4c, 0x56, 0x6a);
+        let inner = NordColor::rgb(0x88, 0xc0, 0xd0);
+
+        // Zero inner width draws a plain border
+        assert_eq!(border_rects(rect, 4, outer, Some((0, inner))), border_rects(rect, 4, outer, None));
+
+        let rects = border_rects(rect, 4, outer, Some((1, inner)));
+        assert_eq!(rects.len(), 8);
+        assert_eq!(create_border_elements(rect, 4, outer, Some((1, inner)), "test-window").len(), 8);
+
+        // Outer ring first, then the inner ring hugging the window
+        assert!(rects[..4].iter().all(|(_, color)| *color == outer));
+        assert!(rects[4..].iter().all(|(_, color)| *color == inner));
+        assert_eq!(rects[0].0, Rectangle::from_loc_and_size((96, 96), (408, 3)));
+        assert_eq!(rects[4].0, Rectangle::from_loc_and_size((99, 99), (402, 1)));
+
+        // The total border width never grows
+        let rects = border_rects(rect, 2, outer, Some((5, inner)));
+        assert_eq!(rects.len(), 4);
+        assert_eq!(rects[0], (Rectangle::from_loc_and_size((98, 98), (404, 2)), inner));
+    }
+
+    #[test]
+    fn test_shadow_extent() {
+        let rect: Rectangle<i32, Physical> = Rectangle::from_loc_and_size((100, 100), (400, 300));
+        let rects = shadow_rects(rect, 2, 8);
+        assert_eq!(rects.len(), 4 * 8);
+
+        // Starts right outside the border and reaches `radius` past it
+        assert_eq!(rects[0].0, Rectangle::from_loc_and_size((97, 97), (406, 1)));
+        let extent = rects
+            .iter()
+            .map(|(rect, _)| *rect)
+            .reduce(|a, b| a.merge(b))
+            .unwrap();
+        assert_eq!(extent, Rectangle::from_loc_and_size((90, 90), (420, 320)));
+
+        // Never covers the window or its border
+        let bordered = Rectangle::from_loc_and_size((98, 98), (404, 304));
+        assert!(rects.iter().all(|(rect, _)| !rect.overlaps(bordered)));
+
+        // Black, fading outwards
+        let alphas: Vec<u8> = rects.iter().step_by(4).map(|(_, color)| color.a).collect();
+        assert!(rects.iter().all(|(_, color)| (color.r, color.g, color.b) == (0, 0, 0)));
+        assert!(alphas.windows(2).all(|pair| pair[0] > pair[1]));
+        assert_eq!(alphas[0], 77);
+
+        assert!(shadow_rects(rect, 2, 0).is_empty());
+        assert_eq!(create_shadow_elements(rect, 0, SHADOW_RADIUS).len(), 4 * SHADOW_RADIUS as usize);
+    }
 }
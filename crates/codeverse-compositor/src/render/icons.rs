@@ -0,0 +1,83 @@
+//! Window icon cache
+//!
+//! Resolves app icons from desktop entries (via the launcher's app
+//! discovery) and keeps decoded, scaled pixels per app_id for drawing
+//! in title bars and on taskbar buttons. Icons are loaded when a window's
+//! app_id becomes known, so rendering only looks them up.
+
+use super::wallpaper::{load_wallpaper_image, scale_wallpaper, ScaleMode};
+use codeverse_launcher::{default_icon_dirs, AppDiscovery};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// Space between an icon and the edge of its title bar or taskbar button
+pub const ICON_PADDING: u32 = 4;
+
+/// Decoded icon pixels (RGBA)
+pub struct CachedIcon {
+    pub data: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Icons keyed by (app_id, size); `None` records an app without a usable icon
+#[derive(Default)]
+pub struct IconCache {
+    discovery: Option<AppDiscovery>,
+    icon_dirs: Vec<PathBuf>,
+    cache: HashMap<(String, u32), Option<CachedIcon>>,
+    generation: u64,
+}
+
+impl IconCache {
+    /// Create an empty icon cache (desktop entries are scanned on first use)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the icon for an app_id scaled to `size`x`size`, loading it if needed
+    pub fn get_or_load(&mut self, app_id: &str, size: u32) -> Option<&CachedIcon> {
+        let key = (app_id.to_string(), size);
+
+        if !self.cache.contains_key(&key) {
+            let icon = self.load(app_id, size);
+            self.cache.insert(key.clone(), icon);
+        }
+
+        self.cache.get(&key).and_then(|icon| icon.as_ref())
+    }
+
+    /// Get an already loaded icon without touching the disk
+    pub fn get(&self, app_id: &str, size: u32) -> Option<&CachedIcon> {
+        self.cache
+            .get(&(app_id.to_string(), size))
+            .and_then(|icon| icon.as_ref())
+    }
+
+    /// Bumped on every `clear`, so renderers know to drop their textures
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Forget all cached icons and desktop entries
+    pub fn clear(&mut self) {
+        self.discovery = None;
+        self.cache.clear();
+        self.generation += 1;
+    }
+
+    fn load(&mut self, app_id: &str, size: u32) -> Option<CachedIcon> {
+        if self.icon_dirs.is_empty() {
+            self.icon_dirs = default_icon_dirs();
+        }
+        let discovery = self.discovery.get_or_insert_with(AppDiscovery::new);
+
+        let path = discovery.icon_for_app_id(app_id, &self.icon_dirs)?;
+        let (data, width, height) = load_wallpaper_image(&path)?;
+        let (data, width, height) = scale_wallpaper(&data, width, height, size, size, ScaleMode::Stretch);
+
+        debug!("Loaded icon for {} from {:?}", app_id, path);
+        Some(CachedIcon { data, width, height })
+    }
+}
@@ -1,7 +1,17 @@
+pub mod clip;
+pub mod damage;
 pub mod decorations;
+pub mod icons;
+pub mod overview;
+pub mod ui;
 pub mod wallpaper;
 
-pub use decorations::{create_border_elements, BorderRenderElement};
+pub use clip::crop_to_output;
+pub use damage::{CommitDamage, SurfaceDamage};
+pub use decorations::{create_border_elements, create_shadow_elements, BorderRenderElement, SHADOW_RADIUS};
+pub use icons::{IconCache, ICON_PADDING};
+pub use overview::overview_rects;
+pub use ui::{centered_rect, Panel};
 pub use wallpaper::{
     load_cached_wallpaper, make_wallpaper_key,
     ScaleMode, WallpaperCache,
@@ -10,13 +20,16 @@ pub use wallpaper::{
 use smithay::backend::renderer::element::solid::SolidColorRenderElement;
 use smithay::backend::renderer::element::surface::WaylandSurfaceRenderElement;
 use smithay::backend::renderer::element::texture::TextureRenderElement;
+use smithay::backend::renderer::element::utils::CropRenderElement;
 use smithay::backend::renderer::{ImportDmaWl, ImportMemWl, Renderer};
 
 // Create a combined render element type for DRM output that can hold
-// window surfaces, border elements (solid colors), and wallpaper textures
+// window surfaces (cropped to the output), border elements (solid colors),
+// and wallpaper textures
 smithay::backend::renderer::element::render_elements! {
     pub OutputRenderElements<R> where R: Renderer + ImportMemWl + ImportDmaWl;
     Surface=WaylandSurfaceRenderElement<R>,
+    CroppedSurface=CropRenderElement<WaylandSurfaceRenderElement<R>>,
     Solid=SolidColorRenderElement,
     Texture=TextureRenderElement<R::TextureId>,
 }
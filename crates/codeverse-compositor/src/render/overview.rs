@@ -0,0 +1,102 @@
+use crate::compositor::CodeVerseCompositor;
+use codeverse_config::NordColor;
+use codeverse_window::{scale_rect_into, Rectangle};
+
+/// Size of the pips used to show the workspace number in a cell
+const PIP_SIZE: u32 = 8;
+
+/// Build the colored rectangles for the workspace overview, ordered back
+/// to front: a backdrop, then per workspace a cell, a miniature box for
+/// each window, number pips and a highlight around the selected cell.
+pub fn overview_rects<BackendData: 'static>(
+    compositor: &CodeVerseCompositor<BackendData>,
+) -> Vec<(Rectangle, NordColor)> {
+    let theme = &compositor.theme;
+    let screen_rect = compositor
+        .last_screen_geometry
+        .unwrap_or_else(|| Rectangle::new(0, 0, 1920, 1080));
+
+    let mut rects = vec![(screen_rect, theme.colors.nord0)];
+
+    let Some(ref manager) = compositor.workspace_manager else {
+        return rects;
+    };
+
+    for (workspace_num, cell) in compositor.overview_cells() {
+        let cell_color = if workspace_num == manager.active_workspace_num() {
+            theme.colors.nord2
+        } else {
+            theme.colors.nord1
+        };
+        rects.push((cell, cell_color));
+
+        // Miniature of the workspace layout using each window's last geometry
+        if let Some(workspace_id) = manager.get_workspace(workspace_num) {
+            for window_id in workspace_windows(compositor, workspace_id) {
+                if let Some(container) = compositor.window_tree.get(window_id) {
+                    let mini = scale_rect_into(container.geometry, screen_rect, cell);
+                    rects.push((inset(mini, 2), theme.colors.nord3));
+                }
+            }
+        }
+
+        // One pip per workspace number (10 pips for workspace 10)
+        for i in 0..workspace_num as u32 {
+            let pip = Rectangle::new(
+                cell.x + (PIP_SIZE + (PIP_SIZE + 4) * i) as i32,
+                cell.y + PIP_SIZE as i32,
+                PIP_SIZE,
+                PIP_SIZE,
+            );
+            rects.push((pip, theme.colors.nord6));
+        }
+
+        if workspace_num == compositor.overview_selected {
+            rects.extend(outline(cell, 4, theme.colors.nord8));
+        }
+    }
+
+    rects
+}
+
+/// Collect all windows below a workspace (tiled and floating)
+fn workspace_windows<BackendData: 'static>(
+    compositor: &CodeVerseCompositor<BackendData>,
+    workspace_id: codeverse_window::NodeId,
+) -> Vec<codeverse_window::NodeId> {
+    let mut windows = Vec::new();
+    let mut pending = vec![workspace_id];
+
+    while let Some(node_id) = pending.pop() {
+        if let Some(node) = compositor.window_tree.get(node_id) {
+            if node.container_type == codeverse_window::ContainerType::Window {
+                windows.push(node_id);
+            } else {
+                pending.extend(node.children.iter().rev());
+            }
+        }
+    }
+
+    windows
+}
+
+/// Shrink a rectangle by `amount` pixels on every side
+fn inset(rect: Rectangle, amount: u32) -> Rectangle {
+    Rectangle::new(
+        rect.x + amount as i32,
+        rect.y + amount as i32,
+        rect.width.saturating_sub(amount * 2),
+        rect.height.saturating_sub(amount * 2),
+    )
+}
+
+/// Four rectangles forming an outline just outside `rect`
+fn outline(rect: Rectangle, width: u32, color: NordColor) -> Vec<(Rectangle, NordColor)> {
+    let w = width as i32;
+    vec![
+        (Rectangle::new(rect.x - w, rect.y - w, rect.width + width * 2, width), color),
+        (Rectangle::new(rect.x - w, rect.y + rect.height as i32, rect.width + width * 2, width), color),
+        (Rectangle::new(rect.x - w, rect.y, width, rect.height), color),
+        (Rectangle::new(rect.x + rect.width as i32, rect.y, width, rect.height), color),
+    ]
+}
@@ -0,0 +1,121 @@
+//! Building blocks for compositor-drawn UI
+//!
+//! The workspace overview, launcher, bar, tooltips and OSDs all draw flat
+//! panels; building them through [`Panel`] keeps padding, colors and
+//! opacity consistent between features and backends.
+
+use codeverse_config::NordColor;
+use codeverse_window::Rectangle;
+use smithay::{
+    backend::renderer::{
+        element::{solid::SolidColorRenderElement, Id, Kind},
+        Color32F,
+    },
+    utils::{Physical, Rectangle as SmithayRectangle},
+};
+
+/// A `width` x `height` rectangle centered in `area` (clamped to fit)
+pub fn centered_rect(area: Rectangle, width: u32, height: u32) -> Rectangle {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rectangle::new(
+        area.x + ((area.width - width) / 2) as i32,
+        area.y + ((area.height - height) / 2) as i32,
+        width,
+        height,
+    )
+}
+
+/// A solid background panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Panel {
+    /// Outer bounds of the panel
+    pub rect: Rectangle,
+    /// Space between the edge and the content
+    pub padding: u32,
+    /// Background color
+    pub background: NordColor,
+    /// Opacity (0.0 - 1.0) applied on top of the color's alpha
+    pub opacity: f32,
+}
+
+impl Panel {
+    /// Create an opaque, unpadded panel
+    pub fn new(rect: Rectangle, background: NordColor) -> Self {
+        Self {
+            rect,
+            padding: 0,
+            background,
+            opacity: 1.0,
+        }
+    }
+
+    /// Set the padding around the content
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Set the opacity (clamped to 0.0 - 1.0)
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Area left for content inside the padding (empty if the padding
+    /// doesn't fit)
+    pub fn content_rect(&self) -> Rectangle {
+        let padding = self.padding.min(self.rect.width / 2).min(self.rect.height / 2);
+
+        Rectangle::new(
+            self.rect.x + padding as i32,
+            self.rect.y + padding as i32,
+            self.rect.width - padding * 2,
+            self.rect.height - padding * 2,
+        )
+    }
+
+    /// Background color with the opacity applied (premultiplied)
+    pub fn color(&self) -> Color32F {
+        let [r, g, b, a] = self.background.to_f32_array();
+        let alpha = a * self.opacity;
+        Color32F::new(r * alpha, g * alpha, b * alpha, alpha)
+    }
+
+    /// Panel bounds in output coordinates
+    pub fn physical_rect(&self) -> SmithayRectangle<i32, Physical> {
+        self.rect.into()
+    }
+
+    /// Render element drawing the panel background
+    pub fn render_element(&self) -> SolidColorRenderElement {
+        SolidColorRenderElement::new(Id::new(), self.physical_rect(), 1, self.color(), Kind::Unspecified)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panel_content_rect() {
+        let color = NordColor::rgb(0x3b, 0x42, 0x52);
+        let panel = Panel::new(Rectangle::new(100, 50, 400, 200), color);
+        assert_eq!(panel.content_rect(), panel.rect);
+
+        let padded = panel.with_padding(12);
+        assert_eq!(padded.content_rect(), Rectangle::new(112, 62, 376, 176));
+
+        // Padding larger than the panel leaves no room for content, and the
+        // content stays inside the panel
+        let cramped = Panel::new(Rectangle::new(0, 0, 20, 100), color).with_padding(15);
+        assert_eq!(cramped.content_rect(), Rectangle::new(10, 10, 0, 80));
+    }
+
+    #[test]
+    fn test_centered_rect() {
+        let screen = Rectangle::new(0, 0, 1920, 1080);
+        assert_eq!(centered_rect(screen, 400, 80), Rectangle::new(760, 500, 400, 80));
+        assert_eq!(centered_rect(Rectangle::new(10, 10, 300, 50), 400, 80), Rectangle::new(10, 10, 300, 50));
+    }
+}
@@ -132,6 +132,11 @@ pub fn scale_wallpaper(
 ) -> (Vec<u8>, u32, u32) {
     use image::{ImageBuffer, Rgba, imageops::FilterType};
 
+    // A zero-size source or target (e.g. an output with a bogus mode) has nothing to scale
+    if src_width == 0 || src_height == 0 || target_width == 0 || target_height == 0 {
+        return (Vec::new(), 0, 0);
+    }
+
     let src_image: ImageBuffer<Rgba<u8>, _> =
         ImageBuffer::from_raw(src_width, src_height, data.to_vec())
             .expect("Invalid image data");
@@ -253,6 +258,10 @@ pub fn load_cached_wallpaper(
     screen_height: u32,
     mode: ScaleMode,
 ) -> bool {
+    if screen_width == 0 || screen_height == 0 {
+        return false;
+    }
+
     let key = WallpaperCacheKey {
         path: path.to_string(),
         screen_width,
@@ -340,4 +349,23 @@ mod tests {
         cache.clear();
         assert!(!cache.contains(&key));
     }
+
+    #[cfg(feature = "wallpaper")]
+    #[test]
+    fn test_scale_wallpaper_zero_target() {
+        let data = vec![0xff; 4 * 4 * 4];
+        let modes = [ScaleMode::Fill, ScaleMode::Fit, ScaleMode::Stretch, ScaleMode::Center, ScaleMode::Tile];
+
+        for mode in modes {
+            assert_eq!(scale_wallpaper(&data, 4, 4, 0, 1080, mode), (Vec::new(), 0, 0));
+            assert_eq!(scale_wallpaper(&data, 4, 4, 1920, 0, mode), (Vec::new(), 0, 0));
+            assert_eq!(scale_wallpaper(&[], 0, 0, 1920, 1080, mode), (Vec::new(), 0, 0));
+        }
+    }
+
+    #[test]
+    fn test_load_cached_wallpaper_zero_screen() {
+        let mut cache = WallpaperCache::new();
+        assert!(!load_cached_wallpaper(&mut cache, "/test/path.png", 0, 0, ScaleMode::Fill));
+    }
 }
@@ -0,0 +1,29 @@
+//! Fixtures shared by the compositor's unit tests
+
+use crate::compositor::CodeVerseCompositor;
+use codeverse_window::{Container, ContainerType, NodeId};
+use smithay::reexports::{calloop::EventLoop, wayland_server::Display};
+
+/// Create a compositor without a backend or outputs
+pub fn create_test_compositor() -> CodeVerseCompositor<()> {
+    let event_loop: EventLoop<CodeVerseCompositor<()>> = EventLoop::try_new().unwrap();
+    let loop_handle = event_loop.handle();
+    let mut display: Display<CodeVerseCompositor<()>> = Display::new().unwrap();
+
+    CodeVerseCompositor::new(&mut display, loop_handle, ())
+}
+
+/// Add an empty window to workspace `workspace_num` of a compositor whose
+/// workspace manager is initialized
+pub fn add_window(compositor: &mut CodeVerseCompositor<()>, workspace_num: usize) -> NodeId {
+    let workspace_id = compositor
+        .workspace_manager
+        .as_ref()
+        .and_then(|manager| manager.get_workspace(workspace_num))
+        .expect("workspace manager not initialized");
+    let window_id = compositor
+        .window_tree
+        .insert(Container::new(NodeId::default(), ContainerType::Window));
+    compositor.window_tree.add_child(workspace_id, window_id).unwrap();
+    window_id
+}
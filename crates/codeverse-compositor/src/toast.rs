@@ -0,0 +1,106 @@
+use codeverse_window::Rectangle;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long an error toast stays on screen
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// Most toasts shown at once; a new one pushes out the oldest
+pub const MAX_TOASTS: usize = 3;
+
+/// Size of a toast and the space between stacked toasts and the screen edge
+pub const TOAST_WIDTH: u32 = 480;
+pub const TOAST_HEIGHT: u32 = 48;
+pub const TOAST_MARGIN: u32 = 16;
+
+/// An error shown on screen for a few seconds. Only an indicator box is
+/// drawn; `message` is kept for the log and for a future text path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub message: String,
+    shown_at: Instant,
+}
+
+/// Queue of user-facing errors (`general.error_toasts`), e.g. a launch
+/// that failed, newest last
+#[derive(Debug, Default)]
+pub struct ErrorToasts {
+    toasts: VecDeque<Toast>,
+}
+
+impl ErrorToasts {
+    /// Show `message` until [`TOAST_DURATION`] has passed
+    pub fn push(&mut self, message: impl Into<String>, now: Instant) {
+        self.expire(now);
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast {
+            message: message.into(),
+            shown_at: now,
+        });
+    }
+
+    /// Drop toasts that have been shown long enough; returns whether any were
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let before = self.toasts.len();
+        self.toasts
+            .retain(|toast| now.saturating_duration_since(toast.shown_at) < TOAST_DURATION);
+        self.toasts.len() != before
+    }
+
+    /// Toasts on screen, oldest first
+    pub fn visible(&self) -> impl Iterator<Item = &Toast> {
+        self.toasts.iter()
+    }
+}
+
+/// Rectangles of `count` toasts stacked down from the top center of `screen`
+pub fn toast_rects(screen: Rectangle, count: usize) -> Vec<Rectangle> {
+    let width = TOAST_WIDTH.min(screen.width);
+    let x = screen.x + ((screen.width - width) / 2) as i32;
+
+    (0..count)
+        .map(|i| {
+            let y = screen.y + (TOAST_MARGIN + i as u32 * (TOAST_HEIGHT + TOAST_MARGIN)) as i32;
+            Rectangle::new(x, y, width, TOAST_HEIGHT)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_toast_queue() {
+        let start = Instant::now();
+        let mut toasts = ErrorToasts::default();
+        assert_eq!(toasts.visible().count(), 0);
+
+        for i in 0..=MAX_TOASTS {
+            toasts.push(format!("error {}", i), start + Duration::from_secs(i as u64));
+        }
+
+        // The oldest toast made room for the newest
+        let messages: Vec<&str> = toasts.visible().map(|toast| toast.message.as_str()).collect();
+        assert_eq!(messages, ["error 1", "error 2", "error 3"]);
+
+        // Each one leaves after its own duration
+        assert!(toasts.expire(start + Duration::from_secs(1) + TOAST_DURATION));
+        assert_eq!(toasts.visible().count(), 2);
+        assert!(!toasts.expire(start + Duration::from_secs(1) + TOAST_DURATION));
+        toasts.expire(start + Duration::from_secs(10));
+        assert_eq!(toasts.visible().count(), 0);
+    }
+
+    #[test]
+    fn test_toast_rects() {
+        let rects = toast_rects(Rectangle::new(0, 0, 1920, 1080), 2);
+        assert_eq!(rects[0], Rectangle::new(720, 16, 480, 48));
+        assert_eq!(rects[1], Rectangle::new(720, 80, 480, 48));
+
+        // Narrow screens get narrower toasts
+        assert_eq!(toast_rects(Rectangle::new(0, 0, 320, 480), 1)[0].width, 320);
+    }
+}
@@ -1,9 +1,11 @@
-use crate::keybindings::KeybindingsConfig;
-use crate::theme::NordTheme;
+use crate::keybindings::{Action, KeybindingsConfig};
+use crate::rules::WindowRule;
+use crate::theme::{NordColor, NordTheme};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
 /// Main configuration struct
@@ -27,8 +29,23 @@ pub struct Config {
     #[serde(default)]
     pub wallpaper: WallpaperConfig,
 
+    #[serde(default)]
+    pub margins: MarginsConfig,
+
+    #[serde(default)]
+    pub input: InputConfig,
+
+    #[serde(default)]
+    pub taskbar: TaskbarConfig,
+
+    #[serde(default)]
+    pub logging: LoggingConfig,
+
     #[serde(default)]
     pub outputs: Vec<OutputConfig>,
+
+    #[serde(default)]
+    pub window_rules: Vec<WindowRule>,
 }
 
 /// General compositor settings
@@ -42,6 +59,14 @@ pub struct GeneralConfig {
     #[serde(default = "default_gap_width")]
     pub gap_width: u32,
 
+    /// Shrink gaps as more windows are tiled (gap_width / window count)
+    #[serde(default)]
+    pub adaptive_gaps: bool,
+
+    /// Smallest gap used when adaptive gaps are enabled
+    #[serde(default = "default_min_gap_width")]
+    pub min_gap_width: u32,
+
     /// Default layout mode for new workspaces
     #[serde(default = "default_layout")]
     pub default_layout: String,
@@ -54,13 +79,99 @@ pub struct GeneralConfig {
     #[serde(default = "default_true")]
     pub borders_enabled: bool,
 
-    /// Enable window shadows (not yet implemented)
+    /// Draw a soft shadow around each window
     #[serde(default)]
     pub shadows_enabled: bool,
 
     /// Title bar height for floating windows (in pixels)
     #[serde(default = "default_title_bar_height")]
     pub title_bar_height: u32,
+
+    /// Action run when Super is tapped (pressed and released alone).
+    /// Disabled when unset.
+    #[serde(default)]
+    pub tap_super_action: Option<Action>,
+
+    /// Shell command run (non-blocking) when the active workspace changes.
+    /// Receives the workspace number and name as $1 and $2.
+    #[serde(default)]
+    pub on_workspace_change: Option<String>,
+
+    /// Shell commands started once the compositor is up (bars, daemons, ...)
+    #[serde(default)]
+    pub autostart: Vec<String>,
+
+    /// Wait this long after the Wayland socket is listening before running
+    /// `autostart`, so clients don't race the compositor's startup
+    #[serde(default = "default_autostart_delay_ms")]
+    pub autostart_delay_ms: u64,
+
+    /// Stop rendering and frame callbacks while every output is disconnected.
+    /// Windows are kept either way; when false, clients keep running offscreen.
+    #[serde(default = "default_true")]
+    pub pause_without_outputs: bool,
+
+    /// Keep laying out and serving clients with no output at all (headless
+    /// automation), against a virtual output of `virtual_output_size`.
+    /// Overrides `pause_without_outputs`.
+    #[serde(default)]
+    pub allow_no_output: bool,
+
+    /// Size of the virtual output used while no output is connected
+    #[serde(default = "default_virtual_output_size")]
+    pub virtual_output_size: (u32, u32),
+
+    /// Re-tile a dragged floating window when it is dropped over a tiled
+    /// window, inserting it next to that window
+    #[serde(default)]
+    pub drop_floating_onto_tile: bool,
+
+    /// Ignore a repeated Quit/CloseWindow within this many milliseconds
+    /// unless the key was released in between (0 disables the guard)
+    #[serde(default = "default_repeat_guard_ms")]
+    pub repeat_guard_ms: u64,
+
+    /// Quit only when the quit binding is pressed twice within a few seconds
+    #[serde(default)]
+    pub confirm_quit: bool,
+
+    /// Flag errors the user should know about (a failed app launch, no
+    /// terminal found) with a toast for a few seconds. The toast is only an
+    /// indicator; the message is in the log.
+    #[serde(default = "default_true")]
+    pub error_toasts: bool,
+
+    /// Closing a window that has a transient dialog open (e.g. "save
+    /// changes?") focuses the dialog instead
+    #[serde(default)]
+    pub close_respects_modals: bool,
+
+    /// Enable animations
+    #[serde(default)]
+    pub animations: bool,
+
+    /// Animation length in milliseconds
+    #[serde(default = "default_animation_duration")]
+    pub animation_duration: u32,
+
+    /// Workspace switch animation: "slide" or "none" (needs `animations`)
+    #[serde(default = "default_workspace_switch_animation")]
+    pub workspace_switch_animation: String,
+
+    /// New windows float when a floating window has focus (otherwise new
+    /// windows always open tiled)
+    #[serde(default)]
+    pub new_windows_inherit_floating: bool,
+
+    /// New floating windows take focus; when false they're raised on top
+    /// but focus stays where it was (tiled windows always take focus)
+    #[serde(default = "default_true")]
+    pub focus_new_windows: bool,
+
+    /// Close and remove windows that haven't committed a buffer this many
+    /// milliseconds after their first configure (0 keeps them forever)
+    #[serde(default = "default_unmapped_window_timeout_ms")]
+    pub unmapped_window_timeout_ms: u64,
 }
 
 impl Default for GeneralConfig {
@@ -68,15 +179,47 @@ impl Default for GeneralConfig {
         Self {
             border_width: default_border_width(),
             gap_width: default_gap_width(),
+            adaptive_gaps: false,
+            min_gap_width: default_min_gap_width(),
             default_layout: default_layout(),
             focus_follows_mouse: false,
             borders_enabled: true,
             shadows_enabled: false,
             title_bar_height: default_title_bar_height(),
+            tap_super_action: None,
+            on_workspace_change: None,
+            autostart: vec![],
+            autostart_delay_ms: default_autostart_delay_ms(),
+            pause_without_outputs: true,
+            allow_no_output: false,
+            virtual_output_size: default_virtual_output_size(),
+            drop_floating_onto_tile: false,
+            repeat_guard_ms: default_repeat_guard_ms(),
+            confirm_quit: false,
+            error_toasts: true,
+            close_respects_modals: false,
+            animations: false,
+            animation_duration: default_animation_duration(),
+            workspace_switch_animation: default_workspace_switch_animation(),
+            new_windows_inherit_floating: false,
+            focus_new_windows: true,
+            unmapped_window_timeout_ms: default_unmapped_window_timeout_ms(),
         }
     }
 }
 
+impl GeneralConfig {
+    /// Get the gap to use for a workspace with `window_count` tiled windows
+    pub fn effective_gap_width(&self, window_count: usize) -> u32 {
+        if !self.adaptive_gaps || window_count <= 1 {
+            return self.gap_width;
+        }
+
+        let floor = self.min_gap_width.min(self.gap_width);
+        (self.gap_width / window_count as u32).max(floor)
+    }
+}
+
 fn default_true() -> bool {
     true
 }
@@ -85,6 +228,30 @@ fn default_title_bar_height() -> u32 {
     30
 }
 
+fn default_virtual_output_size() -> (u32, u32) {
+    (1920, 1080)
+}
+
+fn default_autostart_delay_ms() -> u64 {
+    200
+}
+
+fn default_repeat_guard_ms() -> u64 {
+    500
+}
+
+fn default_unmapped_window_timeout_ms() -> u64 {
+    10_000
+}
+
+fn default_animation_duration() -> u32 {
+    200
+}
+
+fn default_workspace_switch_animation() -> String {
+    "slide".to_string()
+}
+
 fn default_border_width() -> u32 {
     2
 }
@@ -93,6 +260,10 @@ fn default_gap_width() -> u32 {
     10
 }
 
+fn default_min_gap_width() -> u32 {
+    2
+}
+
 fn default_layout() -> String {
     "splith".to_string()
 }
@@ -112,6 +283,16 @@ pub struct ThemeConfig {
 
     /// Custom background color (hex format: #RRGGBB)
     pub background: Option<String>,
+
+    /// Background of tabbed group bars (hex format: #RRGGBB, default nord1)
+    pub group_background: Option<String>,
+
+    /// Width of a second border ring drawn inside the window border (0 disables)
+    #[serde(default)]
+    pub inner_border_width: u32,
+
+    /// Color of the inner border ring (hex format: #RRGGBB, default nord8)
+    pub inner_border_color: Option<String>,
 }
 
 impl Default for ThemeConfig {
@@ -121,6 +302,9 @@ impl Default for ThemeConfig {
             focused_border: None,
             unfocused_border: None,
             background: None,
+            group_background: None,
+            inner_border_width: 0,
+            inner_border_color: None,
         }
     }
 }
@@ -139,6 +323,35 @@ pub struct WorkspacesConfig {
     /// Workspace names
     #[serde(default)]
     pub names: Vec<String>,
+
+    /// Workspace active on startup (1-based, must not exceed `count`)
+    #[serde(default = "default_startup_workspace")]
+    pub startup: usize,
+
+    /// Switching to the workspace that is already active goes back to the
+    /// previously active workspace instead
+    #[serde(default)]
+    pub number_key_toggles_back: bool,
+
+    /// Per-workspace gap/border overrides
+    #[serde(default)]
+    pub per_workspace: Vec<WorkspaceOverrides>,
+
+    /// How switching applies across outputs: "per_output" (only the
+    /// focused output switches) or "global" (all outputs switch together)
+    #[serde(default = "default_workspaces_mode")]
+    pub mode: String,
+}
+
+/// Settings overridden for a single workspace (unset values use the global ones)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceOverrides {
+    /// Workspace number (1-based)
+    pub workspace: usize,
+    /// Gap width in pixels
+    pub gap: Option<u32>,
+    /// Border width in pixels
+    pub border_width: Option<u32>,
 }
 
 impl Default for WorkspacesConfig {
@@ -146,14 +359,84 @@ impl Default for WorkspacesConfig {
         Self {
             count: default_workspace_count(),
             names: vec![],
+            startup: default_startup_workspace(),
+            number_key_toggles_back: false,
+            per_workspace: vec![],
+            mode: default_workspaces_mode(),
+        }
+    }
+}
+
+impl WorkspacesConfig {
+    /// Name of workspace `num` (1-based): the matching entry of `names`,
+    /// or the number itself when it has no (or an empty) name
+    pub fn name_for(&self, num: usize) -> String {
+        num.checked_sub(1)
+            .filter(|_| num <= self.count)
+            .and_then(|index| self.names.get(index))
+            .filter(|name| !name.is_empty())
+            .cloned()
+            .unwrap_or_else(|| num.to_string())
+    }
+
+    /// Whether a workspace switch moves all outputs at once
+    pub fn is_global(&self) -> bool {
+        self.mode == "global"
+    }
+
+    /// Warn about names past `count`, which no workspace will use
+    pub fn check_names(&self) {
+        if self.names.len() > self.count {
+            warn!(
+                "{} workspace names given for {} workspaces, ignoring {:?}",
+                self.names.len(),
+                self.count,
+                &self.names[self.count..]
+            );
+        }
+    }
+
+    /// Get the validated startup workspace number.
+    /// Falls back to workspace 1 if `startup` is outside `1..=count`.
+    pub fn startup_workspace(&self) -> usize {
+        if self.startup >= 1 && self.startup <= self.count {
+            self.startup
+        } else {
+            warn!(
+                "Startup workspace {} is out of range (1-{}), using 1",
+                self.startup, self.count
+            );
+            1
         }
     }
+
+    fn overrides(&self, workspace_num: usize) -> Option<&WorkspaceOverrides> {
+        self.per_workspace.iter().find(|o| o.workspace == workspace_num)
+    }
+
+    /// Gap width override for a workspace
+    pub fn gap_for(&self, workspace_num: usize) -> Option<u32> {
+        self.overrides(workspace_num).and_then(|o| o.gap)
+    }
+
+    /// Border width override for a workspace
+    pub fn border_width_for(&self, workspace_num: usize) -> Option<u32> {
+        self.overrides(workspace_num).and_then(|o| o.border_width)
+    }
 }
 
 fn default_workspace_count() -> usize {
     10
 }
 
+fn default_startup_workspace() -> usize {
+    1
+}
+
+fn default_workspaces_mode() -> String {
+    "per_output".to_string()
+}
+
 /// Launcher configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LauncherConfig {
@@ -168,6 +451,16 @@ pub struct LauncherConfig {
     /// Show descriptions in launcher
     #[serde(default = "default_show_descriptions")]
     pub show_descriptions: bool,
+
+    /// Number of most recently/frequently launched apps listed first
+    /// when the query is empty (0 disables)
+    #[serde(default = "default_recent_count")]
+    pub recent_count: usize,
+
+    /// Close the launcher after this many milliseconds without input
+    /// (disabled when unset)
+    #[serde(default)]
+    pub auto_close_ms: Option<u64>,
 }
 
 impl Default for LauncherConfig {
@@ -176,6 +469,20 @@ impl Default for LauncherConfig {
             additional_paths: vec![],
             max_results: default_max_results(),
             show_descriptions: default_show_descriptions(),
+            recent_count: default_recent_count(),
+            auto_close_ms: None,
+        }
+    }
+}
+
+impl LauncherConfig {
+    /// Whether an open launcher last used at `last_activity` should close at `now`
+    pub fn should_auto_close(&self, last_activity: Instant, now: Instant) -> bool {
+        match self.auto_close_ms {
+            Some(timeout_ms) => {
+                now.saturating_duration_since(last_activity) >= Duration::from_millis(timeout_ms)
+            }
+            None => false,
         }
     }
 }
@@ -188,6 +495,10 @@ fn default_show_descriptions() -> bool {
     true
 }
 
+fn default_recent_count() -> usize {
+    5
+}
+
 /// Wallpaper configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WallpaperConfig {
@@ -228,6 +539,115 @@ fn default_wallpaper_mode() -> String {
     "fill".to_string()
 }
 
+/// Fixed space kept free on each screen edge (applied before gaps)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarginsConfig {
+    #[serde(default)]
+    pub top: u32,
+
+    #[serde(default)]
+    pub bottom: u32,
+
+    #[serde(default)]
+    pub left: u32,
+
+    #[serde(default)]
+    pub right: u32,
+}
+
+/// Keyboard and pointer behaviour
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputConfig {
+    /// Hide the cursor on key presses until the pointer moves again
+    #[serde(default)]
+    pub hide_cursor_while_typing: bool,
+}
+
+/// Strip of buttons for all windows, drawn by the compositor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskbarConfig {
+    /// Show the taskbar
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Screen edge it sits on: "top" or "bottom"
+    #[serde(default = "default_taskbar_edge")]
+    pub edge: String,
+
+    /// Height in pixels (reserved from the work area)
+    #[serde(default = "default_taskbar_height")]
+    pub height: u32,
+}
+
+impl Default for TaskbarConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            edge: default_taskbar_edge(),
+            height: default_taskbar_height(),
+        }
+    }
+}
+
+fn default_taskbar_edge() -> String {
+    "bottom".to_string()
+}
+
+fn default_taskbar_height() -> u32 {
+    32
+}
+
+/// Logging configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingConfig {
+    /// Write logs to ~/.cache/codeverse-compositor/compositor.log
+    /// (the DRM backend always does, since stdout is the console there)
+    #[serde(default)]
+    pub file: bool,
+
+    /// Write logs to stdout
+    #[serde(default = "default_true")]
+    pub stdout: bool,
+
+    /// Log filter ("error", "warn", "info", "debug", "trace" or an
+    /// EnvFilter directive); RUST_LOG takes precedence when set
+    #[serde(default = "default_log_level")]
+    pub level: String,
+
+    /// Rotate the log file once it would grow past this many bytes
+    #[serde(default = "default_log_max_size")]
+    pub max_size: u64,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            file: false,
+            stdout: true,
+            level: default_log_level(),
+            max_size: default_log_max_size(),
+        }
+    }
+}
+
+impl LoggingConfig {
+    /// Get the log file path
+    pub fn log_path() -> Result<PathBuf> {
+        let cache_dir = dirs::cache_dir()
+            .context("Could not determine cache directory")?;
+
+        Ok(cache_dir.join("codeverse-compositor").join("compositor.log"))
+    }
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_log_max_size() -> u64 {
+    10 * 1024 * 1024
+}
+
 /// Output/Display configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OutputConfig {
@@ -246,6 +666,9 @@ pub struct OutputConfig {
 
     /// Position (x, y) for multi-monitor setups
     pub position: Option<(i32, i32)>,
+
+    /// Show another output's content instead (e.g. "eDP-1"), scaled to fit
+    pub mirror_of: Option<String>,
 }
 
 fn default_scale() -> f64 {
@@ -261,7 +684,12 @@ impl Default for Config {
             workspaces: WorkspacesConfig::default(),
             launcher: LauncherConfig::default(),
             wallpaper: WallpaperConfig::default(),
+            margins: MarginsConfig::default(),
+            input: InputConfig::default(),
+            taskbar: TaskbarConfig::default(),
+            logging: LoggingConfig::default(),
             outputs: vec![],
+            window_rules: vec![],
         }
     }
 }
@@ -292,6 +720,7 @@ impl Config {
 
         let config: Config = toml::from_str(&contents)
             .with_context(|| format!("Failed to parse config file: {:?}", path))?;
+        config.workspaces.check_names();
 
         info!("Successfully loaded config from {:?}", path);
         Ok(config)
@@ -341,11 +770,72 @@ impl Config {
         Ok(())
     }
 
+    /// Border width from the first matching window rule that sets one
+    pub fn rule_border_width(&self, app_id: Option<&str>, title: Option<&str>) -> Option<u32> {
+        self.window_rules
+            .iter()
+            .filter(|rule| rule.matches(app_id, title))
+            .find_map(|rule| rule.border_width)
+    }
+
+    /// Group background from the first matching window rule that sets a valid one
+    pub fn rule_group_background(&self, app_id: Option<&str>, title: Option<&str>) -> Option<NordColor> {
+        self.window_rules
+            .iter()
+            .filter(|rule| rule.matches(app_id, title))
+            .find_map(|rule| rule.group_background.as_deref().and_then(NordColor::from_hex))
+    }
+
+    /// Opacity from the first matching window rule that sets one (clamped to 0.0 - 1.0)
+    pub fn rule_opacity(&self, app_id: Option<&str>, title: Option<&str>) -> Option<f32> {
+        self.window_rules
+            .iter()
+            .filter(|rule| rule.matches(app_id, title))
+            .find_map(|rule| rule.opacity)
+            .map(|opacity| opacity.clamp(0.0, 1.0))
+    }
+
+    /// Resize increment from the first matching window rule that sets one
+    pub fn rule_resize_increment(&self, app_id: Option<&str>, title: Option<&str>) -> Option<(u32, u32)> {
+        self.window_rules
+            .iter()
+            .filter(|rule| rule.matches(app_id, title))
+            .find_map(|rule| rule.resize_increment)
+    }
+
+    /// Background for tabbed group bars without their own color
+    pub fn group_background(&self) -> NordColor {
+        self.theme
+            .group_background
+            .as_deref()
+            .and_then(NordColor::from_hex)
+            .unwrap_or_else(|| self.get_theme().colors.nord1)
+    }
+
+    /// Width and color of the inner border ring, if enabled
+    pub fn inner_border(&self) -> Option<(u32, NordColor)> {
+        if self.theme.inner_border_width == 0 {
+            return None;
+        }
+
+        let color = self
+            .theme
+            .inner_border_color
+            .as_deref()
+            .and_then(NordColor::from_hex)
+            .unwrap_or_else(|| self.get_theme().accent());
+        Some((self.theme.inner_border_width, color))
+    }
+
     /// Get the theme based on configuration
     pub fn get_theme(&self) -> NordTheme {
         // For now, always use Nord theme
         // In the future, we can support custom themes here
-        NordTheme::default()
+        NordTheme {
+            animation_duration: self.general.animation_duration,
+            shadow_enabled: self.general.shadows_enabled,
+            ..NordTheme::default()
+        }
     }
 }
 
@@ -406,4 +896,163 @@ border_width = 4
         assert_eq!(config.general.gap_width, 10); // Default
         assert_eq!(config.workspaces.count, 10); // Default
     }
+
+    #[test]
+    fn test_adaptive_gaps() {
+        let mut general = GeneralConfig {
+            gap_width: 12,
+            min_gap_width: 3,
+            ..GeneralConfig::default()
+        };
+
+        // Disabled: gap is constant
+        assert_eq!(general.effective_gap_width(4), 12);
+
+        general.adaptive_gaps = true;
+        assert_eq!(general.effective_gap_width(1), 12);
+        assert_eq!(general.effective_gap_width(2), 6);
+        assert_eq!(general.effective_gap_width(3), 4);
+
+        // Never shrinks below the floor
+        assert_eq!(general.effective_gap_width(6), 3);
+        assert_eq!(general.effective_gap_width(20), 3);
+    }
+
+    #[test]
+    fn test_tap_super_action() {
+        assert_eq!(Config::default().general.tap_super_action, None);
+
+        let toml_str = r#"
+[general]
+tap_super_action = "toggle_launcher"
+"#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.general.tap_super_action, Some(Action::ToggleLauncher));
+    }
+
+    #[test]
+    fn test_launcher_auto_close() {
+        let start = Instant::now();
+        let mut launcher = LauncherConfig::default();
+
+        // Disabled by default
+        assert!(!launcher.should_auto_close(start, start + Duration::from_secs(3600)));
+
+        launcher.auto_close_ms = Some(5000);
+        assert!(!launcher.should_auto_close(start, start + Duration::from_millis(4999)));
+        assert!(launcher.should_auto_close(start, start + Duration::from_millis(5000)));
+
+        // Activity after "now" (clock skew between checks) never closes
+        assert!(!launcher.should_auto_close(start + Duration::from_secs(1), start));
+    }
+
+    #[test]
+    fn test_startup_workspace_validation() {
+        let toml_str = r#"
+[workspaces]
+count = 5
+startup = 3
+"#;
+
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.workspaces.startup_workspace(), 3);
+
+        // Out of range falls back to workspace 1
+        config.workspaces.startup = 6;
+        assert_eq!(config.workspaces.startup_workspace(), 1);
+        config.workspaces.startup = 0;
+        assert_eq!(config.workspaces.startup_workspace(), 1);
+    }
+
+    #[test]
+    fn test_margins_parse() {
+        let toml_str = r#"
+[margins]
+top = 40
+left = 8
+"#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.margins, MarginsConfig { top: 40, bottom: 0, left: 8, right: 0 });
+        assert_eq!(Config::default().margins, MarginsConfig::default());
+    }
+
+    #[test]
+    fn test_input_parse() {
+        let config: Config = toml::from_str("[input]\nhide_cursor_while_typing = true\n").unwrap();
+        assert!(config.input.hide_cursor_while_typing);
+        assert!(!Config::default().input.hide_cursor_while_typing);
+    }
+
+    #[test]
+    fn test_output_mirror_parse() {
+        let config: Config = toml::from_str(
+            "[[outputs]]\nname = \"HDMI-A-1\"\nmirror_of = \"eDP-1\"\n\n[[outputs]]\nname = \"eDP-1\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.outputs[0].mirror_of.as_deref(), Some("eDP-1"));
+        assert_eq!(config.outputs[1].mirror_of, None);
+    }
+
+    #[test]
+    fn test_workspace_name_for() {
+        let names = |names: &[&str]| WorkspacesConfig {
+            count: 3,
+            names: names.iter().map(|name| name.to_string()).collect(),
+            ..WorkspacesConfig::default()
+        };
+
+        // Fewer names than workspaces: the rest use their number
+        let fewer = names(&["web"]);
+        assert_eq!(fewer.name_for(1), "web");
+        assert_eq!(fewer.name_for(2), "2");
+        assert_eq!(fewer.name_for(3), "3");
+
+        // One name per workspace; empty names fall back to the number too
+        let equal = names(&["web", "", "term"]);
+        assert_eq!(equal.name_for(1), "web");
+        assert_eq!(equal.name_for(2), "2");
+        assert_eq!(equal.name_for(3), "term");
+
+        // More names than workspaces: the extras are ignored
+        let more = names(&["web", "code", "term", "music", "chat"]);
+        assert_eq!(more.name_for(3), "term");
+        assert_eq!(more.name_for(4), "4");
+        assert_eq!(more.name_for(0), "0");
+    }
+
+    #[test]
+    fn test_workspace_overrides_parse() {
+        let config: Config = toml::from_str(
+            "[[workspaces.per_workspace]]\nworkspace = 3\ngap = 0\nborder_width = 0\n\n[[workspaces.per_workspace]]\nworkspace = 4\ngap = 20\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.workspaces.gap_for(3), Some(0));
+        assert_eq!(config.workspaces.border_width_for(3), Some(0));
+        assert_eq!(config.workspaces.gap_for(4), Some(20));
+        assert_eq!(config.workspaces.border_width_for(4), None);
+        assert_eq!(config.workspaces.gap_for(1), None);
+    }
+
+    #[test]
+    fn test_group_background() {
+        let mut config = Config::default();
+        assert_eq!(config.group_background(), config.get_theme().colors.nord1);
+
+        config.theme.group_background = Some("#bf616a".to_string());
+        assert_eq!(config.group_background(), NordColor::rgb(0xbf, 0x61, 0x6a));
+
+        config.window_rules.push(WindowRule {
+            app_id: Some("firefox".to_string()),
+            group_background: Some("#a3be8c".to_string()),
+            ..WindowRule::default()
+        });
+        assert_eq!(
+            config.rule_group_background(Some("firefox"), None),
+            Some(NordColor::rgb(0xa3, 0xbe, 0x8c))
+        );
+        assert_eq!(config.rule_group_background(Some("mpv"), None), None);
+    }
 }
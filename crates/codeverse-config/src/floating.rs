@@ -0,0 +1,104 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+/// Position and size a floating window opens at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FloatingGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Per-app default floating geometry saved with the `save_floating_default`
+/// action, persisted in the cache directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FloatingDefaults {
+    /// Saved geometry by app ID
+    #[serde(default)]
+    pub apps: BTreeMap<String, FloatingGeometry>,
+}
+
+impl FloatingDefaults {
+    /// Load saved geometries from the default location
+    /// (~/.cache/codeverse-compositor/floating.toml)
+    pub fn load() -> Result<Self> {
+        let path = Self::cache_path()?;
+
+        if !path.exists() {
+            debug!("No floating defaults at {:?}", path);
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read floating defaults file: {:?}", path))?;
+
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse floating defaults file: {:?}", path))
+    }
+
+    /// Save geometries to the default location
+    pub fn save(&self) -> Result<()> {
+        let path = Self::cache_path()?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create cache directory: {:?}", parent))?;
+        }
+
+        let contents = toml::to_string_pretty(self)
+            .context("Failed to serialize floating defaults to TOML")?;
+
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write floating defaults file: {:?}", path))?;
+
+        info!("Saved {} floating defaults to {:?}", self.apps.len(), path);
+        Ok(())
+    }
+
+    /// Get the floating defaults file path
+    pub fn cache_path() -> Result<PathBuf> {
+        let cache_dir = dirs::cache_dir()
+            .context("Could not determine cache directory")?;
+
+        Ok(cache_dir.join("codeverse-compositor").join("floating.toml"))
+    }
+
+    /// Remember `geometry` for windows of `app_id`, replacing any earlier one
+    pub fn insert(&mut self, app_id: &str, geometry: FloatingGeometry) {
+        self.apps.insert(app_id.to_string(), geometry);
+    }
+
+    /// Saved geometry for windows of `app_id`
+    pub fn get(&self, app_id: &str) -> Option<FloatingGeometry> {
+        self.apps.get(app_id).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_floating_defaults_roundtrip() {
+        let mut defaults = FloatingDefaults::default();
+        let calculator = FloatingGeometry { x: 1500, y: 80, width: 360, height: 480 };
+        defaults.insert("org.gnome.Calculator", calculator);
+        defaults.insert("pavucontrol", FloatingGeometry { x: 0, y: 0, width: 800, height: 600 });
+
+        // Saving again replaces the earlier geometry
+        let moved = FloatingGeometry { x: 1400, ..calculator };
+        defaults.insert("org.gnome.Calculator", moved);
+
+        let toml_str = toml::to_string_pretty(&defaults).unwrap();
+        let loaded: FloatingDefaults = toml::from_str(&toml_str).unwrap();
+
+        assert_eq!(loaded.get("org.gnome.Calculator"), Some(moved));
+        assert_eq!(loaded.get("pavucontrol").map(|g| g.width), Some(800));
+        assert_eq!(loaded.get("foot"), None);
+    }
+}
@@ -14,7 +14,7 @@ pub enum KeybindingError {
 }
 
 /// Modifier keys
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Modifier {
     /// Super/Logo/Windows key
     Super,
@@ -26,11 +26,34 @@ pub enum Modifier {
     Alt,
 }
 
-/// Parsed keybinding
+impl Modifier {
+    /// The held modifiers, in the canonical order bindings use
+    pub fn held(super_pressed: bool, shift_pressed: bool, ctrl_pressed: bool, alt_pressed: bool) -> Vec<Modifier> {
+        [
+            (super_pressed, Modifier::Super),
+            (shift_pressed, Modifier::Shift),
+            (ctrl_pressed, Modifier::Ctrl),
+            (alt_pressed, Modifier::Alt),
+        ]
+        .into_iter()
+        .filter_map(|(pressed, modifier)| pressed.then_some(modifier))
+        .collect()
+    }
+}
+
+/// Parsed keybinding, in canonical form: modifiers sorted without
+/// duplicates and the key as [`canonical_key`] gives it. Two bindings for
+/// the same combo compare (and hash) equal, so pressed keys can be looked
+/// up directly.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Keybinding {
     pub modifiers: Vec<Modifier>,
+    /// Key that triggers the binding (`Keysym::NoSymbol` for button bindings)
     pub key: Keysym,
+    /// Linux input button code for mouse-button bindings (e.g. "Button8")
+    pub button: Option<u32>,
+    /// Zero-based tablet pad button for pad bindings (e.g. "Pad1")
+    pub pad_button: Option<u32>,
 }
 
 impl Keybinding {
@@ -58,20 +81,110 @@ impl Keybinding {
             modifiers.push(modifier);
         }
 
-        // Last part is the key
+        // Last part is the key, mouse button or tablet pad button
         let key_str = parts[parts.len() - 1];
+        if let Some(button) = string_to_button(key_str) {
+            return Ok(Keybinding::for_button(button, modifiers));
+        }
+
+        if let Some(pad_button) = string_to_pad_button(key_str) {
+            return Ok(Keybinding::for_pad_button(pad_button, modifiers));
+        }
+
         let key = string_to_keysym(key_str)
             .ok_or_else(|| KeybindingError::UnknownKey(key_str.to_string()))?;
 
-        Ok(Keybinding { modifiers, key })
+        Ok(Keybinding::for_key(key, modifiers))
+    }
+
+    /// Binding for a key pressed with exactly `modifiers` held
+    pub fn for_key(key: Keysym, mut modifiers: Vec<Modifier>) -> Self {
+        modifiers.sort();
+        modifiers.dedup();
+        Keybinding {
+            modifiers,
+            key: canonical_key(key),
+            button: None,
+            pad_button: None,
+        }
+    }
+
+    /// Binding for a mouse button pressed with exactly `modifiers` held
+    pub fn for_button(button: u32, mut modifiers: Vec<Modifier>) -> Self {
+        modifiers.sort();
+        modifiers.dedup();
+        Keybinding {
+            modifiers,
+            key: Keysym::NoSymbol,
+            button: Some(button),
+            pad_button: None,
+        }
+    }
+
+    /// Binding for a tablet pad button pressed with exactly `modifiers` held
+    pub fn for_pad_button(pad_button: u32, mut modifiers: Vec<Modifier>) -> Self {
+        modifiers.sort();
+        modifiers.dedup();
+        Keybinding {
+            modifiers,
+            key: Keysym::NoSymbol,
+            button: None,
+            pad_button: Some(pad_button),
+        }
+    }
+
+    /// Parse a chord like "Super+space w c" into its steps, pressed one
+    /// after the other (a plain binding parses to a single step)
+    pub fn parse_sequence(s: &str) -> Result<Vec<Self>, KeybindingError> {
+        let steps = s
+            .split_whitespace()
+            .map(Self::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if steps.is_empty() {
+            return Err(KeybindingError::InvalidFormat(
+                "Empty keybinding string".to_string(),
+            ));
+        }
+        if steps.len() > 1 && steps.iter().any(|step| step.button.is_some() || step.pad_button.is_some()) {
+            return Err(KeybindingError::InvalidFormat(format!(
+                "Mouse and pad buttons can't be part of a chord: {}",
+                s
+            )));
+        }
+
+        Ok(steps)
     }
 
     /// Check if this keybinding matches the given input
     pub fn matches(&self, key: Keysym, super_pressed: bool, shift_pressed: bool, ctrl_pressed: bool, alt_pressed: bool) -> bool {
-        if self.key != key {
+        if self.button.is_some() || self.pad_button.is_some() || self.key != canonical_key(key) {
+            return false;
+        }
+
+        self.modifiers_match(super_pressed, shift_pressed, ctrl_pressed, alt_pressed)
+    }
+
+    /// Check if this keybinding matches the given mouse button
+    pub fn matches_button(&self, button: u32, super_pressed: bool, shift_pressed: bool, ctrl_pressed: bool, alt_pressed: bool) -> bool {
+        if self.button != Some(button) {
+            return false;
+        }
+
+        self.modifiers_match(super_pressed, shift_pressed, ctrl_pressed, alt_pressed)
+    }
+
+    /// Check if this keybinding matches the given tablet pad button
+    pub fn matches_pad_button(&self, pad_button: u32, super_pressed: bool, shift_pressed: bool, ctrl_pressed: bool, alt_pressed: bool) -> bool {
+        if self.pad_button != Some(pad_button) {
             return false;
         }
 
+        self.modifiers_match(super_pressed, shift_pressed, ctrl_pressed, alt_pressed)
+    }
+
+    /// Check if exactly this binding's modifiers are pressed
+    fn modifiers_match(&self, super_pressed: bool, shift_pressed: bool, ctrl_pressed: bool, alt_pressed: bool) -> bool {
         let has_super = self.modifiers.contains(&Modifier::Super);
         let has_shift = self.modifiers.contains(&Modifier::Shift);
         let has_ctrl = self.modifiers.contains(&Modifier::Ctrl);
@@ -100,18 +213,42 @@ pub enum Action {
     SwitchWorkspace(usize),
     /// Move window to workspace (1-10)
     MoveToWorkspace(usize),
+    /// Move the active workspace, with its windows and name, to slot (1-10)
+    MoveWorkspaceTo(usize),
     /// Change layout mode
     ChangeLayout(String),
+    /// Wrap the focused window in a new container with this layout
+    WrapContainer(String),
+    /// Dissolve the single-child container around the focused window
+    UnwrapContainer,
+    /// Tear the focused window out of its group into a floating window
+    TearOff,
     /// Toggle floating mode
     ToggleFloating,
     /// Open launcher
     ToggleLauncher,
+    /// Show or hide the workspace grid overview
+    ToggleOverview,
+    /// Focus the previously focused window (switching workspace if needed)
+    FocusPrevious,
+    /// Float the focused window into a fraction of the work area
+    ResizeToGrid(GridSlot),
+    /// Remember the focused floating window's geometry for its app
+    SaveFloatingDefault,
     /// Reload configuration
     ReloadConfig,
     /// Spawn terminal (for testing)
     SpawnTerminal,
 }
 
+impl Action {
+    /// Whether the action may fire again without the key being released.
+    /// Destructive actions are guarded against key repeat.
+    pub fn is_repeatable(&self) -> bool {
+        !matches!(self, Action::Quit | Action::CloseWindow)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Direction {
@@ -128,6 +265,22 @@ pub enum SplitDirection {
     Vertical,
 }
 
+/// Fraction of the work area a window can be snapped to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GridSlot {
+    LeftHalf,
+    RightHalf,
+    TopHalf,
+    BottomHalf,
+    LeftThird,
+    CenterThird,
+    RightThird,
+    LeftTwoThirds,
+    RightTwoThirds,
+    Full,
+}
+
 /// Keybinding configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeybindingsConfig {
@@ -144,11 +297,16 @@ impl Default for KeybindingsConfig {
 }
 
 impl KeybindingsConfig {
-    /// Get parsed keybindings with their actions
+    /// Get parsed keybindings with their actions (chords are left to
+    /// [`Self::parse_chords`])
     pub fn parse_all(&self) -> HashMap<Keybinding, Action> {
         let mut result = HashMap::new();
 
         for (key_str, action) in &self.bindings {
+            if is_chord(key_str) {
+                continue;
+            }
+
             match Keybinding::parse(key_str) {
                 Ok(keybinding) => {
                     result.insert(keybinding, action.clone());
@@ -161,6 +319,41 @@ impl KeybindingsConfig {
 
         result
     }
+
+    /// Get the multi-step bindings ("Super+space w c") with their actions
+    pub fn parse_chords(&self) -> Vec<(Vec<Keybinding>, Action)> {
+        let mut result = Vec::new();
+
+        for (key_str, action) in &self.bindings {
+            if !is_chord(key_str) {
+                continue;
+            }
+
+            match Keybinding::parse_sequence(key_str) {
+                Ok(steps) => result.push((steps, action.clone())),
+                Err(e) => {
+                    tracing::warn!("Failed to parse keybinding '{}': {}", key_str, e);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Whether a binding string has more than one step
+fn is_chord(s: &str) -> bool {
+    s.split_whitespace().nth(1).is_some()
+}
+
+/// The keysym a binding is written with: Shift turns letters into capitals
+/// and Tab into ISO_Left_Tab, but "Super+Shift+q" names the plain key
+pub fn canonical_key(key: Keysym) -> Keysym {
+    match key {
+        Keysym::ISO_Left_Tab => Keysym::Tab,
+        _ if (Keysym::A..=Keysym::Z).contains(&key) => Keysym::new(key.raw() + (Keysym::a.raw() - Keysym::A.raw())),
+        _ => key,
+    }
 }
 
 /// Convert a string to a Keysym
@@ -241,6 +434,29 @@ fn string_to_keysym(s: &str) -> Option<Keysym> {
     Some(keysym)
 }
 
+/// Convert a mouse button name to a Linux input button code
+///
+/// Buttons use X11 numbering: "Button8" is the back/side button and
+/// "Button9" the forward/extra button. Buttons 1-3 are left/middle/right
+/// and 4-7 are scroll axes, so they are not bindable.
+fn string_to_button(s: &str) -> Option<u32> {
+    // Linux input event codes (linux/input-event-codes.h)
+    const BTN_SIDE: u32 = 0x113;
+    const BTN_TASK: u32 = 0x117;
+
+    let number: u32 = s.to_lowercase().strip_prefix("button")?.parse().ok()?;
+    let code = BTN_SIDE + number.checked_sub(8)?;
+
+    (code <= BTN_TASK).then_some(code)
+}
+
+/// Convert a tablet pad button name to libinput's zero-based pad button
+/// number: "Pad1" is the pad's first button
+fn string_to_pad_button(s: &str) -> Option<u32> {
+    let number: u32 = s.to_lowercase().strip_prefix("pad")?.parse().ok()?;
+    number.checked_sub(1)
+}
+
 /// Default keybindings
 fn default_keybindings() -> HashMap<String, Action> {
     let mut bindings = HashMap::new();
@@ -280,6 +496,12 @@ fn default_keybindings() -> HashMap<String, Action> {
     // Launcher
     bindings.insert("Super+d".to_string(), Action::ToggleLauncher);
 
+    // Workspace overview
+    bindings.insert("Super+Tab".to_string(), Action::ToggleOverview);
+
+    // Toggle to the previously focused window (Super+Tab is the overview)
+    bindings.insert("Alt+Tab".to_string(), Action::FocusPrevious);
+
     // Testing
     bindings.insert("F12".to_string(), Action::SpawnTerminal);
 
@@ -339,6 +561,80 @@ mod tests {
         assert!(!kb.matches(Keysym::d, true, true, false, false));
     }
 
+    #[test]
+    fn test_keybinding_canonical_form() {
+        // Modifier order, case and shifted keysyms don't matter
+        let kb = Keybinding::parse("Shift+Super+Shift+Q").unwrap();
+        assert_eq!(kb, Keybinding::parse("Super+Shift+q").unwrap());
+        assert_eq!(kb, Keybinding::for_key(Keysym::Q, Modifier::held(true, true, false, false)));
+        assert!(kb.matches(Keysym::Q, true, true, false, false));
+
+        let tab = Keybinding::for_key(Keysym::ISO_Left_Tab, vec![Modifier::Shift, Modifier::Alt]);
+        assert_eq!(tab, Keybinding::parse("Alt+Shift+Tab").unwrap());
+        let alt_tab = Keybinding::for_key(Keysym::Tab, Modifier::held(false, false, false, true));
+
+        let config = KeybindingsConfig::default();
+        let parsed = config.parse_all();
+        let pressed = Keybinding::for_key(Keysym::d, Modifier::held(true, false, false, false));
+        assert_eq!(parsed.get(&pressed), Some(&Action::ToggleLauncher));
+        assert_eq!(parsed.get(&alt_tab), Some(&Action::FocusPrevious));
+        assert_eq!(parsed.get(&Keybinding::for_button(0x113, Vec::new())), None);
+    }
+
+    #[test]
+    fn test_parse_mouse_button() {
+        let kb = Keybinding::parse("Super+Button8").unwrap();
+        assert_eq!(kb.modifiers, vec![Modifier::Super]);
+        assert_eq!(kb.button, Some(0x113));
+        assert!(kb.matches_button(0x113, true, false, false, false));
+        assert!(!kb.matches_button(0x113, false, false, false, false));
+        assert!(!kb.matches_button(0x114, true, false, false, false));
+        assert!(!kb.matches(Keysym::NoSymbol, true, false, false, false));
+
+        assert_eq!(Keybinding::parse("Button9").unwrap().button, Some(0x114));
+        assert!(Keybinding::parse("Button1").is_err());
+        assert!(Keybinding::parse("Button13").is_err());
+    }
+
+    #[test]
+    fn test_parse_pad_button() {
+        let kb = Keybinding::parse("Pad1").unwrap();
+        assert_eq!(kb.pad_button, Some(0));
+        assert_eq!(kb.button, None);
+        assert!(kb.matches_pad_button(0, false, false, false, false));
+        assert!(!kb.matches_pad_button(1, false, false, false, false));
+        assert!(!kb.matches_button(0x113, false, false, false, false));
+        assert!(!kb.matches(Keysym::NoSymbol, false, false, false, false));
+
+        let kb = Keybinding::parse("Ctrl+Pad4").unwrap();
+        assert!(kb.matches_pad_button(3, false, false, true, false));
+        assert!(!kb.matches_pad_button(3, false, false, false, false));
+
+        assert!(Keybinding::parse("Pad0").is_err());
+        assert!(Keybinding::parse_sequence("Super+space Pad1").is_err());
+    }
+
+    #[test]
+    fn test_parse_chord() {
+        let steps = Keybinding::parse_sequence("Super+space w  c").unwrap();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].modifiers, vec![Modifier::Super]);
+        assert_eq!(steps[0].key, Keysym::space);
+        assert_eq!(steps[1].key, Keysym::w);
+        assert!(steps[2].modifiers.is_empty());
+        assert_eq!(steps[2].key, Keysym::c);
+
+        assert!(Keybinding::parse_sequence("Super+space bogus").is_err());
+        assert!(Keybinding::parse_sequence("Super+space Button8").is_err());
+
+        // Chords and single bindings are parsed separately
+        let mut config = KeybindingsConfig::default();
+        config.bindings.insert("Super+space w c".to_string(), Action::CloseWindow);
+        let chords = config.parse_chords();
+        assert_eq!(chords, vec![(steps, Action::CloseWindow)]);
+        assert_eq!(config.parse_all().len(), config.bindings.len() - 1);
+    }
+
     #[test]
     fn test_default_keybindings() {
         let config = KeybindingsConfig::default();
@@ -1,7 +1,13 @@
 pub mod config;
+pub mod floating;
 pub mod keybindings;
+pub mod presets;
+pub mod rules;
 pub mod theme;
 
-pub use config::{Config, GeneralConfig, LauncherConfig, ThemeConfig, WorkspacesConfig};
-pub use keybindings::{Action, Direction, Keybinding, KeybindingError, KeybindingsConfig, Modifier, SplitDirection};
+pub use config::{Config, GeneralConfig, InputConfig, LauncherConfig, LoggingConfig, MarginsConfig, TaskbarConfig, ThemeConfig, WorkspaceOverrides, WorkspacesConfig};
+pub use floating::{FloatingDefaults, FloatingGeometry};
+pub use keybindings::{canonical_key, Action, Direction, GridSlot, Keybinding, KeybindingError, KeybindingsConfig, Modifier, SplitDirection};
+pub use presets::{PresetNode, PresetStore};
+pub use rules::WindowRule;
 pub use theme::{FontConfig, NordColor, NordColors, NordTheme};
@@ -0,0 +1,180 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+/// A node in a saved layout preset
+///
+/// Container nodes carry a layout name ("splith", "splitv", "stacking",
+/// "tabbed") and child nodes. Window slots have no layout and are matched
+/// against live windows by `app_id` when the preset is applied. Either kind
+/// may pin its share of the parent split with `locked_ratio`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PresetNode {
+    /// Layout mode for a container node (`None` for a window slot)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<String>,
+
+    /// Application ID expected in a window slot (`None` matches any window)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<String>,
+
+    /// Child nodes (containers only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<PresetNode>,
+
+    /// Fixed share of the parent split (0.0-1.0), if the node had one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked_ratio: Option<f32>,
+}
+
+impl PresetNode {
+    /// Create a container node with the given layout and children
+    pub fn container(layout: &str, children: Vec<PresetNode>) -> Self {
+        Self {
+            layout: Some(layout.to_string()),
+            app_id: None,
+            children,
+            locked_ratio: None,
+        }
+    }
+
+    /// Create a window slot for the given app ID
+    pub fn window(app_id: Option<&str>) -> Self {
+        Self {
+            layout: None,
+            app_id: app_id.map(|s| s.to_string()),
+            children: Vec::new(),
+            locked_ratio: None,
+        }
+    }
+
+    /// Pin this node's share of its parent split
+    pub fn with_locked_ratio(mut self, locked_ratio: Option<f32>) -> Self {
+        self.locked_ratio = locked_ratio;
+        self
+    }
+
+    /// Check if this node is a window slot
+    pub fn is_window(&self) -> bool {
+        self.layout.is_none()
+    }
+}
+
+/// Named layout presets, persisted in the cache directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PresetStore {
+    #[serde(default)]
+    pub presets: BTreeMap<String, PresetNode>,
+}
+
+impl PresetStore {
+    /// Load presets from the default location
+    /// (~/.cache/codeverse-compositor/presets.toml)
+    pub fn load() -> Result<Self> {
+        let path = Self::cache_path()?;
+
+        if !path.exists() {
+            debug!("No layout presets at {:?}", path);
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read presets file: {:?}", path))?;
+
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse presets file: {:?}", path))
+    }
+
+    /// Save presets to the default location
+    pub fn save(&self) -> Result<()> {
+        let path = Self::cache_path()?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create cache directory: {:?}", parent))?;
+        }
+
+        let contents = toml::to_string_pretty(self)
+            .context("Failed to serialize presets to TOML")?;
+
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write presets file: {:?}", path))?;
+
+        info!("Saved {} layout presets to {:?}", self.presets.len(), path);
+        Ok(())
+    }
+
+    /// Get the presets file path
+    pub fn cache_path() -> Result<PathBuf> {
+        let cache_dir = dirs::cache_dir()
+            .context("Could not determine cache directory")?;
+
+        Ok(cache_dir.join("codeverse-compositor").join("presets.toml"))
+    }
+
+    /// Store a preset under the given name, replacing any existing one
+    pub fn insert(&mut self, name: &str, preset: PresetNode) {
+        self.presets.insert(name.to_string(), preset);
+    }
+
+    /// Get a preset by name
+    pub fn get(&self, name: &str) -> Option<&PresetNode> {
+        self.presets.get(name)
+    }
+
+    /// Get the name of the preset following `current` (alphabetically, wrapping)
+    pub fn next_after(&self, current: Option<&str>) -> Option<&str> {
+        let next = match current {
+            Some(current) => self
+                .presets
+                .keys()
+                .find(|name| name.as_str() > current),
+            None => None,
+        };
+
+        next.or_else(|| self.presets.keys().next())
+            .map(|name| name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_store_roundtrip() {
+        let mut store = PresetStore::default();
+        store.insert(
+            "coding",
+            PresetNode::container(
+                "splith",
+                vec![
+                    PresetNode::window(Some("editor")).with_locked_ratio(Some(0.6)),
+                    PresetNode::container(
+                        "splitv",
+                        vec![PresetNode::window(Some("foot")), PresetNode::window(Some("foot"))],
+                    ),
+                ],
+            ),
+        );
+
+        let toml_str = toml::to_string_pretty(&store).unwrap();
+        let loaded: PresetStore = toml::from_str(&toml_str).unwrap();
+
+        assert_eq!(loaded.get("coding"), store.get("coding"));
+    }
+
+    #[test]
+    fn test_next_after_wraps() {
+        let mut store = PresetStore::default();
+        store.insert("coding", PresetNode::container("splith", vec![]));
+        store.insert("writing", PresetNode::container("tabbed", vec![]));
+
+        assert_eq!(store.next_after(None), Some("coding"));
+        assert_eq!(store.next_after(Some("coding")), Some("writing"));
+        assert_eq!(store.next_after(Some("writing")), Some("coding"));
+    }
+}
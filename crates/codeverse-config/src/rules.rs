@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+/// A window rule: match criteria plus per-window overrides.
+///
+/// A rule matches when every criterion it sets matches; a rule without
+/// any criteria never matches, so it can't accidentally apply to all windows.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowRule {
+    /// Match windows with exactly this app_id
+    pub app_id: Option<String>,
+
+    /// Match windows whose title contains this text
+    pub title: Option<String>,
+
+    /// Border width override in pixels (0 for borderless)
+    pub border_width: Option<u32>,
+
+    /// Background (hex format: #RRGGBB) of the tabbed/stacking group
+    /// holding the window
+    pub group_background: Option<String>,
+
+    /// Opacity (0.0 - 1.0) the window is drawn with, focused or not
+    pub opacity: Option<f32>,
+
+    /// Size steps `[width, height]` in pixels (e.g. a terminal's character
+    /// cell); the window is sized down to whole steps
+    pub resize_increment: Option<(u32, u32)>,
+}
+
+impl WindowRule {
+    /// Check whether this rule applies to a window
+    pub fn matches(&self, app_id: Option<&str>, title: Option<&str>) -> bool {
+        if self.app_id.is_none() && self.title.is_none() {
+            return false;
+        }
+
+        let app_id_matches = match self.app_id {
+            Some(ref wanted) => app_id == Some(wanted.as_str()),
+            None => true,
+        };
+        let title_matches = match self.title {
+            Some(ref wanted) => title.is_some_and(|title| title.contains(wanted.as_str())),
+            None => true,
+        };
+
+        app_id_matches && title_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_matching() {
+        let rule = WindowRule {
+            app_id: Some("mpv".to_string()),
+            title: Some("Video".to_string()),
+            border_width: Some(0),
+            group_background: None,
+            opacity: None,
+            resize_increment: None,
+        };
+
+        assert!(rule.matches(Some("mpv"), Some("My Video - mpv")));
+        assert!(!rule.matches(Some("mpv"), Some("Audio")));
+        assert!(!rule.matches(Some("firefox"), Some("Video")));
+        assert!(!rule.matches(None, None));
+
+        // A rule without criteria matches nothing
+        assert!(!WindowRule::default().matches(Some("mpv"), None));
+    }
+}
@@ -18,6 +18,18 @@ impl NordColor {
         Self { r, g, b, a }
     }
 
+    /// Parse a "#RRGGBB" or "#RRGGBBAA" hex color
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let digits = hex.strip_prefix('#')?;
+        if !matches!(digits.len(), 6 | 8) || !digits.is_ascii() {
+            return None;
+        }
+
+        let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+        let alpha = if digits.len() == 8 { channel(6)? } else { 255 };
+        Some(Self::rgba(channel(0)?, channel(2)?, channel(4)?, alpha))
+    }
+
     /// Convert to f32 array for rendering (0.0-1.0 range)
     pub fn to_f32_array(&self) -> [f32; 4] {
         [
@@ -138,11 +150,11 @@ pub struct NordTheme {
     pub fonts: FontConfig,
     pub border_width: u32,
     pub gap_width: u32,
-    /// Window opacity (0.0 - 1.0, for future transparency support)
+    /// Default window opacity (0.0 - 1.0); window rules can override it
     pub opacity: f32,
-    /// Enable window shadows (not yet implemented)
+    /// Draw window shadows (`general.shadows_enabled`)
     pub shadow_enabled: bool,
-    /// Animation duration in milliseconds (for future transitions)
+    /// Animation duration in milliseconds (`general.animation_duration`)
     pub animation_duration: u32,
 }
 
@@ -228,6 +240,15 @@ mod tests {
         assert_eq!(f32_array[3], 1.0);
     }
 
+    #[test]
+    fn test_nord_color_from_hex() {
+        assert_eq!(NordColor::from_hex("#3b4252"), Some(NordColor::rgb(0x3b, 0x42, 0x52)));
+        assert_eq!(NordColor::from_hex("#3B425280"), Some(NordColor::rgba(0x3b, 0x42, 0x52, 0x80)));
+        assert_eq!(NordColor::from_hex("3b4252"), None);
+        assert_eq!(NordColor::from_hex("#3b425"), None);
+        assert_eq!(NordColor::from_hex("#zz4252"), None);
+    }
+
     #[test]
     fn test_theme_defaults() {
         let theme = NordTheme::new();
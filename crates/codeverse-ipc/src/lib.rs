@@ -20,10 +20,23 @@ pub enum IpcError {
 pub enum IpcCommand {
     GetWorkspaces,
     SwitchWorkspace { number: usize },
+    /// Move workspace `from` (windows and name) to slot `to`, shifting the
+    /// workspaces in between
+    MoveWorkspace { from: usize, to: usize },
     GetFocusedWindow,
     CloseWindow,
     ReloadConfig,
     ToggleLauncher,
+    SavePreset { name: String },
+    ApplyPreset { name: String },
+    CyclePreset,
+    /// Get the layout of the focused container
+    GetLayout,
+    /// Change the focused container's layout ("splith", "splitv", "stacking", "tabbed")
+    SetLayout { layout: String },
+    /// Get the output rectangle and the part of it left for windows
+    /// after the configured margins
+    GetWorkArea,
     Ping,
 }
 
@@ -38,6 +51,13 @@ pub enum IpcResponse {
     FocusedWindow {
         title: Option<String>,
     },
+    Layout {
+        layout: String,
+    },
+    WorkArea {
+        screen: IpcRect,
+        work_area: IpcRect,
+    },
     Ok,
     Pong,
     Error {
@@ -45,6 +65,15 @@ pub enum IpcResponse {
     },
 }
 
+/// A rectangle in global logical coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpcRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
 /// Returns the default IPC socket path for the compositor instance.
 pub fn socket_path() -> PathBuf {
     let runtime_dir = std::env::var("XDG_RUNTIME_DIR")
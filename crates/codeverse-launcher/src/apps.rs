@@ -1,5 +1,6 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use crate::icons::resolve_icon;
 use tracing::{debug, warn};
 
 /// A desktop application entry
@@ -15,6 +16,10 @@ pub struct App {
     pub desktop_file: PathBuf,
     /// Whether this is a terminal application
     pub terminal: bool,
+    /// Icon name or absolute path (`Icon=`)
+    pub icon: Option<String>,
+    /// Window class/app_id the app's windows use (`StartupWMClass=`)
+    pub wm_class: Option<String>,
 }
 
 impl App {
@@ -27,6 +32,43 @@ impl App {
             .collect::<Vec<_>>()
             .join(" ")
     }
+
+    /// Whether the program the command runs can be found: an existing path
+    /// or a name on `$PATH`. Commands we can't pick apart (quoted programs,
+    /// leading `VAR=value` assignments) are assumed to be installed.
+    pub fn is_installed(&self) -> bool {
+        let command = self.get_command();
+        let Some(program) = command.split_whitespace().next() else {
+            return false;
+        };
+
+        if program.contains(['"', '\'', '=']) {
+            return true;
+        }
+        if program.contains('/') {
+            return Path::new(program).exists();
+        }
+
+        std::env::var_os("PATH").is_some_and(|path| {
+            std::env::split_paths(&path).any(|dir| dir.join(program).is_file())
+        })
+    }
+
+    /// Check whether windows with this app_id belong to this app, matching
+    /// the .desktop file name or StartupWMClass (case-insensitive)
+    pub fn matches_app_id(&self, app_id: &str) -> bool {
+        let stem_matches = self
+            .desktop_file
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| stem.eq_ignore_ascii_case(app_id));
+        let class_matches = self
+            .wm_class
+            .as_deref()
+            .is_some_and(|class| class.eq_ignore_ascii_case(app_id));
+
+        stem_matches || class_matches
+    }
 }
 
 /// Discover and parse desktop applications
@@ -42,6 +84,11 @@ impl AppDiscovery {
         discovery
     }
 
+    /// Create an app discovery instance from an already known app list
+    pub fn from_apps(apps: Vec<App>) -> Self {
+        Self { apps }
+    }
+
     /// Scan for desktop files in standard locations
     fn scan(&mut self) {
         let search_paths = vec![
@@ -87,6 +134,8 @@ impl AppDiscovery {
         let mut exec: Option<String> = None;
         let mut description: Option<String> = None;
         let mut terminal = false;
+        let mut icon: Option<String> = None;
+        let mut wm_class: Option<String> = None;
         let mut no_display = false;
         let mut hidden = false;
 
@@ -127,6 +176,8 @@ impl AppDiscovery {
                         }
                     }
                     "Terminal" => terminal = value.eq_ignore_ascii_case("true"),
+                    "Icon" => icon = Some(value.to_string()),
+                    "StartupWMClass" => wm_class = Some(value.to_string()),
                     "NoDisplay" => no_display = value.eq_ignore_ascii_case("true"),
                     "Hidden" => hidden = value.eq_ignore_ascii_case("true"),
                     _ => {}
@@ -149,6 +200,8 @@ impl AppDiscovery {
             description,
             desktop_file: path.to_path_buf(),
             terminal,
+            icon,
+            wm_class,
         })
     }
 
@@ -157,6 +210,17 @@ impl AppDiscovery {
         &self.apps
     }
 
+    /// Find the app whose windows use the given app_id
+    pub fn find_by_app_id(&self, app_id: &str) -> Option<&App> {
+        self.apps.iter().find(|app| app.matches_app_id(app_id))
+    }
+
+    /// Resolve the icon file for windows with the given app_id
+    pub fn icon_for_app_id(&self, app_id: &str, icon_dirs: &[PathBuf]) -> Option<PathBuf> {
+        let icon = self.find_by_app_id(app_id)?.icon.as_deref()?;
+        resolve_icon(icon, icon_dirs)
+    }
+
     /// Search apps by name (case-insensitive, fuzzy-ish)
     pub fn search(&self, query: &str) -> Vec<&App> {
         if query.is_empty() {
@@ -241,10 +305,80 @@ mod tests {
             description: None,
             desktop_file: PathBuf::from("/test.desktop"),
             terminal: false,
+            icon: None,
+            wm_class: None,
         };
 
         let cmd = app.get_command();
         assert_eq!(cmd, "firefox --new-window");
         assert!(!cmd.contains("%u"));
     }
+
+    #[test]
+    fn test_is_installed() {
+        let app = |exec: &str| App {
+            name: "Test".to_string(),
+            exec: exec.to_string(),
+            description: None,
+            desktop_file: PathBuf::from("/test.desktop"),
+            terminal: false,
+            icon: None,
+            wm_class: None,
+        };
+
+        assert!(app("sh -c true").is_installed());
+        assert!(app("/bin/sh %f").is_installed());
+        assert!(!app("codeverse-no-such-program --flag").is_installed());
+        assert!(!app("/nonexistent/bin/editor").is_installed());
+        assert!(!app("%u").is_installed());
+
+        // Commands we can't parse are given the benefit of the doubt
+        assert!(app("\"/opt/My App/bin/app\" %u").is_installed());
+        assert!(app("GDK_BACKEND=x11 legacy-app").is_installed());
+    }
+
+    #[test]
+    fn test_icon_for_app_id() {
+        let icon_root = std::env::temp_dir().join(format!("codeverse-icons-{}", std::process::id()));
+        let icon_dir = icon_root.join("hicolor").join("48x48").join("apps");
+        fs::create_dir_all(&icon_dir).unwrap();
+        fs::write(icon_dir.join("editor.png"), b"").unwrap();
+
+        let discovery = AppDiscovery::from_apps(vec![
+            App {
+                name: "Editor".to_string(),
+                exec: "editor".to_string(),
+                description: None,
+                desktop_file: PathBuf::from("/apps/org.example.Editor.desktop"),
+                terminal: false,
+                icon: Some("editor".to_string()),
+                wm_class: None,
+            },
+            App {
+                name: "Browser".to_string(),
+                exec: "browser".to_string(),
+                description: None,
+                desktop_file: PathBuf::from("/apps/browser.desktop"),
+                terminal: false,
+                icon: Some("browser".to_string()),
+                wm_class: Some("WebBrowser".to_string()),
+            },
+        ]);
+        let dirs = vec![icon_root.clone()];
+
+        // Matched by .desktop file name
+        assert_eq!(
+            discovery.icon_for_app_id("org.example.Editor", &dirs),
+            Some(icon_dir.join("editor.png"))
+        );
+
+        // Matched by StartupWMClass, but the icon file doesn't exist
+        assert_eq!(discovery.find_by_app_id("webbrowser").unwrap().name, "Browser");
+        assert_eq!(discovery.icon_for_app_id("webbrowser", &dirs), None);
+
+        // Unknown app
+        assert_eq!(discovery.icon_for_app_id("unknown", &dirs), None);
+
+        fs::remove_dir_all(&icon_root).unwrap();
+    }
 }
@@ -0,0 +1,146 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// Launch statistics for a single app
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct FrecencyEntry {
+    /// Number of launches
+    pub count: u32,
+    /// Unix timestamp (seconds) of the last launch
+    pub last_used: u64,
+}
+
+/// Launch history used to rank apps by frecency (frequency + recency),
+/// persisted in the cache directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FrecencyStore {
+    /// Entries keyed by the app's .desktop file path
+    #[serde(default)]
+    pub entries: BTreeMap<String, FrecencyEntry>,
+}
+
+impl FrecencyStore {
+    /// Load launch history from the default location
+    /// (~/.cache/codeverse-compositor/launcher_history.toml)
+    pub fn load() -> Result<Self> {
+        let path = Self::cache_path()?;
+
+        if !path.exists() {
+            debug!("No launcher history at {:?}", path);
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read launcher history: {:?}", path))?;
+
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse launcher history: {:?}", path))
+    }
+
+    /// Save launch history to the default location
+    pub fn save(&self) -> Result<()> {
+        let path = Self::cache_path()?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create cache directory: {:?}", parent))?;
+        }
+
+        let contents = toml::to_string_pretty(self)
+            .context("Failed to serialize launcher history to TOML")?;
+
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write launcher history: {:?}", path))
+    }
+
+    /// Get the launcher history file path
+    pub fn cache_path() -> Result<PathBuf> {
+        let cache_dir = dirs::cache_dir()
+            .context("Could not determine cache directory")?;
+
+        Ok(cache_dir.join("codeverse-compositor").join("launcher_history.toml"))
+    }
+
+    /// Record a launch of `key` at unix time `now`
+    pub fn record(&mut self, key: &str, now: u64) {
+        let entry = self.entries.entry(key.to_string()).or_default();
+        entry.count += 1;
+        entry.last_used = now;
+    }
+
+    /// Frecency score: launch count weighted by how recently it was used
+    pub fn score(&self, key: &str, now: u64) -> f64 {
+        let Some(entry) = self.entries.get(key) else {
+            return 0.0;
+        };
+
+        let age = now.saturating_sub(entry.last_used);
+        let weight = if age < HOUR {
+            4.0
+        } else if age < DAY {
+            2.0
+        } else if age < WEEK {
+            1.0
+        } else {
+            0.5
+        };
+
+        entry.count as f64 * weight
+    }
+
+    /// Keys ordered by frecency, highest first (ties go to the most recent)
+    pub fn ranked(&self, now: u64) -> Vec<&str> {
+        let mut keys: Vec<&str> = self.entries.keys().map(|key| key.as_str()).collect();
+
+        keys.sort_by(|a, b| {
+            self.score(b, now)
+                .total_cmp(&self.score(a, now))
+                .then_with(|| self.entries[*b].last_used.cmp(&self.entries[*a].last_used))
+        });
+
+        keys
+    }
+}
+
+/// Current unix time in seconds
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frecency_ranking() {
+        let now = 10 * WEEK;
+        let mut store = FrecencyStore::default();
+
+        // Used often, but a long time ago
+        for _ in 0..3 {
+            store.record("old.desktop", now - 2 * WEEK);
+        }
+        // Used twice within the last hour
+        store.record("fresh.desktop", now - 60);
+        store.record("fresh.desktop", now - 30);
+        // Used once today
+        store.record("today.desktop", now - 2 * HOUR);
+
+        assert_eq!(store.score("fresh.desktop", now), 8.0);
+        assert_eq!(store.score("old.desktop", now), 1.5);
+        assert_eq!(store.score("missing.desktop", now), 0.0);
+        assert_eq!(store.ranked(now), vec!["fresh.desktop", "today.desktop", "old.desktop"]);
+    }
+}
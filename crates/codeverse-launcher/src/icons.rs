@@ -0,0 +1,47 @@
+use std::path::{Path, PathBuf};
+
+/// hicolor theme sizes to try, best fit for small title-bar icons first
+const ICON_SIZES: [&str; 6] = ["48x48", "32x32", "64x64", "24x24", "128x128", "256x256"];
+
+/// Standard icon directories (user icons take precedence)
+pub fn default_icon_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Some(data_dir) = dirs::data_local_dir() {
+        dirs.push(data_dir.join("icons"));
+    }
+    dirs.push(PathBuf::from("/usr/local/share/icons"));
+    dirs.push(PathBuf::from("/usr/share/icons"));
+    dirs.push(PathBuf::from("/usr/share/pixmaps"));
+
+    dirs
+}
+
+/// Resolve a desktop entry's `Icon=` value to a PNG file.
+///
+/// Absolute paths are used as-is; icon names are looked up in the hicolor
+/// theme and then directly inside each directory (as in /usr/share/pixmaps).
+pub fn resolve_icon(icon: &str, icon_dirs: &[PathBuf]) -> Option<PathBuf> {
+    let path = Path::new(icon);
+    if path.is_absolute() {
+        return path.exists().then(|| path.to_path_buf());
+    }
+
+    let file_name = format!("{}.png", icon);
+
+    for dir in icon_dirs {
+        for size in ICON_SIZES {
+            let candidate = dir.join("hicolor").join(size).join("apps").join(&file_name);
+            if candidate.exists() {
+                return Some(candidate);
+            }
+        }
+
+        let candidate = dir.join(&file_name);
+        if candidate.exists() {
+            return Some(candidate);
+        }
+    }
+
+    None
+}
@@ -1,6 +1,30 @@
 pub mod apps;
+pub mod frecency;
+pub mod icons;
 
 pub use apps::{App, AppDiscovery};
+pub use frecency::{unix_now, FrecencyEntry, FrecencyStore};
+pub use icons::{default_icon_dirs, resolve_icon};
+
+/// Split a `:N` workspace target off a launcher query: ":3 firefox"
+/// searches "firefox" and opens it on workspace 3. Anything else is
+/// searched as is.
+pub fn parse_workspace_target(query: &str) -> (Option<usize>, &str) {
+    let Some(rest) = query.strip_prefix(':') else {
+        return (None, query);
+    };
+
+    let digits_end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let (digits, search) = rest.split_at(digits_end);
+    if digits.is_empty() || !(search.is_empty() || search.starts_with(char::is_whitespace)) {
+        return (None, query);
+    }
+
+    match digits.parse() {
+        Ok(workspace) if workspace > 0 => (Some(workspace), search.trim_start()),
+        _ => (None, query),
+    }
+}
 
 /// Launcher state for managing application search and selection
 pub struct LauncherState {
@@ -15,30 +39,91 @@ pub struct LauncherState {
 
     /// Currently selected index in results
     selected_index: usize,
+
+    /// Launch history for ranking recent apps
+    history: FrecencyStore,
+
+    /// Number of recent apps listed first for an empty query
+    recent_count: usize,
 }
 
 impl LauncherState {
     /// Create a new launcher state
     pub fn new() -> Self {
-        let discovery = AppDiscovery::new();
-        let results = discovery.apps().to_vec();
+        let history = FrecencyStore::load().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load launcher history: {}, starting empty", e);
+            FrecencyStore::default()
+        });
+
+        Self::with_discovery(AppDiscovery::new(), history)
+    }
 
-        Self {
+    /// Create a launcher state from an app list and launch history
+    pub fn with_discovery(discovery: AppDiscovery, history: FrecencyStore) -> Self {
+        let mut launcher = Self {
             discovery,
             query: String::new(),
-            results,
+            results: Vec::new(),
             selected_index: 0,
+            history,
+            recent_count: 0,
+        };
+        launcher.refresh_results();
+        launcher
+    }
+
+    /// Set how many recent apps lead the results for an empty query
+    pub fn set_recent_count(&mut self, recent_count: usize) {
+        self.recent_count = recent_count;
+        if self.query.is_empty() {
+            self.refresh_results();
+        }
+    }
+
+    /// Record a launch for frecency ranking and persist the history
+    pub fn record_launch(&mut self, app: &App) {
+        let key = app.desktop_file.to_string_lossy();
+        self.history.record(&key, unix_now());
+
+        if let Err(e) = self.history.save() {
+            tracing::warn!("Failed to save launcher history: {}", e);
         }
     }
 
     /// Update the search query and refresh results
     pub fn set_query(&mut self, query: String) {
         self.query = query;
-        self.results = self.discovery.search(&self.query)
+        self.refresh_results();
+    }
+
+    /// Recompute results for the current query. An empty query lists the
+    /// most frecent apps first, then the rest in discovery order.
+    fn refresh_results(&mut self) {
+        self.selected_index = 0;
+
+        let (_, search) = parse_workspace_target(&self.query);
+        if !search.is_empty() {
+            self.results = self.discovery.search(search)
+                .into_iter()
+                .cloned()
+                .collect();
+            return;
+        }
+
+        let apps = self.discovery.apps();
+        let recents: Vec<&App> = self
+            .history
+            .ranked(unix_now())
             .into_iter()
-            .cloned()
+            .filter_map(|key| apps.iter().find(|app| app.desktop_file.to_string_lossy() == key))
+            .take(self.recent_count)
             .collect();
-        self.selected_index = 0;
+
+        let rest = apps.iter().filter(|app| {
+            !recents.iter().any(|recent| recent.desktop_file == app.desktop_file)
+        });
+
+        self.results = recents.iter().copied().chain(rest).cloned().collect();
     }
 
     /// Get the current search query
@@ -46,24 +131,21 @@ impl LauncherState {
         &self.query
     }
 
+    /// Workspace the query asks to open the app on (`:N` prefix)
+    pub fn workspace_target(&self) -> Option<usize> {
+        parse_workspace_target(&self.query).0
+    }
+
     /// Add a character to the search query
     pub fn push_char(&mut self, ch: char) {
         self.query.push(ch);
-        self.results = self.discovery.search(&self.query)
-            .into_iter()
-            .cloned()
-            .collect();
-        self.selected_index = 0;
+        self.refresh_results();
     }
 
     /// Remove last character from search query
     pub fn pop_char(&mut self) {
         self.query.pop();
-        self.results = self.discovery.search(&self.query)
-            .into_iter()
-            .cloned()
-            .collect();
-        self.selected_index = 0;
+        self.refresh_results();
     }
 
     /// Get current search results
@@ -98,8 +180,7 @@ impl LauncherState {
     /// Reset launcher state
     pub fn reset(&mut self) {
         self.query.clear();
-        self.results = self.discovery.apps().to_vec();
-        self.selected_index = 0;
+        self.refresh_results();
     }
 }
 
@@ -108,3 +189,72 @@ impl Default for LauncherState {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn app(name: &str) -> App {
+        App {
+            name: name.to_string(),
+            exec: name.to_lowercase(),
+            description: None,
+            desktop_file: PathBuf::from(format!("/apps/{}.desktop", name.to_lowercase())),
+            terminal: false,
+            icon: None,
+            wm_class: None,
+        }
+    }
+
+    #[test]
+    fn test_empty_query_leads_with_recents() {
+        let discovery = AppDiscovery::from_apps(vec![
+            app("Alacritty"),
+            app("Browser"),
+            app("Calculator"),
+            app("Editor"),
+        ]);
+
+        let now = unix_now();
+        let mut history = FrecencyStore::default();
+        history.record("/apps/editor.desktop", now);
+        history.record("/apps/editor.desktop", now);
+        history.record("/apps/calculator.desktop", now);
+        history.record("/apps/browser.desktop", now - 30 * 24 * 60 * 60);
+
+        let mut launcher = LauncherState::with_discovery(discovery, history);
+        launcher.set_recent_count(2);
+
+        let names: Vec<&str> = launcher.results().iter().map(|app| app.name.as_str()).collect();
+        assert_eq!(names, vec!["Editor", "Calculator", "Alacritty", "Browser"]);
+
+        // A non-empty query uses plain search ranking
+        launcher.set_query("a".to_string());
+        assert_eq!(launcher.results()[0].name, "Alacritty");
+
+        // Clearing the query brings the recents back
+        launcher.reset();
+        assert_eq!(launcher.results()[0].name, "Editor");
+    }
+
+    #[test]
+    fn test_parse_workspace_target() {
+        assert_eq!(parse_workspace_target(":3 firefox"), (Some(3), "firefox"));
+        assert_eq!(parse_workspace_target(":10   term"), (Some(10), "term"));
+        assert_eq!(parse_workspace_target(":2"), (Some(2), ""));
+        assert_eq!(parse_workspace_target("firefox"), (None, "firefox"));
+        assert_eq!(parse_workspace_target(":0 firefox"), (None, ":0 firefox"));
+        assert_eq!(parse_workspace_target(":3firefox"), (None, ":3firefox"));
+        assert_eq!(parse_workspace_target(":web"), (None, ":web"));
+
+        // The target doesn't take part in the search
+        let mut launcher = LauncherState::with_discovery(
+            AppDiscovery::from_apps(vec![app("Browser"), app("Editor")]),
+            FrecencyStore::default(),
+        );
+        launcher.set_query(":4 edit".to_string());
+        assert_eq!(launcher.workspace_target(), Some(4));
+        assert_eq!(launcher.selected_app().map(|app| app.name.as_str()), Some("Editor"));
+    }
+}
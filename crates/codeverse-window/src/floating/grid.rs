@@ -0,0 +1,51 @@
+use crate::tree::Rectangle;
+use codeverse_config::GridSlot;
+
+/// Rectangle a grid slot covers within `area`.
+///
+/// Slots are expressed as column/row spans of an evenly divided grid;
+/// edges are rounded down so adjacent slots (e.g. left and center third)
+/// share an edge without gaps or overlap.
+pub fn grid_slot_rect(slot: GridSlot, area: Rectangle) -> Rectangle {
+    // (first column, column span, columns, first row, row span, rows)
+    let (col, col_span, cols, row, row_span, rows) = match slot {
+        GridSlot::LeftHalf => (0, 1, 2, 0, 1, 1),
+        GridSlot::RightHalf => (1, 1, 2, 0, 1, 1),
+        GridSlot::TopHalf => (0, 1, 1, 0, 1, 2),
+        GridSlot::BottomHalf => (0, 1, 1, 1, 1, 2),
+        GridSlot::LeftThird => (0, 1, 3, 0, 1, 1),
+        GridSlot::CenterThird => (1, 1, 3, 0, 1, 1),
+        GridSlot::RightThird => (2, 1, 3, 0, 1, 1),
+        GridSlot::LeftTwoThirds => (0, 2, 3, 0, 1, 1),
+        GridSlot::RightTwoThirds => (1, 2, 3, 0, 1, 1),
+        GridSlot::Full => (0, 1, 1, 0, 1, 1),
+    };
+
+    let x0 = area.width * col / cols;
+    let x1 = area.width * (col + col_span) / cols;
+    let y0 = area.height * row / rows;
+    let y1 = area.height * (row + row_span) / rows;
+
+    Rectangle::new(area.x + x0 as i32, area.y + y0 as i32, x1 - x0, y1 - y0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_slot_rects() {
+        let area = Rectangle::new(10, 40, 1900, 1000);
+
+        assert_eq!(grid_slot_rect(GridSlot::LeftHalf, area), Rectangle::new(10, 40, 950, 1000));
+        assert_eq!(grid_slot_rect(GridSlot::RightHalf, area), Rectangle::new(960, 40, 950, 1000));
+        assert_eq!(grid_slot_rect(GridSlot::TopHalf, area), Rectangle::new(10, 40, 1900, 500));
+        assert_eq!(grid_slot_rect(GridSlot::BottomHalf, area), Rectangle::new(10, 540, 1900, 500));
+        assert_eq!(grid_slot_rect(GridSlot::LeftThird, area), Rectangle::new(10, 40, 633, 1000));
+        assert_eq!(grid_slot_rect(GridSlot::CenterThird, area), Rectangle::new(643, 40, 633, 1000));
+        assert_eq!(grid_slot_rect(GridSlot::RightThird, area), Rectangle::new(1276, 40, 634, 1000));
+        assert_eq!(grid_slot_rect(GridSlot::LeftTwoThirds, area), Rectangle::new(10, 40, 1266, 1000));
+        assert_eq!(grid_slot_rect(GridSlot::RightTwoThirds, area), Rectangle::new(643, 40, 1267, 1000));
+        assert_eq!(grid_slot_rect(GridSlot::Full, area), area);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::tree::{NodeId, Rectangle, WindowTree};
+use crate::tree::{NodeId, Rectangle, WindowTree, WindowTreeExt};
+use codeverse_config::{FloatingDefaults, FloatingGeometry};
+use std::collections::HashMap;
 
 /// State for mouse-based window operations
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -43,6 +45,25 @@ pub struct FloatingManager {
 
     /// Title bar height
     title_bar_height: u32,
+
+    /// Transient dialogs mapped to their parent window and the parent
+    /// geometry they were last placed against
+    transients: HashMap<NodeId, (NodeId, Rectangle)>,
+
+    /// Per-app geometry windows open at when made floating
+    saved_geometries: FloatingDefaults,
+}
+
+/// How far inside a tiled window a floating window must be dropped to
+/// re-tile it; drops closer to the edge keep the window floating
+pub const DROP_EDGE_MARGIN: i32 = 32;
+
+/// Geometry for a `width`x`height` dialog centered over its parent
+pub fn centered_on_parent(parent: Rectangle, width: u32, height: u32) -> Rectangle {
+    let x = parent.x + (parent.width as i32 - width as i32) / 2;
+    let y = parent.y + (parent.height as i32 - height as i32) / 2;
+
+    Rectangle::new(x, y, width, height)
 }
 
 impl FloatingManager {
@@ -56,6 +77,8 @@ impl FloatingManager {
             default_width: 800,
             default_height: 600,
             title_bar_height: 30,
+            transients: HashMap::new(),
+            saved_geometries: FloatingDefaults::default(),
         }
     }
 
@@ -74,18 +97,32 @@ impl FloatingManager {
             self.make_tiled(tree, window_id)?;
         } else {
             // Make it floating
-            self.make_floating(tree, window_id, screen_geometry)?;
+            self.make_floating(tree, window_id, screen_geometry, false)?;
         }
 
         Ok(())
     }
 
-    /// Make a window floating
+    /// Float a window that was just mapped, on top of the stack. It only
+    /// takes focus with `focus`; otherwise it's raised but focus stays put.
+    pub fn float_new_window(
+        &mut self,
+        tree: &mut WindowTree,
+        window_id: NodeId,
+        screen_geometry: Rectangle,
+        focus: bool,
+    ) -> Result<(), String> {
+        self.make_floating(tree, window_id, screen_geometry, focus)
+    }
+
+    /// Make a window floating. It's always raised to the top of the stack;
+    /// `focus` also moves focus to it.
     fn make_floating(
         &mut self,
         tree: &mut WindowTree,
         window_id: NodeId,
         screen_geometry: Rectangle,
+        focus: bool,
     ) -> Result<(), String> {
         let container = tree.get_mut(window_id)
             .ok_or("Window not found")?;
@@ -94,22 +131,133 @@ impl FloatingManager {
         container.floating_original_geometry = Some(container.geometry);
         container.is_floating = true;
 
-        // Center the window with default size
-        let x = screen_geometry.x + (screen_geometry.width as i32 - self.default_width as i32) / 2;
-        let y = screen_geometry.y + (screen_geometry.height as i32 - self.default_height as i32) / 2;
+        // Open where the app was saved, else centered with the default size
+        let saved = container
+            .app_id
+            .as_deref()
+            .and_then(|app_id| self.saved_geometries.get(app_id));
+        container.geometry = match saved {
+            Some(saved) => Rectangle::new(saved.x, saved.y, saved.width, saved.height),
+            None => {
+                let x = screen_geometry.x + (screen_geometry.width as i32 - self.default_width as i32) / 2;
+                let y = screen_geometry.y + (screen_geometry.height as i32 - self.default_height as i32) / 2;
+
+                Rectangle::new(
+                    x,
+                    y,
+                    self.default_width,
+                    self.default_height,
+                )
+            }
+        };
+
+        // Raise: stacking only, focus is separate
+        self.raise_window(window_id);
+        if focus {
+            tree.set_focused(Some(window_id));
+        }
+
+        Ok(())
+    }
+
+    /// Replace the per-app geometries floating windows open at
+    pub fn set_saved_geometries(&mut self, saved: FloatingDefaults) {
+        self.saved_geometries = saved;
+    }
 
-        container.geometry = Rectangle::new(
-            x,
-            y,
-            self.default_width,
-            self.default_height,
+    /// Per-app geometries floating windows open at
+    pub fn saved_geometries(&self) -> &FloatingDefaults {
+        &self.saved_geometries
+    }
+
+    /// Move a floating window to its app's saved geometry, for windows
+    /// floated before their app ID was known. Returns whether it moved.
+    pub fn apply_saved_geometry(&self, tree: &mut WindowTree, window_id: NodeId) -> bool {
+        let Some(container) = tree.get_mut(window_id) else {
+            return false;
+        };
+        if !container.is_floating {
+            return false;
+        }
+        let Some(saved) = container
+            .app_id
+            .as_deref()
+            .and_then(|app_id| self.saved_geometries.get(app_id))
+        else {
+            return false;
+        };
+
+        container.geometry = Rectangle::new(saved.x, saved.y, saved.width, saved.height);
+        true
+    }
+
+    /// Remember a floating window's current geometry as the one future
+    /// windows of its app open at; returns the app ID
+    pub fn save_default_geometry(&mut self, tree: &WindowTree, window_id: NodeId) -> Result<String, String> {
+        let container = tree.get(window_id)
+            .ok_or("Window not found")?;
+        if !container.is_floating {
+            return Err("Window is not floating".to_string());
+        }
+        let app_id = container.app_id.clone()
+            .ok_or("Window has no app ID")?;
+
+        let geometry = container.geometry;
+        self.saved_geometries.insert(
+            &app_id,
+            FloatingGeometry {
+                x: geometry.x,
+                y: geometry.y,
+                width: geometry.width,
+                height: geometry.height,
+            },
         );
 
-        // Add to stacking order (on top)
-        if !self.stack.contains(&window_id) {
-            self.stack.push(window_id);
+        Ok(app_id)
+    }
+
+    /// Float a window (if it isn't already) and place it at `geometry`
+    pub fn float_at(
+        &mut self,
+        tree: &mut WindowTree,
+        window_id: NodeId,
+        geometry: Rectangle,
+    ) -> Result<(), String> {
+        let container = tree.get_mut(window_id)
+            .ok_or("Window not found")?;
+
+        if !container.is_floating {
+            container.floating_original_geometry = Some(container.geometry);
+            container.is_floating = true;
+        }
+        container.geometry = geometry;
+
+        self.raise_window(window_id);
+
+        Ok(())
+    }
+
+    /// Tear a tiled window out of its container (e.g. a tab out of a
+    /// tabbed group) and float it at the default size, centered on (`x`, `y`)
+    pub fn tear_off(
+        &mut self,
+        tree: &mut WindowTree,
+        window_id: NodeId,
+        x: i32,
+        y: i32,
+    ) -> Result<(), String> {
+        let container = tree.get(window_id)
+            .ok_or("Window not found")?;
+        if container.is_floating {
+            return Err("Window is already floating".to_string());
         }
 
+        tree.detach_to_workspace(window_id)?;
+
+        let geometry = centered_on_parent(Rectangle::new(x, y, 0, 0), self.default_width, self.default_height);
+        self.float_at(tree, window_id, geometry)?;
+        tree.set_focused(Some(window_id));
+
         Ok(())
     }
 
@@ -132,6 +280,118 @@ impl FloatingManager {
 
         // Remove from stacking order
         self.stack.retain(|&id| id != window_id);
+        self.transients.remove(&window_id);
+
+        Ok(())
+    }
+
+    /// Float a transient dialog centered over its parent and stack it above
+    pub fn set_transient(
+        &mut self,
+        tree: &mut WindowTree,
+        window_id: NodeId,
+        parent_id: NodeId,
+    ) -> Result<(), String> {
+        if window_id == parent_id {
+            return Err("Window cannot be its own parent".to_string());
+        }
+
+        let parent_geometry = tree.get(parent_id)
+            .ok_or("Parent window not found")?
+            .geometry;
+        let container = tree.get_mut(window_id)
+            .ok_or("Window not found")?;
+
+        if !container.is_floating {
+            container.floating_original_geometry = Some(container.geometry);
+            container.is_floating = true;
+        }
+
+        // Keep the dialog's own size (the default if it has none yet), but
+        // never larger than the parent
+        let (width, height) = match (container.geometry.width, container.geometry.height) {
+            (0, 0) => (self.default_width, self.default_height),
+            size => size,
+        };
+        let width = width.min(parent_geometry.width).max(self.min_width);
+        let height = height.min(parent_geometry.height).max(self.min_height);
+        container.geometry = centered_on_parent(parent_geometry, width, height);
+
+        self.transients.insert(window_id, (parent_id, parent_geometry));
+        self.raise_window(window_id);
+
+        Ok(())
+    }
+
+    /// Get the parent of a transient dialog
+    pub fn transient_parent(&self, window_id: NodeId) -> Option<NodeId> {
+        self.transients.get(&window_id).map(|&(parent_id, _)| parent_id)
+    }
+
+    /// Move transient dialogs along with their parents.
+    ///
+    /// A dialog keeps its offset from the parent's center, so a dialog the
+    /// user dragged aside stays where they put it relative to the parent.
+    pub fn follow_parents(&mut self, tree: &mut WindowTree) {
+        for (&dialog_id, (parent_id, anchor)) in self.transients.iter_mut() {
+            let Some(parent_geometry) = tree.get(*parent_id).map(|c| c.geometry) else {
+                continue;
+            };
+            if parent_geometry == *anchor {
+                continue;
+            }
+
+            let dx = (parent_geometry.x + parent_geometry.width as i32 / 2) - (anchor.x + anchor.width as i32 / 2);
+            let dy = (parent_geometry.y + parent_geometry.height as i32 / 2) - (anchor.y + anchor.height as i32 / 2);
+
+            if let Some(dialog) = tree.get_mut(dialog_id) {
+                dialog.geometry.x += dx;
+                dialog.geometry.y += dy;
+            }
+            *anchor = parent_geometry;
+        }
+    }
+
+    /// Find the tiled window of a workspace that a floating window dropped
+    /// at (`x`, `y`) should be tiled next to
+    pub fn drop_target(&self, tree: &WindowTree, workspace_id: NodeId, window_id: NodeId, x: i32, y: i32) -> Option<NodeId> {
+        tree.find_windows().into_iter().find(|&id| {
+            if id == window_id || tree.find_workspace(id) != Some(workspace_id) {
+                return false;
+            }
+
+            tree.get(id).is_some_and(|container| {
+                let geom = container.geometry;
+                !container.is_floating
+                    && x >= geom.x + DROP_EDGE_MARGIN
+                    && x < geom.x + geom.width as i32 - DROP_EDGE_MARGIN
+                    && y >= geom.y + DROP_EDGE_MARGIN
+                    && y < geom.y + geom.height as i32 - DROP_EDGE_MARGIN
+            })
+        })
+    }
+
+    /// Tile a floating window again, inserting it right after `target`
+    pub fn drop_into_tile(
+        &mut self,
+        tree: &mut WindowTree,
+        window_id: NodeId,
+        target_id: NodeId,
+    ) -> Result<(), String> {
+        let target_parent = tree.parent(target_id)
+            .ok_or("Drop target has no parent")?;
+
+        self.make_tiled(tree, window_id)?;
+        if let Some(parent_id) = tree.parent(window_id) {
+            tree.remove_child(parent_id, window_id);
+        }
+        tree.insert_child_after(target_parent, target_id, window_id)?;
+
+        if let MouseOperation::Moving { window, .. } = self.operation {
+            if window == window_id {
+                self.operation = MouseOperation::None;
+            }
+        }
 
         Ok(())
     }
@@ -277,17 +537,38 @@ impl FloatingManager {
         self.operation = MouseOperation::None;
     }
 
+    /// Get the topmost transient dialog of a window
+    pub fn transient_dialog(&self, parent_id: NodeId) -> Option<NodeId> {
+        self.stack
+            .iter()
+            .rev()
+            .copied()
+            .find(|&id| self.transient_parent(id) == Some(parent_id))
+    }
+
     /// Get the current operation
     pub fn current_operation(&self) -> MouseOperation {
         self.operation
     }
 
-    /// Raise a window to the top of the stacking order
+    /// Raise a window to the top of the stacking order (its transient
+    /// dialogs stay above it)
     pub fn raise_window(&mut self, window_id: NodeId) {
         // Remove from current position
         self.stack.retain(|&id| id != window_id);
         // Add to top
         self.stack.push(window_id);
+
+        let dialogs: Vec<NodeId> = self
+            .stack
+            .iter()
+            .copied()
+            .filter(|id| self.transient_parent(*id) == Some(window_id))
+            .collect();
+        for dialog_id in dialogs {
+            self.stack.retain(|&id| id != dialog_id);
+            self.stack.push(dialog_id);
+        }
     }
 
     /// Get floating windows in stacking order (bottom to top)
@@ -295,10 +576,24 @@ impl FloatingManager {
         &self.stack
     }
 
+    /// Get the floating windows belonging to a workspace, in that
+    /// workspace's stacking order (bottom to top)
+    pub fn stack_for_workspace(&self, tree: &WindowTree, workspace_id: NodeId) -> Vec<NodeId> {
+        self.stack
+            .iter()
+            .copied()
+            .filter(|&id| tree.find_workspace(id) == Some(workspace_id))
+            .collect()
+    }
+
     /// Remove a window from the floating manager
     pub fn remove_window(&mut self, window_id: NodeId) {
         self.stack.retain(|&id| id != window_id);
 
+        // Forget the window as a dialog and as a parent
+        self.transients.remove(&window_id);
+        self.transients.retain(|_, (parent_id, _)| *parent_id != window_id);
+
         // Cancel operation if it involves this window
         match self.operation {
             MouseOperation::Moving { window, .. } | MouseOperation::Resizing { window, .. }
@@ -309,10 +604,10 @@ impl FloatingManager {
         }
     }
 
-    /// Find the topmost floating window at a given position
-    pub fn find_window_at(&self, tree: &WindowTree, x: i32, y: i32) -> Option<NodeId> {
+    /// Find the topmost floating window of a workspace at a given position
+    pub fn find_window_at(&self, tree: &WindowTree, workspace_id: NodeId, x: i32, y: i32) -> Option<NodeId> {
         // Search from top to bottom (reverse order)
-        for &window_id in self.stack.iter().rev() {
+        for window_id in self.stack_for_workspace(tree, workspace_id).into_iter().rev() {
             if let Some(container) = tree.get(window_id) {
                 if container.is_floating && container.geometry.contains_point(x, y) {
                     return Some(window_id);
@@ -495,4 +790,218 @@ mod tests {
 
         assert_eq!(floating_mgr.stack, vec![id2, id3, id1]);
     }
+
+    #[test]
+    fn test_new_floating_window_raised_without_focus() {
+        let mut tree = WindowTree::new();
+        let mut floating_mgr = FloatingManager::new();
+        let screen = Rectangle::new(0, 0, 1920, 1080);
+
+        let editor = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        let palette = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        floating_mgr.float_new_window(&mut tree, palette, screen, true).unwrap();
+        assert_eq!(tree.focused(), Some(palette));
+
+        // Without focus the new window still goes on top of the stack
+        tree.set_focused(Some(editor));
+        let picker = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        floating_mgr.float_new_window(&mut tree, picker, screen, false).unwrap();
+        assert!(tree.get(picker).unwrap().is_floating);
+        assert_eq!(floating_mgr.get_stack(), &[palette, picker]);
+        assert_eq!(tree.focused(), Some(editor));
+    }
+
+    #[test]
+    fn test_saved_floating_default() {
+        let mut tree = WindowTree::new();
+        let mut floating_mgr = FloatingManager::new();
+        let screen = Rectangle::new(0, 0, 1920, 1080);
+
+        let pavucontrol = || {
+            let mut container = Container::new(NodeId::default(), ContainerType::Window);
+            container.app_id = Some("pavucontrol".to_string());
+            container
+        };
+        let mixer = tree.insert(pavucontrol());
+
+        // Only floating windows with an app ID can be saved
+        assert!(floating_mgr.save_default_geometry(&tree, mixer).is_err());
+        floating_mgr.toggle_floating(&mut tree, mixer, screen).unwrap();
+        tree.get_mut(mixer).unwrap().geometry = Rectangle::new(1400, 60, 480, 640);
+        assert_eq!(floating_mgr.save_default_geometry(&tree, mixer).unwrap(), "pavucontrol");
+        assert_eq!(floating_mgr.saved_geometries().get("pavucontrol").map(|g| g.x), Some(1400));
+
+        // The next window of the app floats where the first one was saved
+        let second = tree.insert(pavucontrol());
+        floating_mgr.toggle_floating(&mut tree, second, screen).unwrap();
+        assert_eq!(tree.get(second).unwrap().geometry, Rectangle::new(1400, 60, 480, 640));
+
+        // Other apps still open centered at the default size
+        let other = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        floating_mgr.toggle_floating(&mut tree, other, screen).unwrap();
+        assert_eq!(tree.get(other).unwrap().geometry, Rectangle::new(560, 240, 800, 600));
+    }
+
+    #[test]
+    fn test_stack_filtered_by_workspace() {
+        let mut tree = WindowTree::new();
+        let mut floating_mgr = FloatingManager::new();
+        let screen = Rectangle::new(0, 0, 1920, 1080);
+
+        let ws1 = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+        let ws2 = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+
+        let mut windows = Vec::new();
+        for workspace_id in [ws1, ws2, ws1] {
+            let window_id = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+            tree.add_child(workspace_id, window_id).unwrap();
+            floating_mgr.toggle_floating(&mut tree, window_id, screen).unwrap();
+            windows.push(window_id);
+        }
+
+        // Raising a window on workspace 2 doesn't disturb workspace 1's order
+        floating_mgr.raise_window(windows[0]);
+        floating_mgr.raise_window(windows[1]);
+
+        assert_eq!(floating_mgr.stack_for_workspace(&tree, ws1), vec![windows[2], windows[0]]);
+        assert_eq!(floating_mgr.stack_for_workspace(&tree, ws2), vec![windows[1]]);
+
+        // Hit-testing only sees the requested workspace's windows
+        assert_eq!(floating_mgr.find_window_at(&tree, ws1, 960, 540), Some(windows[0]));
+        assert_eq!(floating_mgr.find_window_at(&tree, ws2, 960, 540), Some(windows[1]));
+    }
+
+    #[test]
+    fn test_centered_on_parent() {
+        let parent = Rectangle::new(100, 100, 1000, 800);
+        assert_eq!(centered_on_parent(parent, 400, 300), Rectangle::new(400, 350, 400, 300));
+
+        // A dialog larger than its parent overhangs it evenly
+        let small_parent = Rectangle::new(0, 0, 300, 200);
+        assert_eq!(centered_on_parent(small_parent, 500, 400), Rectangle::new(-100, -100, 500, 400));
+    }
+
+    #[test]
+    fn test_transient_follows_and_stays_above_parent() {
+        let mut tree = WindowTree::new();
+        let mut floating_mgr = FloatingManager::new();
+        let screen = Rectangle::new(0, 0, 1920, 1080);
+
+        let parent = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        let other = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        let dialog = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        floating_mgr.toggle_floating(&mut tree, parent, screen).unwrap();
+        tree.get_mut(parent).unwrap().geometry = Rectangle::new(100, 100, 1000, 800);
+
+        floating_mgr.set_transient(&mut tree, dialog, parent).unwrap();
+        assert!(tree.get(dialog).unwrap().is_floating);
+        assert_eq!(tree.get(dialog).unwrap().geometry, Rectangle::new(200, 200, 800, 600));
+        assert_eq!(floating_mgr.transient_parent(dialog), Some(parent));
+        assert_eq!(floating_mgr.transient_dialog(parent), Some(dialog));
+        assert_eq!(floating_mgr.transient_dialog(dialog), None);
+
+        // Raising the parent keeps the dialog on top of it
+        floating_mgr.toggle_floating(&mut tree, other, screen).unwrap();
+        floating_mgr.raise_window(parent);
+        assert_eq!(floating_mgr.get_stack(), &[other, parent, dialog]);
+
+        // Moving the parent drags the dialog along
+        tree.get_mut(parent).unwrap().geometry.x += 50;
+        floating_mgr.follow_parents(&mut tree);
+        assert_eq!(tree.get(dialog).unwrap().geometry.x, 250);
+
+        // Closing the parent forgets the relationship
+        floating_mgr.remove_window(parent);
+        assert_eq!(floating_mgr.transient_parent(dialog), None);
+    }
+
+    #[test]
+    fn test_transient_keeps_its_size() {
+        let mut tree = WindowTree::new();
+        let mut floating_mgr = FloatingManager::new();
+
+        let parent = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        tree.get_mut(parent).unwrap().geometry = Rectangle::new(100, 100, 1000, 800);
+
+        let dialog = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        tree.get_mut(dialog).unwrap().geometry = Rectangle::new(0, 0, 400, 300);
+        floating_mgr.set_transient(&mut tree, dialog, parent).unwrap();
+        assert_eq!(tree.get(dialog).unwrap().geometry, Rectangle::new(400, 350, 400, 300));
+
+        // A dialog larger than its parent is clamped to it
+        let large = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        tree.get_mut(large).unwrap().geometry = Rectangle::new(0, 0, 1600, 500);
+        floating_mgr.set_transient(&mut tree, large, parent).unwrap();
+        assert_eq!(tree.get(large).unwrap().geometry, Rectangle::new(100, 250, 1000, 500));
+    }
+
+    #[test]
+    fn test_drop_floating_window_onto_tile() {
+        let mut tree = WindowTree::new();
+        let mut floating_mgr = FloatingManager::new();
+        let screen = Rectangle::new(0, 0, 1920, 1080);
+
+        let workspace = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+        let mut tiles = Vec::new();
+        for _ in 0..3 {
+            let window_id = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+            tree.add_child(workspace, window_id).unwrap();
+            tiles.push(window_id);
+        }
+        let dragged = tiles.pop().unwrap();
+        tree.get_mut(tiles[0]).unwrap().geometry = Rectangle::new(0, 0, 960, 1080);
+        tree.get_mut(tiles[1]).unwrap().geometry = Rectangle::new(960, 0, 960, 1080);
+        floating_mgr.toggle_floating(&mut tree, dragged, screen).unwrap();
+
+        // Only drops well inside a tiled window pick it as the target
+        assert_eq!(floating_mgr.drop_target(&tree, workspace, dragged, 480, 540), Some(tiles[0]));
+        assert_eq!(floating_mgr.drop_target(&tree, workspace, dragged, 1400, 540), Some(tiles[1]));
+        assert_eq!(floating_mgr.drop_target(&tree, workspace, dragged, 950, 540), None);
+        assert_eq!(floating_mgr.drop_target(&tree, workspace, dragged, 480, 5), None);
+
+        // The window is tiled again, right after the window it was dropped on
+        floating_mgr.start_move(&tree, dragged, 960, 540).unwrap();
+        floating_mgr.drop_into_tile(&mut tree, dragged, tiles[0]).unwrap();
+
+        assert!(!tree.get(dragged).unwrap().is_floating);
+        assert!(floating_mgr.get_stack().is_empty());
+        assert_eq!(tree.children(workspace), &[tiles[0], dragged, tiles[1]]);
+        assert_eq!(floating_mgr.current_operation(), MouseOperation::None);
+    }
+
+    #[test]
+    fn test_tear_off_from_tabbed_group() {
+        let mut tree = WindowTree::new();
+        let mut floating_mgr = FloatingManager::new();
+
+        let workspace = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+        let mut group = Container::new(NodeId::default(), ContainerType::Split);
+        group.layout = crate::LayoutMode::Tabbed;
+        let group = tree.insert(group);
+        tree.add_child(workspace, group).unwrap();
+
+        let tabs: Vec<NodeId> = (0..3)
+            .map(|_| {
+                let tab = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+                tree.add_child(group, tab).unwrap();
+                tab
+            })
+            .collect();
+
+        // The torn-off tab leaves the group and floats around the cursor
+        floating_mgr.tear_off(&mut tree, tabs[1], 500, 400).unwrap();
+        assert_eq!(tree.children(group), vec![tabs[0], tabs[2]]);
+        assert_eq!(tree.parent(tabs[1]), Some(workspace));
+        assert!(tree.get(tabs[1]).unwrap().is_floating);
+        assert_eq!(tree.get(tabs[1]).unwrap().geometry, Rectangle::new(100, 100, 800, 600));
+        assert_eq!(floating_mgr.get_stack(), &[tabs[1]]);
+        assert_eq!(tree.focused(), Some(tabs[1]));
+        assert!(floating_mgr.tear_off(&mut tree, tabs[1], 0, 0).is_err());
+
+        // A group left with one window is dissolved into the workspace
+        floating_mgr.tear_off(&mut tree, tabs[2], 500, 400).unwrap();
+        assert!(tree.get(group).is_none());
+        assert_eq!(tree.children(workspace), vec![tabs[0], tabs[1], tabs[2]]);
+        assert_eq!(tree.parent(tabs[0]), Some(workspace));
+    }
 }
@@ -1,3 +1,5 @@
+pub mod grid;
 pub mod manager;
 
-pub use manager::{FloatingManager, MouseOperation, ResizeEdge};
+pub use grid::grid_slot_rect;
+pub use manager::{centered_on_parent, FloatingManager, MouseOperation, ResizeEdge};
@@ -4,4 +4,4 @@ pub mod tabbed;
 
 pub use split::SplitLayout;
 pub use stack::StackingLayout;
-pub use tabbed::TabbedLayout;
+pub use tabbed::{GroupBar, TabbedLayout};
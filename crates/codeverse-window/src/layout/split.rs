@@ -38,7 +38,7 @@ impl SplitLayout {
             return;
         }
 
-        let child_width = available_width / num_children as i32;
+        let sizes = split_sizes(&locked_ratios(tree, &children), geometry.width as i32, available_width);
         let mut x = geometry.x;
 
         for (i, &child_id) in children.iter().enumerate() {
@@ -46,7 +46,7 @@ impl SplitLayout {
             let width = if i == num_children - 1 {
                 (geometry.x + geometry.width as i32) - x
             } else {
-                child_width
+                sizes[i]
             };
 
             let child_geometry = Rectangle::new(
@@ -88,7 +88,7 @@ impl SplitLayout {
             return;
         }
 
-        let child_height = available_height / num_children as i32;
+        let sizes = split_sizes(&locked_ratios(tree, &children), geometry.height as i32, available_height);
         let mut y = geometry.y;
 
         for (i, &child_id) in children.iter().enumerate() {
@@ -96,7 +96,7 @@ impl SplitLayout {
             let height = if i == num_children - 1 {
                 (geometry.y + geometry.height as i32) - y
             } else {
-                child_height
+                sizes[i]
             };
 
             let child_geometry = Rectangle::new(
@@ -144,6 +144,46 @@ impl SplitLayout {
     }
 }
 
+/// Collect the locked ratios of a list of children
+pub fn locked_ratios(tree: &WindowTree, children: &[NodeId]) -> Vec<Option<f32>> {
+    children
+        .iter()
+        .map(|&id| tree.get(id).and_then(|c| c.locked_ratio))
+        .collect()
+}
+
+/// Divide `available` pixels (the parent's `total` size minus gaps) between
+/// children. Children with a locked ratio get that fraction of `total`;
+/// the rest share the remaining space equally.
+pub fn split_sizes(locked_ratios: &[Option<f32>], total: i32, available: i32) -> Vec<i32> {
+    let available = available.max(0);
+    let mut remaining = available;
+
+    let locked: Vec<Option<i32>> = locked_ratios
+        .iter()
+        .map(|ratio| {
+            ratio.map(|ratio| {
+                let size = ((total as f32) * ratio.clamp(0.0, 1.0)).round() as i32;
+                let size = size.min(remaining);
+                remaining -= size;
+                size
+            })
+        })
+        .collect();
+
+    let unlocked_count = locked.iter().filter(|size| size.is_none()).count() as i32;
+    let shared = if unlocked_count > 0 {
+        remaining / unlocked_count
+    } else {
+        0
+    };
+
+    locked
+        .into_iter()
+        .map(|size| size.unwrap_or(shared))
+        .collect()
+}
+
 impl Default for SplitLayout {
     fn default() -> Self {
         Self::new()
@@ -218,4 +258,17 @@ mod tests {
         assert_eq!(child2_geom.x, 0);
         assert!(child1_geom.y < child2_geom.y);
     }
+
+    #[test]
+    fn test_split_sizes_with_locked_ratio() {
+        // No locks: equal shares
+        assert_eq!(split_sizes(&[None, None], 1000, 1000), vec![500, 500]);
+
+        // Locked child keeps its fraction of the total, others share the rest
+        assert_eq!(split_sizes(&[Some(0.25), None], 1000, 1000), vec![250, 750]);
+        assert_eq!(split_sizes(&[Some(0.25), None, None], 1000, 1000), vec![250, 375, 375]);
+
+        // Locked sizes never exceed the available space
+        assert_eq!(split_sizes(&[Some(0.8), Some(0.8)], 1000, 1000), vec![800, 200]);
+    }
 }
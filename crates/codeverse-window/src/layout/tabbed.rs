@@ -1,4 +1,16 @@
-use crate::tree::{Container, ContainerType, NodeId, Rectangle, WindowTree};
+use crate::tree::{Container, ContainerType, LayoutMode, NodeId, Rectangle, WindowTree};
+use codeverse_config::NordColor;
+
+/// What to draw for a tabbed group's bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroupBar {
+    /// The tabbed container
+    pub container: NodeId,
+    /// Space reserved above the group's windows
+    pub rect: Rectangle,
+    /// Bar background
+    pub background: NordColor,
+}
 
 /// Tabbed layout - shows windows as tabs with only active window visible
 /// Similar to browser tabs, only one window is shown at a time
@@ -65,6 +77,42 @@ impl TabbedLayout {
         }
     }
 
+    /// Bar for a tabbed container, in its own background color or
+    /// `default_background`. Stacking containers reserve no bar space yet.
+    pub fn group_bar(&self, tree: &WindowTree, container_id: NodeId, default_background: NordColor) -> Option<GroupBar> {
+        let container = tree.get(container_id)?;
+        if container.layout != LayoutMode::Tabbed || container.children.is_empty() {
+            return None;
+        }
+
+        let geometry = container.geometry;
+        Some(GroupBar {
+            container: container_id,
+            rect: Rectangle::new(
+                geometry.x,
+                geometry.y,
+                geometry.width,
+                self.tab_bar_height.min(geometry.height),
+            ),
+            background: container.background_color.unwrap_or(default_background),
+        })
+    }
+
+    /// Bars of the tabbed groups holding `windows`, each group once
+    pub fn group_bars(&self, tree: &WindowTree, windows: &[NodeId], default_background: NordColor) -> Vec<GroupBar> {
+        let mut bars: Vec<GroupBar> = Vec::new();
+        for &window_id in windows {
+            let Some(parent_id) = tree.get(window_id).and_then(|c| c.parent) else {
+                continue;
+            };
+            if bars.iter().any(|bar| bar.container == parent_id) {
+                continue;
+            }
+            bars.extend(self.group_bar(tree, parent_id, default_background));
+        }
+        bars
+    }
+
     /// Layout a container based on its layout mode
     fn layout_container(
         &self,
@@ -89,7 +137,6 @@ impl Default for TabbedLayout {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tree::LayoutMode;
 
     #[test]
     fn test_tabbed_layout() {
@@ -120,4 +167,35 @@ mod tests {
             assert_eq!(child_geom.height, 970); // 1000 - 30 (tab bar height)
         }
     }
+
+    #[test]
+    fn test_group_bar_background() {
+        let mut tree = WindowTree::new();
+        let layout = TabbedLayout::new();
+        let nord1 = NordColor::rgb(0x3b, 0x42, 0x52);
+        let red = NordColor::rgb(0xbf, 0x61, 0x6a);
+
+        let mut parent = Container::new(NodeId::default(), ContainerType::Split);
+        parent.layout = LayoutMode::Tabbed;
+        let parent_id = tree.insert(parent);
+        let child_id = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        tree.add_child(parent_id, child_id).unwrap();
+        tree.get_mut(parent_id).unwrap().geometry = Rectangle::new(10, 20, 800, 600);
+
+        // Unset: the default background
+        let bars = layout.group_bars(&tree, &[child_id, child_id], nord1);
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].rect, Rectangle::new(10, 20, 800, 30));
+        assert_eq!(bars[0].background, nord1);
+
+        // Set: the container's own color is passed through
+        tree.get_mut(parent_id).unwrap().background_color = Some(red);
+        let bar = layout.group_bar(&tree, parent_id, nord1).unwrap();
+        assert_eq!(bar.background, red);
+        assert_eq!(bar.container, parent_id);
+
+        // Split containers have no bar
+        tree.get_mut(parent_id).unwrap().layout = LayoutMode::SplitH;
+        assert!(layout.group_bars(&tree, &[child_id], nord1).is_empty());
+    }
 }
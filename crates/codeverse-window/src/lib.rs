@@ -4,10 +4,13 @@ pub mod tree;
 pub mod workspace;
 
 // Re-export commonly used types
-pub use floating::{FloatingManager, MouseOperation, ResizeEdge};
-pub use layout::SplitLayout;
+pub use floating::{centered_on_parent, grid_slot_rect, FloatingManager, MouseOperation, ResizeEdge};
+pub use layout::{GroupBar, SplitLayout, TabbedLayout};
 pub use tree::{
-    Container, ContainerType, Direction, LayoutMode, NodeId, Orientation, Rectangle, WindowHandle,
-    WindowTree, WindowTreeExt,
+    layout_snapshot_text, Container, ContainerType, Direction, LayoutMode, NodeId, Orientation,
+    Rectangle, WindowHandle, WindowTree, WindowTreeExt,
+};
+pub use workspace::{
+    overview_columns, overview_grid, scale_rect_into, switch_outputs_to_workspace, taskbar_button_at,
+    taskbar_buttons, taskbar_strip, WorkspaceManager, MAX_WORKSPACES,
 };
-pub use workspace::{WorkspaceManager, MAX_WORKSPACES};
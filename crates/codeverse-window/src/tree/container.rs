@@ -1,6 +1,9 @@
 use codeverse_config::NordColor;
 use slotmap::{new_key_type, SlotMap};
+use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
+use smithay::utils::{Point, Rectangle as SmithayRectangle};
 use smithay::wayland::shell::xdg::ToplevelSurface;
+use std::time::{Duration, Instant};
 
 /// Handle to a Wayland window surface
 pub type WindowHandle = ToplevelSurface;
@@ -59,6 +62,31 @@ impl LayoutMode {
             _ => None,
         }
     }
+
+    /// Get the config name of this layout mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            LayoutMode::SplitH => "splith",
+            LayoutMode::SplitV => "splitv",
+            LayoutMode::Stacking => "stacking",
+            LayoutMode::Tabbed => "tabbed",
+        }
+    }
+}
+
+impl std::str::FromStr for LayoutMode {
+    type Err = String;
+
+    /// Parse a layout mode from its config name ("splith", "splitv", "stacking", "tabbed")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "splith" => Ok(LayoutMode::SplitH),
+            "splitv" => Ok(LayoutMode::SplitV),
+            "stacking" => Ok(LayoutMode::Stacking),
+            "tabbed" => Ok(LayoutMode::Tabbed),
+            _ => Err(format!("Unknown layout mode: {}", s)),
+        }
+    }
 }
 
 /// Rectangle for geometry calculations
@@ -81,6 +109,43 @@ impl Rectangle {
             && y >= self.y
             && y < self.y + self.height as i32
     }
+
+    /// Whether the rectangle has no area (e.g. an output with a zero-size mode)
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// The part of this rectangle inside `other` (`None` if they don't
+    /// overlap), e.g. the on-screen part of a window
+    pub fn intersection(&self, other: Rectangle) -> Option<Rectangle> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width as i32).min(other.x + other.width as i32);
+        let bottom = (self.y + self.height as i32).min(other.y + other.height as i32);
+
+        if right <= x || bottom <= y {
+            return None;
+        }
+        Some(Rectangle::new(x, y, (right - x) as u32, (bottom - y) as u32))
+    }
+
+    /// Top-left corner as a smithay point (logical or physical)
+    pub fn loc<Kind>(&self) -> Point<i32, Kind> {
+        Point::from((self.x, self.y))
+    }
+}
+
+impl<Kind> From<Rectangle> for SmithayRectangle<i32, Kind> {
+    fn from(rect: Rectangle) -> Self {
+        SmithayRectangle::from_loc_and_size((rect.x, rect.y), (rect.width as i32, rect.height as i32))
+    }
+}
+
+/// Negative sizes (never produced by smithay for valid input) become empty
+impl<Kind> From<SmithayRectangle<i32, Kind>> for Rectangle {
+    fn from(rect: SmithayRectangle<i32, Kind>) -> Self {
+        Rectangle::new(rect.loc.x, rect.loc.y, rect.size.w.max(0) as u32, rect.size.h.max(0) as u32)
+    }
 }
 
 /// A node in the container tree
@@ -109,9 +174,22 @@ pub struct Container {
     /// Border width (in pixels)
     pub border_width: u32,
 
+    /// Border width set by a matching window rule, overriding the global default
+    pub border_width_override: Option<u32>,
+
+    /// Opacity set by a matching window rule (the theme's opacity if unset)
+    pub opacity: Option<f32>,
+
+    /// Size steps (e.g. a terminal's character cell) the client wants to be
+    /// sized in. xdg-shell has no such hint, so this comes from window rules.
+    pub resize_increment: Option<(u32, u32)>,
+
     /// Current border color
     pub border_color: NordColor,
 
+    /// Background behind a tabbed/stacking group's bar (theme default if unset)
+    pub background_color: Option<NordColor>,
+
     /// Window data (only for ContainerType::Window)
     pub window: Option<WindowHandle>,
 
@@ -124,11 +202,24 @@ pub struct Container {
     /// Is this a floating window?
     pub is_floating: bool,
 
+    /// Is this window fullscreen? (tiled windows only; covers the workspace)
+    pub is_fullscreen: bool,
+
     /// Original geometry before floating (for toggle back)
     pub floating_original_geometry: Option<Rectangle>,
 
     /// Last size sent to the client via send_configure (to avoid spamming)
     pub last_configured_size: Option<(u32, u32)>,
+
+    /// When the client was first configured
+    pub first_configured_at: Option<Instant>,
+
+    /// Has the client ever committed a buffer?
+    pub has_buffer: bool,
+
+    /// Fixed fraction (0.0-1.0) of the parent split this container occupies.
+    /// Unlocked siblings share whatever space remains.
+    pub locked_ratio: Option<f32>,
 }
 
 impl Container {
@@ -143,13 +234,21 @@ impl Container {
             layout: LayoutMode::SplitH,
             focused: false,
             border_width: 2,
+            border_width_override: None,
+            opacity: None,
+            resize_increment: None,
             border_color: NordColor::rgb(0x4c, 0x56, 0x6a), // nord3
+            background_color: None,
             window: None,
             title: None,
             app_id: None,
             is_floating: false,
+            is_fullscreen: false,
             floating_original_geometry: None,
             last_configured_size: None,
+            first_configured_at: None,
+            has_buffer: false,
+            locked_ratio: None,
         }
     }
 
@@ -166,6 +265,33 @@ impl Container {
         self.children.is_empty()
     }
 
+    /// Whether the window was configured more than `timeout` ago and still
+    /// has no buffer (a zero timeout never reaps)
+    pub fn should_reap(&self, now: Instant, timeout: Duration) -> bool {
+        !timeout.is_zero()
+            && !self.has_buffer
+            && self
+                .first_configured_at
+                .is_some_and(|configured_at| now.saturating_duration_since(configured_at) >= timeout)
+    }
+
+    /// Size to configure the client with: `size` snapped down to whole
+    /// resize increments (but at least one), so no partial cell is left over
+    pub fn snapped_size(&self, (width, height): (u32, u32)) -> (u32, u32) {
+        let snap = |length: u32, step: u32| {
+            if step == 0 || length < step {
+                length
+            } else {
+                length - length % step
+            }
+        };
+
+        match self.resize_increment {
+            Some((step_x, step_y)) => (snap(width, step_x), snap(height, step_y)),
+            None => (width, height),
+        }
+    }
+
     /// Set the border color based on focus state and theme
     pub fn update_border_color(&mut self, focused_color: NordColor, unfocused_color: NordColor) {
         self.border_color = if self.focused {
@@ -186,6 +312,9 @@ pub struct WindowTree {
 
     /// Currently focused node
     focused: Option<NodeId>,
+
+    /// Recently focused windows, most recent first
+    focus_history: Vec<NodeId>,
 }
 
 impl WindowTree {
@@ -195,6 +324,7 @@ impl WindowTree {
             nodes: SlotMap::with_key(),
             root: None,
             focused: None,
+            focus_history: Vec::new(),
         }
     }
 
@@ -215,6 +345,7 @@ impl WindowTree {
 
     /// Remove a container by ID
     pub fn remove(&mut self, id: NodeId) -> Option<Container> {
+        self.focus_history.retain(|&window_id| window_id != id);
         self.nodes.remove(id)
     }
 
@@ -242,6 +373,12 @@ impl WindowTree {
         if let Some(new_focused) = id {
             if let Some(container) = self.nodes.get_mut(new_focused) {
                 container.focused = true;
+
+                // Move windows to the front of the focus history
+                if container.container_type == ContainerType::Window {
+                    self.focus_history.retain(|&window_id| window_id != new_focused);
+                    self.focus_history.insert(0, new_focused);
+                }
             }
         }
     }
@@ -251,6 +388,19 @@ impl WindowTree {
         self.focused
     }
 
+    /// Recently focused windows, most recent first
+    pub fn focus_history(&self) -> &[NodeId] {
+        &self.focus_history
+    }
+
+    /// Most recently focused window other than the current focus
+    pub fn previous_focused(&self) -> Option<NodeId> {
+        self.focus_history
+            .iter()
+            .copied()
+            .find(|&window_id| Some(window_id) != self.focused)
+    }
+
     /// Add a child to a container
     pub fn add_child(&mut self, parent_id: NodeId, child_id: NodeId) -> Result<(), String> {
         // Check if parent can have children
@@ -278,6 +428,21 @@ impl WindowTree {
         Ok(())
     }
 
+    /// Insert a child into `parent_id` right after `sibling_id` (at the end
+    /// if the sibling isn't one of its children)
+    pub fn insert_child_after(&mut self, parent_id: NodeId, sibling_id: NodeId, child_id: NodeId) -> Result<(), String> {
+        self.add_child(parent_id, child_id)?;
+
+        if let Some(parent) = self.nodes.get_mut(parent_id) {
+            if let Some(index) = parent.children.iter().position(|&id| id == sibling_id) {
+                parent.children.pop();
+                parent.children.insert(index + 1, child_id);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Remove a child from its parent
     pub fn remove_child(&mut self, parent_id: NodeId, child_id: NodeId) {
         if let Some(parent) = self.nodes.get_mut(parent_id) {
@@ -300,11 +465,16 @@ impl WindowTree {
 
     /// Find a window by its ToplevelSurface
     pub fn find_window_by_handle(&self, window: &WindowHandle) -> Option<NodeId> {
+        self.find_window_by_surface(window.wl_surface())
+    }
+
+    /// Find a window by its toplevel wl_surface
+    pub fn find_window_by_surface(&self, surface: &WlSurface) -> Option<NodeId> {
         self.nodes
             .iter()
             .find(|(_, container)| {
                 if let Some(ref handle) = container.window {
-                    handle.wl_surface() == window.wl_surface()
+                    handle.wl_surface() == surface
                 } else {
                     false
                 }
@@ -339,6 +509,83 @@ impl Default for WindowTree {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use smithay::utils::{Logical, Physical};
+
+    #[test]
+    fn test_rectangle_smithay_conversions() {
+        let rect = Rectangle::new(-20, 40, 800, 600);
+
+        let logical: SmithayRectangle<i32, Logical> = rect.into();
+        assert_eq!(logical, SmithayRectangle::from_loc_and_size((-20, 40), (800, 600)));
+        assert_eq!(Rectangle::from(logical), rect);
+
+        let physical: SmithayRectangle<i32, Physical> = rect.into();
+        assert_eq!(Rectangle::from(physical), rect);
+
+        let loc: Point<i32, Logical> = rect.loc();
+        assert_eq!(loc, Point::from((-20, 40)));
+
+        let negative: SmithayRectangle<i32, Logical> = SmithayRectangle::from_loc_and_size((5, 5), (-10, 3));
+        assert_eq!(Rectangle::from(negative), Rectangle::new(5, 5, 0, 3));
+    }
+
+    #[test]
+    fn test_rectangle_intersection() {
+        let output = Rectangle::new(0, 0, 1920, 1080);
+
+        // A window hanging off the right edge is clamped to the output width
+        let window = Rectangle::new(1600, 100, 800, 600);
+        assert_eq!(window.intersection(output), Some(Rectangle::new(1600, 100, 320, 600)));
+
+        // Fully inside: unchanged; the operation is symmetric
+        let inside = Rectangle::new(10, 20, 300, 200);
+        assert_eq!(inside.intersection(output), Some(inside));
+        assert_eq!(output.intersection(window), window.intersection(output));
+
+        // Off the top-left corner of an output placed to the right
+        let second = Rectangle::new(1920, 0, 1280, 1024);
+        assert_eq!(Rectangle::new(1800, -50, 400, 200).intersection(second), Some(Rectangle::new(1920, 0, 280, 150)));
+
+        // Entirely off the output, or just touching its edge
+        assert_eq!(Rectangle::new(2000, 0, 100, 100).intersection(output), None);
+        assert_eq!(Rectangle::new(1920, 0, 100, 100).intersection(output), None);
+        assert_eq!(Rectangle::new(100, 100, 0, 50).intersection(output), None);
+    }
+
+    #[test]
+    fn test_snapped_size() {
+        let mut terminal = Container::new(NodeId::default(), ContainerType::Window);
+        assert_eq!(terminal.snapped_size((955, 1058)), (955, 1058));
+
+        // 9x18 character cells
+        terminal.resize_increment = Some((9, 18));
+        assert_eq!(terminal.snapped_size((955, 1058)), (954, 1044));
+        assert_eq!(terminal.snapped_size((954, 1044)), (954, 1044));
+
+        // Never below a single cell, and a zero step is ignored
+        assert_eq!(terminal.snapped_size((5, 10)), (5, 10));
+        terminal.resize_increment = Some((0, 18));
+        assert_eq!(terminal.snapped_size((955, 1058)), (955, 1044));
+    }
+
+    #[test]
+    fn test_should_reap() {
+        let timeout = Duration::from_secs(10);
+        let configured_at = Instant::now();
+        let mut container = Container::new(NodeId::default(), ContainerType::Window);
+
+        // Never configured: nothing to wait for
+        assert!(!container.should_reap(configured_at + timeout, timeout));
+
+        container.first_configured_at = Some(configured_at);
+        assert!(!container.should_reap(configured_at + Duration::from_secs(9), timeout));
+        assert!(container.should_reap(configured_at + timeout, timeout));
+        assert!(!container.should_reap(configured_at + timeout, Duration::ZERO));
+
+        // A committed buffer keeps the window no matter how long it took
+        container.has_buffer = true;
+        assert!(!container.should_reap(configured_at + Duration::from_secs(60), timeout));
+    }
 
     #[test]
     fn test_create_tree() {
@@ -362,4 +609,27 @@ mod tests {
         assert_eq!(tree.focused(), Some(id1));
         assert!(tree.get(id1).unwrap().focused);
     }
+
+    #[test]
+    fn test_focus_history() {
+        let mut tree = WindowTree::new();
+        let a = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        let b = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        let workspace = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+
+        tree.set_focused(Some(a));
+        tree.set_focused(Some(b));
+        assert_eq!(tree.focus_history(), &[b, a]);
+        assert_eq!(tree.previous_focused(), Some(a));
+
+        // Refocusing moves a window to the front; non-windows aren't recorded
+        tree.set_focused(Some(a));
+        tree.set_focused(Some(workspace));
+        assert_eq!(tree.focus_history(), &[a, b]);
+        assert_eq!(tree.previous_focused(), Some(a));
+
+        // Closed windows are pruned
+        tree.remove(a);
+        assert_eq!(tree.focus_history(), &[b]);
+    }
 }
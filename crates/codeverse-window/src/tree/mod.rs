@@ -1,7 +1,9 @@
 pub mod container;
+pub mod snapshot;
 pub mod tree;
 
 pub use container::{
     Container, ContainerType, LayoutMode, NodeId, Orientation, Rectangle, WindowTree, WindowHandle,
 };
+pub use snapshot::layout_snapshot_text;
 pub use tree::{Direction, WindowTreeExt};
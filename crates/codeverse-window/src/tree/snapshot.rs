@@ -0,0 +1,120 @@
+use super::container::{ContainerType, NodeId, WindowTree};
+use std::fmt::Write;
+
+/// Produce a deterministic text dump of a workspace's computed geometry.
+///
+/// Each line describes one node, indented by depth:
+/// `<kind> [layout] [app_id] [floating] <x>,<y> <width>x<height>`.
+/// Node IDs are omitted so the output is stable across runs, which makes
+/// it suitable for golden-file layout regression tests.
+pub fn layout_snapshot_text(tree: &WindowTree, workspace_id: NodeId) -> String {
+    let mut out = String::new();
+    write_node(tree, workspace_id, 0, &mut out);
+    out
+}
+
+fn write_node(tree: &WindowTree, node_id: NodeId, depth: usize, out: &mut String) {
+    let Some(node) = tree.get(node_id) else {
+        return;
+    };
+
+    let mut line = format!("{:indent$}", "", indent = depth * 2);
+
+    match node.container_type {
+        ContainerType::Window => {
+            line.push_str("window");
+            if let Some(ref app_id) = node.app_id {
+                let _ = write!(line, " {}", app_id);
+            }
+            if node.is_floating {
+                line.push_str(" floating");
+            }
+        }
+        other => {
+            let _ = write!(line, "{} {}", kind_name(other), node.layout.name());
+        }
+    }
+
+    let geom = node.geometry;
+    let _ = writeln!(out, "{} {},{} {}x{}", line, geom.x, geom.y, geom.width, geom.height);
+
+    for &child_id in &node.children {
+        write_node(tree, child_id, depth + 1, out);
+    }
+}
+
+fn kind_name(container_type: ContainerType) -> &'static str {
+    match container_type {
+        ContainerType::Root => "root",
+        ContainerType::Output => "output",
+        ContainerType::Workspace => "workspace",
+        ContainerType::Split => "split",
+        ContainerType::Stacked => "stacked",
+        ContainerType::Tabbed => "tabbed",
+        ContainerType::Window => "window",
+        ContainerType::Floating => "floating",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::{Container, LayoutMode, Rectangle, WindowTreeExt};
+    use std::path::PathBuf;
+
+    fn golden_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("golden")
+            .join(name)
+    }
+
+    /// Compare against a golden file; set UPDATE_GOLDEN=1 to regenerate it
+    fn assert_golden(name: &str, actual: &str) {
+        let path = golden_path(name);
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&path, actual).unwrap();
+            return;
+        }
+
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("Failed to read golden file {:?}: {}", path, e));
+        assert_eq!(actual, expected, "Layout snapshot differs from {:?}", path);
+    }
+
+    fn open_window(tree: &mut WindowTree, workspace_id: NodeId, app_id: &str) -> NodeId {
+        let mut window = Container::new(NodeId::default(), ContainerType::Window);
+        window.app_id = Some(app_id.to_string());
+        let window_id = tree.insert(window);
+        tree.add_child(workspace_id, window_id).unwrap();
+        tree.set_focused(Some(window_id));
+        window_id
+    }
+
+    #[test]
+    fn test_three_windows_splith_then_splitv() {
+        let mut tree = WindowTree::new();
+        let mut workspace = Container::new(NodeId::default(), ContainerType::Workspace);
+        workspace.layout = LayoutMode::SplitH;
+        let workspace_id = tree.insert(workspace);
+
+        let screen = Rectangle::new(0, 0, 1920, 1080);
+        let gap_width = 10;
+
+        for app_id in ["editor", "browser", "terminal"] {
+            open_window(&mut tree, workspace_id, app_id);
+        }
+
+        let mut snapshot = String::from("# splith\n");
+        tree.calculate_layout(workspace_id, screen, gap_width);
+        snapshot.push_str(&layout_snapshot_text(&tree, workspace_id));
+
+        tree.change_layout(LayoutMode::SplitV).unwrap();
+        tree.calculate_layout(workspace_id, screen, gap_width);
+        snapshot.push_str("# splitv\n");
+        snapshot.push_str(&layout_snapshot_text(&tree, workspace_id));
+
+        assert_golden("three_windows_splith_splitv.txt", &snapshot);
+    }
+}
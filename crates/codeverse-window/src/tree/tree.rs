@@ -1,4 +1,6 @@
 use super::container::{Container, ContainerType, LayoutMode, NodeId, Orientation, Rectangle, WindowHandle, WindowTree};
+use crate::layout::split::{locked_ratios, split_sizes};
+use codeverse_config::PresetNode;
 use tracing::{debug, warn};
 
 /// Direction for navigation
@@ -30,6 +32,11 @@ pub trait WindowTreeExt {
     /// Insert a new window into the tree at the focused location
     fn insert_window(&mut self, window: WindowHandle, workspace_id: NodeId) -> Result<NodeId, String>;
 
+    /// Container a new window on `workspace_id` goes into: the focused
+    /// tiled window's parent, the focused container itself, or the
+    /// workspace when focus is on a floating window or another workspace
+    fn insertion_target(&self, workspace_id: NodeId) -> NodeId;
+
     /// Remove a window from the tree
     fn remove_window(&mut self, window_id: NodeId) -> Result<(), String>;
 
@@ -50,6 +57,26 @@ pub trait WindowTreeExt {
 
     /// Change the layout mode of the focused container's parent
     fn change_layout(&mut self, layout: LayoutMode) -> Result<(), String>;
+
+    /// Wrap the focused container in a new container with the given layout,
+    /// taking its place in the parent so later splits nest inside it
+    fn wrap_in_container(&mut self, layout: LayoutMode) -> Result<NodeId, String>;
+
+    /// Dissolve the focused container's parent when it holds only that
+    /// container, moving the container up into the grandparent
+    fn unwrap_container(&mut self) -> Result<(), String>;
+
+    /// Move a window out of its container to the top level of its
+    /// workspace; a container left with a single child (or none) is
+    /// dissolved
+    fn detach_to_workspace(&mut self, window_id: NodeId) -> Result<NodeId, String>;
+
+    /// Capture the tiled structure of a workspace as a layout preset
+    fn snapshot_preset(&self, workspace_id: NodeId) -> PresetNode;
+
+    /// Rebuild a workspace's tiled structure from a preset, placing the
+    /// current windows into the preset's slots by app_id
+    fn apply_preset(&mut self, workspace_id: NodeId, preset: &PresetNode) -> Result<(), String>;
 }
 
 impl WindowTreeExt for WindowTree {
@@ -65,23 +92,7 @@ impl WindowTreeExt for WindowTree {
         };
 
         // Get current focus to determine where to insert
-        let insert_target = if let Some(focused_id) = self.focused() {
-            // If we have a focused window, insert next to it
-            if let Some(focused) = self.get(focused_id) {
-                if focused.container_type == ContainerType::Window {
-                    // Insert as sibling of focused window
-                    focused.parent.unwrap_or(workspace_id)
-                } else {
-                    // Insert into focused container
-                    focused_id
-                }
-            } else {
-                workspace_id
-            }
-        } else {
-            // No focus, insert directly into workspace
-            workspace_id
-        };
+        let insert_target = self.insertion_target(workspace_id);
 
         // Check if target has children
         let children_count = self.children(insert_target).len();
@@ -104,6 +115,30 @@ impl WindowTreeExt for WindowTree {
         Ok(window_id)
     }
 
+    fn insertion_target(&self, workspace_id: NodeId) -> NodeId {
+        let Some(focused_id) = self.focused() else {
+            // No focus, insert directly into workspace
+            return workspace_id;
+        };
+        let Some(focused) = self.get(focused_id) else {
+            return workspace_id;
+        };
+
+        // Focus on another workspace says nothing about this one, and a
+        // floating window's parent is just its workspace
+        if self.find_workspace(focused_id) != Some(workspace_id) || focused.is_floating {
+            return workspace_id;
+        }
+
+        if focused.container_type == ContainerType::Window {
+            // Insert as sibling of focused window
+            focused.parent.unwrap_or(workspace_id)
+        } else {
+            // Insert into focused container
+            focused_id
+        }
+    }
+
     fn remove_window(&mut self, window_id: NodeId) -> Result<(), String> {
         debug!("Removing window {:?} from tree", window_id);
 
@@ -207,6 +242,12 @@ impl WindowTreeExt for WindowTree {
     fn calculate_layout(&mut self, workspace_id: NodeId, screen_geometry: Rectangle, gap_width: i32) {
         debug!("Calculating layout for workspace {:?} with gap_width {}", workspace_id, gap_width);
 
+        // Nothing sensible to lay out on a zero-size output; keep the last geometry
+        if screen_geometry.is_empty() {
+            debug!("Skipping layout for empty geometry {:?}", screen_geometry);
+            return;
+        }
+
         // Set workspace geometry
         if let Some(workspace) = self.get_mut(workspace_id) {
             workspace.geometry = screen_geometry;
@@ -214,6 +255,21 @@ impl WindowTreeExt for WindowTree {
 
         // Recursively layout children
         self.layout_container(workspace_id, screen_geometry, gap_width);
+
+        // Fullscreen windows cover the whole workspace, without gaps
+        let fullscreen: Vec<NodeId> = self
+            .find_windows()
+            .into_iter()
+            .filter(|&id| {
+                self.get(id).is_some_and(|c| c.is_fullscreen && !c.is_floating)
+                    && self.find_workspace(id) == Some(workspace_id)
+            })
+            .collect();
+        for window_id in fullscreen {
+            if let Some(window) = self.get_mut(window_id) {
+                window.geometry = screen_geometry;
+            }
+        }
     }
 
     fn find_workspace(&self, mut node_id: NodeId) -> Option<NodeId> {
@@ -262,9 +318,279 @@ impl WindowTreeExt for WindowTree {
 
         Ok(())
     }
+
+    fn wrap_in_container(&mut self, layout: LayoutMode) -> Result<NodeId, String> {
+        let focused_id = self.focused().ok_or("No focused container")?;
+        let focused = self.get(focused_id).ok_or("Focused container not found")?;
+
+        if matches!(
+            focused.container_type,
+            ContainerType::Root | ContainerType::Output | ContainerType::Workspace
+        ) {
+            return Err(format!("Cannot wrap a {:?} container", focused.container_type));
+        }
+
+        let parent_id = focused.parent.ok_or("Focused container has no parent")?;
+        let geometry = focused.geometry;
+        let locked_ratio = focused.locked_ratio;
+
+        debug!("Wrapping {:?} in a new {:?} container", focused_id, layout);
+
+        // The wrapper takes over the focused container's slot and share of the parent
+        let wrapper_id = {
+            let mut wrapper = Container::new(NodeId::default(), ContainerType::Split);
+            wrapper.layout = layout;
+            wrapper.geometry = geometry;
+            wrapper.locked_ratio = locked_ratio;
+            wrapper.parent = Some(parent_id);
+            wrapper.children = vec![focused_id];
+            self.insert(wrapper)
+        };
+
+        if let Some(parent) = self.get_mut(parent_id) {
+            for child in parent.children.iter_mut() {
+                if *child == focused_id {
+                    *child = wrapper_id;
+                }
+            }
+        }
+
+        if let Some(focused) = self.get_mut(focused_id) {
+            focused.parent = Some(wrapper_id);
+            focused.locked_ratio = None;
+        }
+
+        Ok(wrapper_id)
+    }
+
+    fn unwrap_container(&mut self) -> Result<(), String> {
+        let focused_id = self.focused().ok_or("No focused container")?;
+        let wrapper_id = self.parent(focused_id).ok_or("Focused container has no parent")?;
+        let wrapper = self.get(wrapper_id).ok_or("Parent not found")?;
+
+        if wrapper.container_type != ContainerType::Split {
+            return Err(format!("Cannot unwrap a {:?} container", wrapper.container_type));
+        }
+        if wrapper.children.len() != 1 {
+            return Err("Parent container holds more than one child".to_string());
+        }
+
+        if wrapper.parent.is_none() {
+            return Err("Parent container has no parent".to_string());
+        }
+
+        debug!("Unwrapping {:?} out of {:?}", focused_id, wrapper_id);
+        self.collapse_container(wrapper_id);
+        Ok(())
+    }
+
+    fn detach_to_workspace(&mut self, window_id: NodeId) -> Result<NodeId, String> {
+        let workspace_id = self.find_workspace(window_id).ok_or("Window is not on a workspace")?;
+        let parent_id = self.parent(window_id).ok_or("Window has no parent")?;
+
+        debug!("Detaching {:?} from {:?} to workspace {:?}", window_id, parent_id, workspace_id);
+
+        self.remove_child(parent_id, window_id);
+        self.add_child(workspace_id, window_id)?;
+        if let Some(window) = self.get_mut(window_id) {
+            window.locked_ratio = None;
+        }
+
+        let left_behind = self.get(parent_id).map(|c| (c.container_type, c.children.len()));
+        if matches!(left_behind, Some((ContainerType::Split, 0 | 1))) {
+            self.collapse_container(parent_id);
+        }
+
+        Ok(workspace_id)
+    }
+
+    fn snapshot_preset(&self, workspace_id: NodeId) -> PresetNode {
+        let layout = self
+            .get(workspace_id)
+            .map(|c| c.layout)
+            .unwrap_or(LayoutMode::SplitH);
+
+        PresetNode::container(layout.name(), self.snapshot_children(workspace_id))
+    }
+
+    fn apply_preset(&mut self, workspace_id: NodeId, preset: &PresetNode) -> Result<(), String> {
+        if preset.is_window() {
+            return Err("Preset root must be a container".to_string());
+        }
+
+        debug!("Applying layout preset to workspace {:?}", workspace_id);
+
+        // Detach every window from the workspace and drop the old containers
+        let mut available = Vec::new();
+        for child_id in self.children(workspace_id) {
+            self.detach_subtree(child_id, &mut available);
+        }
+        if let Some(workspace) = self.get_mut(workspace_id) {
+            workspace.children.clear();
+        }
+
+        // Floating windows keep their place directly under the workspace
+        let floating: Vec<NodeId> = available
+            .iter()
+            .copied()
+            .filter(|&id| self.get(id).map(|c| c.is_floating).unwrap_or(false))
+            .collect();
+        available.retain(|id| !floating.contains(id));
+
+        if let Some(layout) = preset.layout.as_deref().and_then(|name| name.parse().ok()) {
+            if let Some(workspace) = self.get_mut(workspace_id) {
+                workspace.layout = layout;
+            }
+        }
+
+        self.build_from_preset(workspace_id, &preset.children, &mut available)?;
+
+        // Windows the preset had no slot for go to the end of the workspace
+        for window_id in available.into_iter().chain(floating) {
+            self.add_child(workspace_id, window_id)?;
+        }
+
+        // Focus may have pointed at one of the removed containers
+        let focus_valid = self.focused().map(|id| self.get(id).is_some()).unwrap_or(false);
+        if !focus_valid {
+            let target = self.first_focusable_descendant(workspace_id);
+            self.set_focused(target);
+        }
+
+        Ok(())
+    }
 }
 
 impl WindowTree {
+    /// Replace a container holding at most one child with that child (or
+    /// drop it when empty); the child takes over the container's slot
+    fn collapse_container(&mut self, container_id: NodeId) {
+        let Some(container) = self.get(container_id) else {
+            return;
+        };
+        let Some(grandparent_id) = container.parent else {
+            return;
+        };
+        let only_child = container.children.first().copied();
+        let locked_ratio = container.locked_ratio;
+
+        if let Some(grandparent) = self.get_mut(grandparent_id) {
+            match only_child {
+                Some(child_id) => {
+                    for child in grandparent.children.iter_mut() {
+                        if *child == container_id {
+                            *child = child_id;
+                        }
+                    }
+                }
+                None => grandparent.children.retain(|&id| id != container_id),
+            }
+        }
+
+        if let Some(child) = only_child.and_then(|child_id| self.get_mut(child_id)) {
+            child.parent = Some(grandparent_id);
+            child.locked_ratio = locked_ratio;
+        }
+
+        if self.focused() == Some(container_id) {
+            self.set_focused(only_child.or(Some(grandparent_id)));
+        }
+        self.remove(container_id);
+    }
+
+    /// Build preset nodes for the tiled children of a container
+    fn snapshot_children(&self, container_id: NodeId) -> Vec<PresetNode> {
+        let mut nodes = Vec::new();
+
+        for child_id in self.children(container_id) {
+            let Some(child) = self.get(child_id) else {
+                continue;
+            };
+
+            if child.container_type == ContainerType::Window {
+                if !child.is_floating {
+                    nodes.push(PresetNode::window(child.app_id.as_deref()).with_locked_ratio(child.locked_ratio));
+                }
+            } else {
+                let children = self.snapshot_children(child_id);
+                if !children.is_empty() {
+                    nodes.push(PresetNode::container(child.layout.name(), children).with_locked_ratio(child.locked_ratio));
+                }
+            }
+        }
+
+        nodes
+    }
+
+    /// Detach all windows below a node, removing the intermediate containers
+    fn detach_subtree(&mut self, node_id: NodeId, windows: &mut Vec<NodeId>) {
+        let Some(node) = self.get(node_id) else {
+            return;
+        };
+
+        if node.container_type == ContainerType::Window {
+            if let Some(node) = self.get_mut(node_id) {
+                node.parent = None;
+            }
+            windows.push(node_id);
+            return;
+        }
+
+        for child_id in self.children(node_id) {
+            self.detach_subtree(child_id, windows);
+        }
+        self.remove(node_id);
+    }
+
+    /// Recreate preset containers under `parent_id`, filling window slots
+    /// from `available` (matching app_id first, then any window for open slots)
+    fn build_from_preset(
+        &mut self,
+        parent_id: NodeId,
+        nodes: &[PresetNode],
+        available: &mut Vec<NodeId>,
+    ) -> Result<(), String> {
+        for node in nodes {
+            if node.is_window() {
+                let index = match node.app_id {
+                    Some(ref app_id) => available.iter().position(|&id| {
+                        self.get(id).and_then(|c| c.app_id.as_ref()) == Some(app_id)
+                    }),
+                    None if !available.is_empty() => Some(0),
+                    None => None,
+                };
+
+                if let Some(index) = index {
+                    let window_id = available.remove(index);
+                    self.add_child(parent_id, window_id)?;
+                    if let Some(window) = self.get_mut(window_id) {
+                        window.locked_ratio = node.locked_ratio;
+                    }
+                }
+            } else {
+                let mut split = Container::new(NodeId::default(), ContainerType::Split);
+                split.layout = node
+                    .layout
+                    .as_deref()
+                    .and_then(|name| name.parse().ok())
+                    .unwrap_or(LayoutMode::SplitH);
+                split.locked_ratio = node.locked_ratio;
+                let split_id = self.insert(split);
+                self.add_child(parent_id, split_id)?;
+
+                self.build_from_preset(split_id, &node.children, available)?;
+
+                // Drop containers that received no windows
+                if self.children(split_id).is_empty() {
+                    self.remove_child(parent_id, split_id);
+                    self.remove(split_id);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Navigate focus recursively up the tree
     fn navigate_focus_recursive(&mut self, current: NodeId, direction: Direction) -> Option<NodeId> {
         let parent_id = self.parent(current)?;
@@ -311,11 +637,12 @@ impl WindowTree {
 
         match layout {
             LayoutMode::SplitH => {
-                // Horizontal split: divide width equally
-                let child_width = (geometry.width as i32 - (num_children as i32 - 1) * gap_width) / num_children as i32;
+                // Horizontal split: divide width equally, except for locked children
+                let available = geometry.width as i32 - (num_children as i32 - 1) * gap_width;
+                let sizes = split_sizes(&locked_ratios(self, &children), geometry.width as i32, available);
                 let mut x = geometry.x;
 
-                for (i, &child_id) in children.iter().enumerate() {
+                for (&child_id, &child_width) in children.iter().zip(&sizes) {
                     let child_geometry = Rectangle::new(
                         x,
                         geometry.y,
@@ -334,11 +661,12 @@ impl WindowTree {
                 }
             }
             LayoutMode::SplitV => {
-                // Vertical split: divide height equally
-                let child_height = (geometry.height as i32 - (num_children as i32 - 1) * gap_width) / num_children as i32;
+                // Vertical split: divide height equally, except for locked children
+                let available = geometry.height as i32 - (num_children as i32 - 1) * gap_width;
+                let sizes = split_sizes(&locked_ratios(self, &children), geometry.height as i32, available);
                 let mut y = geometry.y;
 
-                for (i, &child_id) in children.iter().enumerate() {
+                for (&child_id, &child_height) in children.iter().zip(&sizes) {
                     let child_geometry = Rectangle::new(
                         geometry.x,
                         y,
@@ -385,3 +713,212 @@ impl WindowTree {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_window(tree: &mut WindowTree, parent_id: NodeId, app_id: &str) -> NodeId {
+        let mut window = Container::new(NodeId::default(), ContainerType::Window);
+        window.app_id = Some(app_id.to_string());
+        let window_id = tree.insert(window);
+        tree.add_child(parent_id, window_id).unwrap();
+        window_id
+    }
+
+    #[test]
+    fn test_preset_roundtrip() {
+        let mut tree = WindowTree::new();
+        let workspace_id = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+
+        // Editor on the left, two terminals stacked on the right
+        let editor = add_window(&mut tree, workspace_id, "editor");
+        let mut right = Container::new(NodeId::default(), ContainerType::Split);
+        right.layout = LayoutMode::SplitV;
+        let right_id = tree.insert(right);
+        tree.add_child(workspace_id, right_id).unwrap();
+        let term1 = add_window(&mut tree, right_id, "foot");
+        let term2 = add_window(&mut tree, right_id, "foot");
+
+        let preset = tree.snapshot_preset(workspace_id);
+
+        // Flatten the workspace into a different arrangement
+        for id in [editor, term1, term2] {
+            let parent = tree.parent(id).unwrap();
+            tree.remove_child(parent, id);
+        }
+        tree.remove_child(workspace_id, right_id);
+        tree.remove(right_id);
+        for id in [term2, editor, term1] {
+            tree.add_child(workspace_id, id).unwrap();
+        }
+
+        tree.apply_preset(workspace_id, &preset).unwrap();
+
+        let children = tree.children(workspace_id);
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0], editor);
+
+        let split = tree.get(children[1]).unwrap();
+        assert_eq!(split.layout, LayoutMode::SplitV);
+        assert_eq!(split.children, vec![term2, term1]);
+        assert_eq!(tree.parent(term1), Some(children[1]));
+        assert_eq!(tree.snapshot_preset(workspace_id), preset);
+    }
+
+    #[test]
+    fn test_preset_restores_locked_ratios() {
+        let mut tree = WindowTree::new();
+        let workspace_id = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+        let screen = Rectangle::new(0, 0, 1200, 800);
+
+        // Chat pinned to a third, next to a split of two terminals
+        let chat = add_window(&mut tree, workspace_id, "chat");
+        tree.get_mut(chat).unwrap().locked_ratio = Some(1.0 / 3.0);
+        let mut right = Container::new(NodeId::default(), ContainerType::Split);
+        right.layout = LayoutMode::SplitV;
+        let right_id = tree.insert(right);
+        tree.add_child(workspace_id, right_id).unwrap();
+        let term1 = add_window(&mut tree, right_id, "foot");
+        tree.get_mut(term1).unwrap().locked_ratio = Some(0.25);
+        add_window(&mut tree, right_id, "foot");
+
+        let preset = tree.snapshot_preset(workspace_id);
+        assert_eq!(preset.children[0].locked_ratio, Some(1.0 / 3.0));
+        assert_eq!(preset.children[1].children[0].locked_ratio, Some(0.25));
+
+        // Unpinned windows get pinned by the preset, pinned ones released
+        tree.get_mut(chat).unwrap().locked_ratio = None;
+        tree.get_mut(term1).unwrap().locked_ratio = Some(0.5);
+        tree.apply_preset(workspace_id, &preset).unwrap();
+
+        assert_eq!(tree.get(chat).unwrap().locked_ratio, Some(1.0 / 3.0));
+        assert_eq!(tree.get(term1).unwrap().locked_ratio, Some(0.25));
+        tree.calculate_layout(workspace_id, screen, 10);
+        assert_eq!(tree.get(chat).unwrap().geometry.width, 400);
+        assert_eq!(tree.get(term1).unwrap().geometry.height, 200);
+    }
+
+    #[test]
+    fn test_wrap_and_unwrap_container() {
+        let mut tree = WindowTree::new();
+        let workspace_id = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+        let left = add_window(&mut tree, workspace_id, "left");
+        let middle = add_window(&mut tree, workspace_id, "middle");
+        let right = add_window(&mut tree, workspace_id, "right");
+
+        tree.set_focused(Some(middle));
+        let wrapper = tree.wrap_in_container(LayoutMode::SplitV).unwrap();
+
+        // Wrapper takes the window's slot; the window moves inside it
+        assert_eq!(tree.children(workspace_id), vec![left, wrapper, right]);
+        assert_eq!(tree.children(wrapper), vec![middle]);
+        assert_eq!(tree.parent(wrapper), Some(workspace_id));
+        assert_eq!(tree.parent(middle), Some(wrapper));
+        assert_eq!(tree.get(wrapper).unwrap().layout, LayoutMode::SplitV);
+        assert_eq!(tree.focused(), Some(middle));
+
+        // Workspaces can't be wrapped
+        tree.set_focused(Some(workspace_id));
+        assert!(tree.wrap_in_container(LayoutMode::SplitH).is_err());
+
+        // Unwrapping collapses the wrapper back into the workspace
+        tree.set_focused(Some(middle));
+        tree.unwrap_container().unwrap();
+        assert_eq!(tree.children(workspace_id), vec![left, middle, right]);
+        assert_eq!(tree.parent(middle), Some(workspace_id));
+        assert!(tree.get(wrapper).is_none());
+
+        // Nothing to unwrap when the parent is the workspace
+        assert!(tree.unwrap_container().is_err());
+    }
+
+    #[test]
+    fn test_locked_ratio_survives_new_sibling() {
+        let mut tree = WindowTree::new();
+        let workspace_id = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+        let screen = Rectangle::new(0, 0, 1200, 800);
+
+        let chat = add_window(&mut tree, workspace_id, "chat");
+        tree.get_mut(chat).unwrap().locked_ratio = Some(1.0 / 3.0);
+        let editor = add_window(&mut tree, workspace_id, "editor");
+
+        tree.calculate_layout(workspace_id, screen, 10);
+        assert_eq!(tree.get(chat).unwrap().geometry.width, 400);
+        assert_eq!(tree.get(editor).unwrap().geometry.width, 790);
+
+        let terminal = add_window(&mut tree, workspace_id, "terminal");
+        tree.calculate_layout(workspace_id, screen, 10);
+
+        // The locked window keeps its third; the others share the remainder
+        assert_eq!(tree.get(chat).unwrap().geometry.width, 400);
+        assert_eq!(tree.get(editor).unwrap().geometry.width, 390);
+        assert_eq!(tree.get(terminal).unwrap().geometry.width, 390);
+    }
+
+    #[test]
+    fn test_layout_ignores_zero_size_screen() {
+        let mut tree = WindowTree::new();
+        let workspace_id = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+        let editor = add_window(&mut tree, workspace_id, "editor");
+
+        tree.calculate_layout(workspace_id, Rectangle::new(0, 0, 1200, 800), 10);
+        let before = tree.get(editor).unwrap().geometry;
+
+        tree.calculate_layout(workspace_id, Rectangle::new(0, 0, 0, 800), 10);
+        tree.calculate_layout(workspace_id, Rectangle::new(0, 0, 1200, 0), 10);
+
+        assert_eq!(tree.get(editor).unwrap().geometry, before);
+        assert_eq!(tree.get(workspace_id).unwrap().geometry.width, 1200);
+    }
+
+    #[test]
+    fn test_insertion_target() {
+        let mut tree = WindowTree::new();
+        let workspace_id = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+        let other_workspace = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+        let left = add_window(&mut tree, workspace_id, "left");
+        let right = add_window(&mut tree, workspace_id, "right");
+        let dialog = add_window(&mut tree, workspace_id, "dialog");
+        tree.get_mut(dialog).unwrap().is_floating = true;
+        let elsewhere = add_window(&mut tree, other_workspace, "elsewhere");
+
+        // Nothing focused: the workspace itself
+        assert_eq!(tree.insertion_target(workspace_id), workspace_id);
+
+        // Tiled window focused: next to it, in its container
+        tree.set_focused(Some(right));
+        tree.wrap_in_container(LayoutMode::SplitV).unwrap();
+        let wrapper = tree.parent(right).unwrap();
+        assert_eq!(tree.insertion_target(workspace_id), wrapper);
+        tree.set_focused(Some(left));
+        assert_eq!(tree.insertion_target(workspace_id), workspace_id);
+
+        // Container focused: into it
+        tree.set_focused(Some(wrapper));
+        assert_eq!(tree.insertion_target(workspace_id), wrapper);
+
+        // Floating window focused: the workspace's tiling tree
+        tree.set_focused(Some(dialog));
+        assert_eq!(tree.insertion_target(workspace_id), workspace_id);
+
+        // Focus on another workspace doesn't pick a container there
+        tree.set_focused(Some(elsewhere));
+        assert_eq!(tree.insertion_target(workspace_id), workspace_id);
+    }
+
+    #[test]
+    fn test_fullscreen_window_covers_workspace() {
+        let mut tree = WindowTree::new();
+        let workspace_id = tree.insert(Container::new(NodeId::default(), ContainerType::Workspace));
+        let screen = Rectangle::new(0, 0, 1200, 800);
+        let video = add_window(&mut tree, workspace_id, "video");
+        let editor = add_window(&mut tree, workspace_id, "editor");
+
+        tree.get_mut(video).unwrap().is_fullscreen = true;
+        tree.calculate_layout(workspace_id, screen, 10);
+
+        assert_eq!(tree.get(video).unwrap().geometry, screen);
+        assert_eq!(tree.get(editor).unwrap().geometry.width, 595);
+    }
+}
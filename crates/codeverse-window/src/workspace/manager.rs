@@ -1,4 +1,5 @@
 use crate::tree::{Container, ContainerType, LayoutMode, NodeId, Rectangle, WindowTree};
+use codeverse_config::WorkspacesConfig;
 use tracing::{debug, info};
 
 /// Maximum number of workspaces
@@ -12,6 +13,14 @@ pub struct WorkspaceManager {
     /// Currently active workspace index (0-9)
     active_workspace: usize,
 
+    /// Workspace index that was active before the current one
+    previous_workspace: Option<usize>,
+
+    /// Which configured name (`workspaces.names`, 1-based) each slot shows.
+    /// Starts as the slot's own number and follows its workspace when it
+    /// moves, so the config itself stays untouched.
+    name_slots: [usize; MAX_WORKSPACES],
+
     /// Output node ID this workspace manager is attached to
     output_id: NodeId,
 }
@@ -41,6 +50,8 @@ impl WorkspaceManager {
         Self {
             workspaces,
             active_workspace: 0,
+            previous_workspace: None,
+            name_slots: std::array::from_fn(|index| index + 1),
             output_id,
         }
     }
@@ -55,6 +66,11 @@ impl WorkspaceManager {
         self.active_workspace + 1
     }
 
+    /// Get the number (1-10) of the workspace active before the current one
+    pub fn previous_workspace_num(&self) -> Option<usize> {
+        self.previous_workspace.map(|index| index + 1)
+    }
+
     /// Switch to a workspace by number (1-10)
     pub fn switch_to_workspace(&mut self, workspace_num: usize) -> Option<NodeId> {
         if workspace_num < 1 || workspace_num > MAX_WORKSPACES {
@@ -65,6 +81,9 @@ impl WorkspaceManager {
         let index = workspace_num - 1;
         info!("Switching to workspace {}", workspace_num);
 
+        if index != self.active_workspace {
+            self.previous_workspace = Some(self.active_workspace);
+        }
         self.active_workspace = index;
         self.workspaces[index]
     }
@@ -77,6 +96,18 @@ impl WorkspaceManager {
         self.workspaces[workspace_num - 1]
     }
 
+    /// Get the workspace number (1-10) containing a node
+    pub fn workspace_num_of(&self, tree: &WindowTree, node_id: NodeId) -> Option<usize> {
+        let mut current = Some(node_id);
+        while let Some(id) = current {
+            if let Some(index) = self.workspaces.iter().position(|&ws| ws == Some(id)) {
+                return Some(index + 1);
+            }
+            current = tree.parent(id);
+        }
+        None
+    }
+
     /// Move a window to a different workspace
     pub fn move_window_to_workspace(
         &mut self,
@@ -110,6 +141,42 @@ impl WorkspaceManager {
         Ok(())
     }
 
+    /// Move workspace `from` (with its windows and name) to slot `to`,
+    /// shifting the workspaces in between by one. The active and previous
+    /// workspace follow their content.
+    pub fn move_workspace(&mut self, from: usize, to: usize) -> Result<(), String> {
+        for num in [from, to] {
+            if !(1..=MAX_WORKSPACES).contains(&num) {
+                return Err(format!("Invalid workspace number: {}", num));
+            }
+        }
+
+        info!("Moving workspace {} to {}", from, to);
+
+        let (from, to) = (from - 1, to - 1);
+        if from < to {
+            self.workspaces[from..=to].rotate_left(1);
+            self.name_slots[from..=to].rotate_left(1);
+        } else {
+            self.workspaces[to..=from].rotate_right(1);
+            self.name_slots[to..=from].rotate_right(1);
+        }
+
+        self.active_workspace = shifted_index(self.active_workspace, from, to);
+        self.previous_workspace = self.previous_workspace.map(|index| shifted_index(index, from, to));
+
+        Ok(())
+    }
+
+    /// Name of workspace `workspace_num` (1-10): the configured name it
+    /// started out with, wherever it has been moved since
+    pub fn workspace_name(&self, workspace_num: usize, config: &WorkspacesConfig) -> String {
+        match workspace_num.checked_sub(1).and_then(|index| self.name_slots.get(index)) {
+            Some(&name_slot) => config.name_for(name_slot),
+            None => config.name_for(workspace_num),
+        }
+    }
+
     /// Get all workspace IDs
     pub fn all_workspaces(&self) -> Vec<Option<NodeId>> {
         self.workspaces.to_vec()
@@ -145,6 +212,23 @@ impl WorkspaceManager {
         }
     }
 
+    /// Lay out a workspace that isn't necessarily active (e.g. the one a
+    /// workspace switch animates away from)
+    pub fn layout_workspace(&self, tree: &mut WindowTree, workspace_num: usize, screen_geometry: Rectangle, gap_width: i32) {
+        if let Some(workspace_id) = self.get_workspace(workspace_num) {
+            use crate::tree::WindowTreeExt;
+            tree.calculate_layout(workspace_id, screen_geometry, gap_width);
+        }
+    }
+
+    /// Get the windows of any workspace
+    pub fn workspace_windows(&self, tree: &WindowTree, workspace_num: usize) -> Vec<NodeId> {
+        match self.get_workspace(workspace_num) {
+            Some(workspace_id) => self.collect_windows_recursive(tree, workspace_id),
+            None => vec![],
+        }
+    }
+
     /// Get list of visible windows on active workspace
     pub fn visible_windows(&self, tree: &WindowTree) -> Vec<NodeId> {
         let workspace_id = match self.active_workspace() {
@@ -173,6 +257,44 @@ impl WorkspaceManager {
     }
 }
 
+/// Switch workspace on a set of outputs, one manager per output.
+///
+/// Per-output (i3-like) switching only changes the `focused` output;
+/// `global` switching (GNOME-like) moves every output to `workspace_num`.
+/// Returns the focused output's new workspace; nothing switches if
+/// `focused` isn't one of the outputs.
+pub fn switch_outputs_to_workspace(
+    managers: &mut [WorkspaceManager],
+    focused: usize,
+    workspace_num: usize,
+    global: bool,
+) -> Option<NodeId> {
+    if focused >= managers.len() {
+        return None;
+    }
+    if global {
+        for (index, manager) in managers.iter_mut().enumerate() {
+            if index != focused {
+                manager.switch_to_workspace(workspace_num);
+            }
+        }
+    }
+    managers.get_mut(focused)?.switch_to_workspace(workspace_num)
+}
+
+/// New index of slot `index` after the slot at `from` moved to `to`
+fn shifted_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < to && (from + 1..=to).contains(&index) {
+        index - 1
+    } else if to < from && (to..from).contains(&index) {
+        index + 1
+    } else {
+        index
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,4 +321,97 @@ mod tests {
         // Test invalid workspace
         assert!(manager.switch_to_workspace(11).is_none());
     }
+
+    #[test]
+    fn test_previous_workspace() {
+        let mut tree = WindowTree::new();
+        let output_id = tree.insert(Container::new(NodeId::default(), ContainerType::Output));
+        let mut manager = WorkspaceManager::new(&mut tree, output_id);
+        assert_eq!(manager.previous_workspace_num(), None);
+
+        manager.switch_to_workspace(5);
+        assert_eq!(manager.previous_workspace_num(), Some(1));
+
+        // Re-selecting the active workspace keeps the previous one
+        manager.switch_to_workspace(5);
+        assert_eq!(manager.previous_workspace_num(), Some(1));
+    }
+
+    #[test]
+    fn test_global_workspace_switch() {
+        let mut tree = WindowTree::new();
+        let mut managers: Vec<WorkspaceManager> = (0..2)
+            .map(|_| {
+                let output_id = tree.insert(Container::new(NodeId::default(), ContainerType::Output));
+                WorkspaceManager::new(&mut tree, output_id)
+            })
+            .collect();
+
+        // Per output: only the focused output switches
+        let switched = switch_outputs_to_workspace(&mut managers, 1, 3, false);
+        assert_eq!(switched, managers[1].get_workspace(3));
+        assert_eq!(managers[0].active_workspace_num(), 1);
+        assert_eq!(managers[1].active_workspace_num(), 3);
+
+        // Global: every output switches together
+        switch_outputs_to_workspace(&mut managers, 0, 5, true);
+        assert!(managers.iter().all(|manager| manager.active_workspace_num() == 5));
+
+        // An unknown focused output switches nothing
+        assert!(switch_outputs_to_workspace(&mut managers, 2, 4, true).is_none());
+        assert!(managers.iter().all(|manager| manager.active_workspace_num() == 5));
+    }
+
+    #[test]
+    fn test_move_workspace() {
+        let mut tree = WindowTree::new();
+        let output_id = tree.insert(Container::new(NodeId::default(), ContainerType::Output));
+        let mut manager = WorkspaceManager::new(&mut tree, output_id);
+
+        let window = tree.insert(Container::new(NodeId::default(), ContainerType::Window));
+        tree.add_child(manager.get_workspace(5).unwrap(), window).unwrap();
+        let second = manager.get_workspace(2);
+        manager.switch_to_workspace(5);
+        manager.switch_to_workspace(3);
+
+        manager.move_workspace(5, 2).unwrap();
+
+        // The window moved along with its workspace; the others shifted up
+        assert_eq!(manager.workspace_num_of(&tree, window), Some(2));
+        assert_eq!(manager.workspace_windows(&tree, 2), vec![window]);
+        assert_eq!(manager.get_workspace(3), second);
+        assert_eq!(manager.active_workspace_num(), 4);
+        assert_eq!(manager.previous_workspace_num(), Some(2));
+
+        // And back again
+        manager.move_workspace(2, 5).unwrap();
+        assert_eq!(manager.workspace_num_of(&tree, window), Some(5));
+        assert_eq!(manager.get_workspace(2), second);
+        assert_eq!(manager.active_workspace_num(), 3);
+
+        assert!(manager.move_workspace(0, 2).is_err());
+        assert!(manager.move_workspace(2, 11).is_err());
+    }
+
+    #[test]
+    fn test_move_workspace_name() {
+        let mut tree = WindowTree::new();
+        let output_id = tree.insert(Container::new(NodeId::default(), ContainerType::Output));
+        let mut manager = WorkspaceManager::new(&mut tree, output_id);
+        let mut config = WorkspacesConfig {
+            names: vec!["web".to_string(), "code".to_string()],
+            ..Default::default()
+        };
+
+        // Names travel with their workspace; the config is only read
+        manager.move_workspace(1, 4).unwrap();
+        let names: Vec<String> = (1..=5).map(|num| manager.workspace_name(num, &config)).collect();
+        assert_eq!(names, vec!["code", "3", "4", "web", "5"]);
+        assert_eq!(config.names, vec!["web", "code"]);
+
+        // A reloaded config renames the moved workspace where it is now
+        config.names[0] = "mail".to_string();
+        assert_eq!(manager.workspace_name(4, &config), "mail");
+        assert_eq!(manager.workspace_name(11, &config), "11");
+    }
 }
@@ -1,3 +1,7 @@
 pub mod manager;
+pub mod overview;
+pub mod taskbar;
 
-pub use manager::{WorkspaceManager, MAX_WORKSPACES};
+pub use manager::{switch_outputs_to_workspace, WorkspaceManager, MAX_WORKSPACES};
+pub use overview::{overview_columns, overview_grid, scale_rect_into};
+pub use taskbar::{taskbar_button_at, taskbar_buttons, taskbar_strip};
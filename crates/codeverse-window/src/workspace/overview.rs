@@ -0,0 +1,127 @@
+use crate::tree::Rectangle;
+
+/// Number of grid columns used to show `count` workspaces in the overview
+pub fn overview_columns(count: usize) -> usize {
+    let mut columns = 1;
+    while columns * columns < count {
+        columns += 1;
+    }
+    columns
+}
+
+/// Compute the overview grid cells for `count` workspaces.
+///
+/// Cells are placed row-major in a near-square grid, keep the aspect
+/// ratio of `area`, are separated by `gap` pixels and the whole grid is
+/// centered within `area`.
+pub fn overview_grid(count: usize, area: Rectangle, gap: u32) -> Vec<Rectangle> {
+    if count == 0 || area.width == 0 || area.height == 0 {
+        return Vec::new();
+    }
+
+    let columns = overview_columns(count);
+    let rows = count.div_ceil(columns);
+    let gap = gap as i64;
+
+    let max_cell_width = (area.width as i64 - gap * (columns as i64 + 1)) / columns as i64;
+    let max_cell_height = (area.height as i64 - gap * (rows as i64 + 1)) / rows as i64;
+    if max_cell_width <= 0 || max_cell_height <= 0 {
+        return Vec::new();
+    }
+
+    // Scale the output down uniformly so each cell looks like a small screen
+    let scale = (max_cell_width as f64 / area.width as f64)
+        .min(max_cell_height as f64 / area.height as f64);
+    let cell_width = (area.width as f64 * scale) as i64;
+    let cell_height = (area.height as f64 * scale) as i64;
+
+    let grid_width = columns as i64 * cell_width + (columns as i64 - 1) * gap;
+    let grid_height = rows as i64 * cell_height + (rows as i64 - 1) * gap;
+    let origin_x = area.x as i64 + (area.width as i64 - grid_width) / 2;
+    let origin_y = area.y as i64 + (area.height as i64 - grid_height) / 2;
+
+    (0..count)
+        .map(|i| {
+            let column = (i % columns) as i64;
+            let row = (i / columns) as i64;
+            Rectangle::new(
+                (origin_x + column * (cell_width + gap)) as i32,
+                (origin_y + row * (cell_height + gap)) as i32,
+                cell_width as u32,
+                cell_height as u32,
+            )
+        })
+        .collect()
+}
+
+/// Map a rectangle from `from` coordinates into the `to` rectangle,
+/// scaling it proportionally (used to draw miniature window layouts)
+pub fn scale_rect_into(rect: Rectangle, from: Rectangle, to: Rectangle) -> Rectangle {
+    if from.width == 0 || from.height == 0 {
+        return Rectangle::new(to.x, to.y, 0, 0);
+    }
+
+    let scale_x = to.width as f64 / from.width as f64;
+    let scale_y = to.height as f64 / from.height as f64;
+
+    Rectangle::new(
+        to.x + ((rect.x - from.x) as f64 * scale_x) as i32,
+        to.y + ((rect.y - from.y) as f64 * scale_y) as i32,
+        (rect.width as f64 * scale_x) as u32,
+        (rect.height as f64 * scale_y) as u32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overview_grid_placement() {
+        let screen = Rectangle::new(0, 0, 1920, 1080);
+
+        assert!(overview_grid(0, screen, 40).is_empty());
+
+        // A single workspace is centered
+        let cells = overview_grid(1, screen, 40);
+        assert_eq!(cells.len(), 1);
+        let cell = cells[0];
+        assert!((cell.x * 2 + cell.width as i32 - 1920).abs() <= 1);
+        assert!((cell.y * 2 + cell.height as i32 - 1080).abs() <= 1);
+
+        // Five workspaces: 3 columns, 2 rows, row-major
+        assert_eq!(overview_columns(5), 3);
+        let cells = overview_grid(5, screen, 40);
+        assert_eq!(cells.len(), 5);
+        assert_eq!(cells[0].y, cells[2].y);
+        assert!(cells[0].x < cells[1].x && cells[1].x < cells[2].x);
+        assert_eq!(cells[3].x, cells[0].x);
+        assert!(cells[3].y > cells[0].y);
+
+        for (i, a) in cells.iter().enumerate() {
+            // Same size, screen aspect ratio, inside the screen
+            assert_eq!((a.width, a.height), (cells[0].width, cells[0].height));
+            assert!((a.width as i32 * 9 / 16 - a.height as i32).abs() <= 1);
+            assert!(a.x >= 0 && a.x + a.width as i32 <= 1920);
+            assert!(a.y >= 0 && a.y + a.height as i32 <= 1080);
+
+            // No overlaps
+            for b in &cells[i + 1..] {
+                let disjoint = a.x + a.width as i32 <= b.x
+                    || b.x + b.width as i32 <= a.x
+                    || a.y + a.height as i32 <= b.y
+                    || b.y + b.height as i32 <= a.y;
+                assert!(disjoint, "{:?} overlaps {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scale_rect_into() {
+        let screen = Rectangle::new(0, 0, 1920, 1080);
+        let cell = Rectangle::new(100, 100, 480, 270);
+
+        let window = Rectangle::new(960, 0, 960, 1080);
+        assert_eq!(scale_rect_into(window, screen, cell), Rectangle::new(340, 100, 240, 270));
+    }
+}
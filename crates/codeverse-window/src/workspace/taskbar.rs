@@ -0,0 +1,85 @@
+use crate::tree::Rectangle;
+
+/// Space between taskbar buttons and around them
+pub const TASKBAR_BUTTON_GAP: u32 = 4;
+
+/// Widest a taskbar button gets when there are only a few windows
+pub const TASKBAR_BUTTON_MAX_WIDTH: u32 = 200;
+
+/// The taskbar strip along the top or bottom edge of `screen`
+pub fn taskbar_strip(screen: Rectangle, at_top: bool, height: u32) -> Rectangle {
+    let height = height.min(screen.height);
+    let y = if at_top {
+        screen.y
+    } else {
+        screen.y + (screen.height - height) as i32
+    };
+    Rectangle::new(screen.x, y, screen.width, height)
+}
+
+/// Lay out `count` buttons left to right in the taskbar `strip`.
+///
+/// Buttons share the strip's width evenly (up to
+/// [`TASKBAR_BUTTON_MAX_WIDTH`]) and are inset by [`TASKBAR_BUTTON_GAP`].
+pub fn taskbar_buttons(strip: Rectangle, count: usize) -> Vec<Rectangle> {
+    let gap = TASKBAR_BUTTON_GAP as i64;
+    if count == 0 || strip.height as i64 <= gap * 2 {
+        return Vec::new();
+    }
+
+    let width = ((strip.width as i64 - gap * (count as i64 + 1)) / count as i64)
+        .min(TASKBAR_BUTTON_MAX_WIDTH as i64);
+    if width <= 0 {
+        return Vec::new();
+    }
+
+    (0..count as i64)
+        .map(|i| {
+            Rectangle::new(
+                (strip.x as i64 + gap + i * (width + gap)) as i32,
+                strip.y + gap as i32,
+                width as u32,
+                (strip.height as i64 - gap * 2) as u32,
+            )
+        })
+        .collect()
+}
+
+/// Index of the taskbar button under (`x`, `y`), if any
+pub fn taskbar_button_at(buttons: &[Rectangle], x: i32, y: i32) -> Option<usize> {
+    buttons.iter().position(|button| button.contains_point(x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_taskbar_hit_regions() {
+        let screen = Rectangle::new(0, 0, 1920, 1080);
+        let strip = taskbar_strip(screen, false, 32);
+        assert_eq!(strip, Rectangle::new(0, 1048, 1920, 32));
+        assert_eq!(taskbar_strip(screen, true, 32).y, 0);
+
+        // A few windows get full-width buttons packed to the left
+        let buttons = taskbar_buttons(strip, 3);
+        assert_eq!(buttons[0], Rectangle::new(4, 1052, 200, 24));
+        assert_eq!(buttons[1].x, 208);
+
+        assert_eq!(taskbar_button_at(&buttons, 10, 1060), Some(0));
+        assert_eq!(taskbar_button_at(&buttons, 420, 1075), Some(2));
+        // Gaps between and past the buttons hit nothing
+        assert_eq!(taskbar_button_at(&buttons, 206, 1060), None);
+        assert_eq!(taskbar_button_at(&buttons, 1000, 1060), None);
+        assert_eq!(taskbar_button_at(&buttons, 10, 1049), None);
+
+        // Many windows shrink the buttons to fit the strip
+        let crowded = taskbar_buttons(strip, 40);
+        assert_eq!(crowded.len(), 40);
+        let last = crowded[39];
+        assert!(last.x + last.width as i32 <= 1920);
+        assert!(crowded[0].width < TASKBAR_BUTTON_MAX_WIDTH);
+
+        assert!(taskbar_buttons(strip, 0).is_empty());
+    }
+}